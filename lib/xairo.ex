@@ -296,9 +296,17 @@ defmodule Xairo do
           | :hsl_color
           | :hsl_luminosity
 
+  @typedoc """
+  Valid options for `Xairo.sketch_stroke/3` and `Xairo.sketch_fill/3`
+  """
+  @type sketch_option ::
+          {:roughness | :bowing | :hachure_angle | :hachure_gap | :fill_weight, number()}
+          | {:seed, non_neg_integer()}
+
   alias Xairo.{
     FontFace,
     Image,
+    Layout,
     LinearGradient,
     Matrix,
     Mesh,
@@ -309,6 +317,7 @@ defmodule Xairo do
     SolidPattern,
     SurfacePattern,
     SvgSurface,
+    TextExtents,
     Vector
   }
 
@@ -357,6 +366,45 @@ defmodule Xairo do
     image
   end
 
+  @doc """
+  Draws an elliptical arc from the current point to `point`, specified as in
+  an SVG path's `A` command.
+
+  In addition to the `Xairo.Image` struct, this function takes the following
+  arguments
+
+  * the `{rx, ry}` radii of the ellipse
+  * the rotation of the ellipse's x-axis (given in radians)
+  * whether to draw the larger of the two arcs between the points
+  * whether to draw the arc that runs clockwise from the current point
+  * the point to end the arc at
+
+  Radii too small for the ellipse to reach from the current point to `point`
+  are scaled up until it does, and a zero radius draws a straight line. If
+  there is no current point, this behaves as `Xairo.move_to/2`.
+  """
+  @doc section: :drawing
+  @spec arc_to(
+          image(),
+          {number(), number()},
+          number(),
+          boolean(),
+          boolean(),
+          Xairo.point()
+        ) :: Xairo.or_error(image())
+  def arc_to(%Image{context: ctx} = image, {rx, ry}, rotation, large_arc, sweep, point) do
+    with {:ok, _} <-
+           N.context_arc_to(
+             ctx.context,
+             {rx / 1, ry / 1},
+             rotation / 1,
+             large_arc,
+             sweep,
+             Point.from(point)
+           ),
+         do: image
+  end
+
   @doc """
   Closes the path by drawing a straight line from the current point to the path's origin.
   """
@@ -399,6 +447,20 @@ defmodule Xairo do
     image
   end
 
+  @doc """
+  Draws a closed ellipse around the given center point, with the given
+  horizontal and vertical radii.
+
+  Like `Xairo.rectangle/4`, the ellipse is drawn as a new subpath, starting
+  from its rightmost point and running clockwise.
+  """
+  @doc section: :drawing
+  @spec ellipse(image(), Xairo.point(), number(), number()) :: image()
+  def ellipse(%Image{context: ctx} = image, center, rx, ry) do
+    N.context_ellipse(ctx.context, Point.from(center), rx / 1, ry / 1)
+    image
+  end
+
   @doc """
   Renders the current path to the context's surface by filling it in.
 
@@ -413,6 +475,21 @@ defmodule Xairo do
     with {:ok, _} <- N.context_fill(ctx.context), do: image
   end
 
+  @doc """
+  Fills markers, such as arrowheads, at the vertices of the current path,
+  sized for the current line width, and preserves the current path so that
+  it can be stroked.
+
+  Takes the same `start`, `mid`, `end` and `size` options as
+  `Xairo.Path.markers/2`, which describes where the markers are placed and
+  how they are turned.
+  """
+  @doc section: :drawing
+  @spec fill_markers(image(), [Path.marker_option()]) :: Xairo.or_error(image())
+  def fill_markers(%Image{context: ctx} = image, opts \\ []) do
+    with {:ok, _} <- N.context_fill_markers(ctx.context, Path.marker_style(opts)), do: image
+  end
+
   @doc """
   Identical to `Xairo.fill/1` but preserves the current path.
   """
@@ -494,6 +571,20 @@ defmodule Xairo do
     with {:ok, _} <- N.context_paint_with_alpha(ctx.context, alpha / 1), do: image
   end
 
+  @doc """
+  Draws a quadratic Bézier curve from the current point to `point`, with the
+  given control point.
+
+  If there is no current point, the curve starts from the control point, as
+  `Xairo.curve_to/4` does.
+  """
+  @doc section: :drawing
+  @spec quad_to(image(), Xairo.point(), Xairo.point()) :: Xairo.or_error(image())
+  def quad_to(%Image{context: ctx} = image, control, point) do
+    with {:ok, _} <- N.context_quad_to(ctx.context, Point.from(control), Point.from(point)),
+         do: image
+  end
+
   @doc """
   Draws a rectangle based on the given arguments.
 
@@ -514,6 +605,31 @@ defmodule Xairo do
     image
   end
 
+  @doc """
+  Draws a closed regular polygon with the given number of sides, its corners
+  lying on a circle with the given center and radius.
+
+  See `Xairo.Path.regular_polygon/4` for the options this takes. Draws
+  nothing for fewer than 3 sides, and returns `{:error, :invalid_size}` for
+  more than 65536.
+  """
+  @doc section: :drawing
+  @spec regular_polygon(image(), Xairo.point(), number(), pos_integer(), [Path.shape_option()]) ::
+          Xairo.or_error(image())
+  def regular_polygon(%Image{context: ctx} = image, center, radius, sides, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+
+    with {:ok, _} <-
+           N.context_regular_polygon(
+             ctx.context,
+             Point.from(center),
+             radius / 1,
+             sides,
+             rotation / 1
+           ),
+         do: image
+  end
+
   @doc """
   Draws a cubic Bézier curve with the given control points relative to the path's current point.
 
@@ -549,6 +665,81 @@ defmodule Xairo do
     image
   end
 
+  @doc """
+  Draws a quadratic Bézier curve with its control point and end point given
+  relative to the path's current point.
+  """
+  @doc section: :drawing
+  @spec rel_quad_to(image(), Xairo.vector(), Xairo.vector()) :: image()
+  def rel_quad_to(%Image{context: ctx} = image, vec1, vec2) do
+    N.context_rel_quad_to(ctx.context, Vector.from(vec1), Vector.from(vec2))
+    image
+  end
+
+  @doc """
+  Draws a closed rose curve with the given center and radius, and the
+  petal-count `k`, given as an integer or as an `{n, d}` tuple for `n / d`.
+
+  See `Xairo.Path.rose/4` for the shape drawn and the options this takes.
+  """
+  @doc section: :drawing
+  @spec rose(
+          image(),
+          Xairo.point(),
+          number(),
+          non_neg_integer() | {non_neg_integer(), pos_integer()},
+          [Path.shape_option()]
+        ) :: Xairo.or_error(image())
+  def rose(%Image{context: ctx} = image, center, radius, k, opts \\ []) do
+    {n, d} = if is_integer(k), do: {k, 1}, else: k
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, _} <-
+           N.context_rose(
+             ctx.context,
+             Point.from(center),
+             radius / 1,
+             {n, d},
+             rotation / 1,
+             tolerance / 1
+           ),
+         do: image
+  end
+
+  @doc """
+  Draws a rectangle with rounded corners.
+
+  Takes the same arguments as `Xairo.rectangle/4`, followed by the radius of
+  the corners. This can be a single number for all four corners, or a
+  4-element tuple giving a radius for each corner, starting with the one at
+  the origin and going clockwise.
+
+  As in CSS, where the radii at either end of a side would add up to more
+  than its length, all of the radii are scaled down until they fit.
+  """
+  @doc section: :drawing
+  @spec rounded_rectangle(
+          image(),
+          Xairo.point(),
+          number(),
+          number(),
+          number() | {number(), number(), number(), number()}
+        ) :: image()
+  def rounded_rectangle(%Image{context: ctx} = image, origin, width, height, radii) do
+    {r1, r2, r3, r4} = if is_number(radii), do: {radii, radii, radii, radii}, else: radii
+
+    N.context_rounded_rectangle(
+      ctx.context,
+      Point.from(origin),
+      width / 1,
+      height / 1,
+      {r1 / 1, r2 / 1, r3 / 1, r4 / 1}
+    )
+
+    image
+  end
+
   @doc """
   Sets the current color data source for the context. This can be one of any
   struct defined as part of `t:Xairo.color_source/0`.
@@ -618,6 +809,69 @@ defmodule Xairo do
     end
   end
 
+  @doc """
+  Renders a `Xairo.Path` as if filled by hand, with rough hachure lines
+  drawn across the area it encloses in the current source.
+
+  The hachure lines are clipped to the path according to the current fill
+  rule, and are each drawn twice, in the same hand-drawn style as
+  `Xairo.sketch_stroke/3`, with the current line cap and join. As well as
+  that function's `roughness`, `bowing` and `seed` options, this takes:
+
+  * `hachure_angle` - the angle of the hachure lines, in radians. Defaults to
+    -41 degrees.
+  * `hachure_gap` - the distance between hachure lines. Defaults to four
+    times the current line width.
+  * `fill_weight` - the width the hachure lines are drawn with. Defaults to
+    half the current line width.
+
+  The current path is left as it was. Returns `{:error, :invalid_size}` if
+  the gap is not positive, or if more than 65536 hachure lines would cross
+  the path.
+  """
+  @doc section: :drawing
+  @spec sketch_fill(image(), Path.t(), [sketch_option()]) :: Xairo.or_error(image())
+  def sketch_fill(%Image{context: ctx} = image, %Path{path: path}, opts \\ []) do
+    with {:ok, _} <- N.context_sketch_fill(ctx.context, path, sketch_style(opts)), do: image
+  end
+
+  @doc """
+  Renders a `Xairo.Path` as if stroked by hand, with the current source and
+  line settings.
+
+  Each line and curve of the path is drawn twice, with its points moved by
+  small random amounts, and each line bowed slightly to one side. Takes the
+  following options:
+
+  * `roughness` - how far the points are moved, with `0` drawing every
+    stroke exactly along the path. Defaults to `1`.
+  * `bowing` - how far lines bow to the side, in proportion to their length.
+    Defaults to `1`.
+  * `seed` - the non-negative integer the random amounts are generated from.
+    The same seed always draws the same strokes. Defaults to `0`.
+
+  The current path is left as it was.
+  """
+  @doc section: :drawing
+  @spec sketch_stroke(image(), Path.t(), [sketch_option()]) :: Xairo.or_error(image())
+  def sketch_stroke(%Image{context: ctx} = image, %Path{path: path}, opts \\ []) do
+    with {:ok, _} <- N.context_sketch_stroke(ctx.context, path, sketch_style(opts)), do: image
+  end
+
+  defp sketch_style(opts) do
+    %{
+      roughness: Keyword.get(opts, :roughness, 1) / 1,
+      bowing: Keyword.get(opts, :bowing, 1) / 1,
+      seed: Keyword.get(opts, :seed, 0),
+      hachure_angle: Keyword.get(opts, :hachure_angle, -41 * :math.pi() / 180) / 1,
+      hachure_gap: float_or_nil(Keyword.get(opts, :hachure_gap)),
+      fill_weight: float_or_nil(Keyword.get(opts, :fill_weight))
+    }
+  end
+
+  defp float_or_nil(nil), do: nil
+  defp float_or_nil(number), do: number / 1
+
   @doc """
   Returns the currently set color data source for the context.
   """
@@ -625,6 +879,58 @@ defmodule Xairo do
   @spec source(image()) :: Xairo.color_source()
   def source(%Image{context: ctx}), do: ctx.source
 
+  @doc """
+  Draws an open Archimedean spiral around the given center, moving steadily
+  out from the first of the `{inner, outer}` radii to the second over the
+  given number of turns.
+
+  See `Xairo.Path.spiral/4` for the options this takes.
+  """
+  @doc section: :drawing
+  @spec spiral(image(), Xairo.point(), {number(), number()}, number(), [Path.shape_option()]) ::
+          Xairo.or_error(image())
+  def spiral(%Image{context: ctx} = image, center, {inner, outer}, turns, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, _} <-
+           N.context_spiral(
+             ctx.context,
+             Point.from(center),
+             {inner / 1, outer / 1},
+             turns / 1,
+             rotation / 1,
+             tolerance / 1
+           ),
+         do: image
+  end
+
+  @doc """
+  Draws a closed star with the given number of points, its tips at the outer
+  radius from the given center and the corners between them at the inner
+  radius.
+
+  See `Xairo.Path.star/5` for the options this takes. Draws nothing for fewer
+  than 2 points, and returns `{:error, :invalid_size}` for more than 32768.
+  """
+  @doc section: :drawing
+  @spec star(image(), Xairo.point(), number(), number(), pos_integer(), [Path.shape_option()]) ::
+          Xairo.or_error(image())
+  def star(%Image{context: ctx} = image, center, outer, inner, points, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+
+    with {:ok, _} <-
+           N.context_star(
+             ctx.context,
+             Point.from(center),
+             outer / 1,
+             inner / 1,
+             points,
+             rotation / 1
+           ),
+         do: image
+  end
+
   @doc """
   Renders the current path to the context's surface by drawing along the path.
 
@@ -647,6 +953,37 @@ defmodule Xairo do
     with {:ok, _} <- N.context_stroke_preserve(ctx.context), do: image
   end
 
+  @doc """
+  Draws a closed superellipse around the given center, with the given
+  `{rx, ry}` radii and exponent.
+
+  See `Xairo.Path.superellipse/4` for the shape drawn and the options this
+  takes.
+  """
+  @doc section: :drawing
+  @spec superellipse(
+          image(),
+          Xairo.point(),
+          {number(), number()},
+          number(),
+          [Path.shape_option()]
+        ) :: image()
+  def superellipse(%Image{context: ctx} = image, center, {rx, ry}, exponent, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    N.context_superellipse(
+      ctx.context,
+      Point.from(center),
+      {rx / 1, ry / 1},
+      exponent / 1,
+      rotation / 1,
+      tolerance / 1
+    )
+
+    image
+  end
+
   @doc """
   Returns the surface from which the context was instantiated.
 
@@ -1115,7 +1452,7 @@ defmodule Xairo do
   defdelegate font_extents(image), to: Xairo.FontExtents, as: :for
 
   @doc section: :calc
-  defdelegate text_extents(image, text), to: Xairo.TextExtents, as: :for
+  defdelegate text_extents(image, text, opts \\ []), to: Xairo.TextExtents, as: :for
 
   #########
   # TEXT #
@@ -1126,13 +1463,32 @@ defmodule Xairo do
 
   This function will immediately render the text and move the current point
   to the end of its output. It does not affect the current path.
+
+  Cairo's text API places each character at the end of the previous one's
+  advance, without kerning. Passing any of the options described in
+  `t:Xairo.TextExtents.spacing_option/0` instead lays the text out with the
+  given letter and word spacing, applying the font's kerning unless
+  `kerning: false` is given, and optionally from top to bottom.
+
+      show_text(image, "AVATAR", letter_spacing: 2, kerning: false)
+
+  The extents of the text as drawn can be found by calling
+  `Xairo.text_extents/3` with the same options.
   """
   @doc section: :text
-  @spec show_text(image(), String.t()) :: Xairo.or_error(image())
-  def show_text(%Image{context: ctx} = image, text) do
+  @spec show_text(image(), String.t(), [Xairo.TextExtents.spacing_option()]) ::
+          Xairo.or_error(image())
+  def show_text(image, text, opts \\ [])
+
+  def show_text(%Image{context: ctx} = image, text, []) do
     with {:ok, _} <- N.context_show_text(ctx.context, text), do: image
   end
 
+  def show_text(%Image{context: ctx} = image, text, opts) do
+    with {:ok, _} <- N.text_spacing_show_text(ctx.context, text, TextExtents.spacing(opts)),
+         do: image
+  end
+
   @doc """
   Sets the font size for the context and surface.
   """
@@ -1150,15 +1506,144 @@ defmodule Xairo do
   until a call to `Xairo.fill/1` or `Xairo.stroke/1` is made.
 
   Calling this function followed by `Xairo.fill/1` is equivalent
-  to calling `Xairo.show_text/2` with the same text.
+  to calling `Xairo.show_text/3` with the same text and options.
   """
   @doc section: :text
-  @spec text_path(image(), String.t()) :: image()
-  def text_path(%Image{context: ctx} = image, text) do
+  @spec text_path(image(), String.t(), [Xairo.TextExtents.spacing_option()]) ::
+          Xairo.or_error(image())
+  def text_path(image, text, opts \\ [])
+
+  def text_path(%Image{context: ctx} = image, text, []) do
     N.context_text_path(ctx.context, text)
     image
   end
 
+  def text_path(%Image{context: ctx} = image, text, opts) do
+    with {:ok, _} <- N.text_spacing_text_path(ctx.context, text, TextExtents.spacing(opts)),
+         do: image
+  end
+
+  @doc """
+  Returns the outline of each glyph in the given text, in the context's
+  current font, as a separate `Xairo.Path`.
+
+  Each glyph is returned as a tuple of
+
+  * the text of the character, or cluster of characters, the glyph renders
+  * the glyph's index in the font
+  * the glyph's origin, as a `Xairo.Point`
+  * the glyph's advance, as a `Xairo.Vector`
+  * the glyph's outline, as a `Xairo.Path`
+
+  Glyphs are positioned as they would be by `Xairo.text_path/2`, starting at
+  the current point (or `{0, 0}` if there is no current point), so appending
+  all of the paths recreates the outline of the whole text. This function
+  does not affect the current path.
+  """
+  @doc section: :text
+  @spec glyph_paths(image(), String.t()) ::
+          Xairo.or_error([{String.t(), integer(), Point.t(), Vector.t(), Path.t()}])
+  def glyph_paths(%Image{context: ctx}, text) do
+    with {:ok, glyphs} <- N.context_glyph_paths(ctx.context, text) do
+      Enum.map(glyphs, fn {cluster, index, origin, advance, path} ->
+        {cluster, index, origin, advance, Path.from(path)}
+      end)
+    end
+  end
+
+  @doc """
+  Renders the given text along a `Xairo.Path`, such as one returned by
+  `Xairo.copy_path/1`, using the current font and source.
+
+  Each glyph is placed on the path at the arc length its advance would
+  have it at if the text were drawn in a straight line, and rotated to
+  follow the path's direction at that point. Arc lengths along curves are
+  measured to within the context's tolerance, as `Xairo.Path.point_at_length/3`
+  measures them, and points are found on the curves themselves rather than
+  on the path flattened as `Xairo.copy_path_flat/1` flattens it. Glyphs that
+  would fall off either end of the path are not drawn.
+
+  In addition to the image, path, and text, this function takes
+
+  * `offset` - the distance along the path at which to start the text,
+    relative to the alignment. Defaults to `0`.
+  * `alignment` - one of `:left`, `:center` or `:right`, aligning the text
+    with the start, middle, or end of the path. Defaults to `:left`.
+
+  Like `Xairo.show_text/2`, this renders the text immediately. It does not
+  affect the current path or current point.
+  """
+  @doc section: :text
+  @spec show_text_along_path(image(), Path.t(), String.t(), number(), Layout.alignment()) ::
+          Xairo.or_error(image())
+  def show_text_along_path(
+        %Image{context: ctx} = image,
+        %Path{path: path},
+        text,
+        offset \\ 0,
+        alignment \\ :left
+      ) do
+    with {:ok, _} <-
+           N.context_show_text_along_path(ctx.context, path, text, offset / 1, alignment),
+         do: image
+  end
+
+  @doc """
+  Adds the outlines of the given text, laid out along a `Xairo.Path`, to
+  the current path.
+
+  The text is positioned exactly as in `Xairo.show_text_along_path/5`, but,
+  as with `Xairo.text_path/2`, nothing is rendered until a call to
+  `Xairo.fill/1` or `Xairo.stroke/1` is made.
+  """
+  @doc section: :text
+  @spec text_path_along_path(image(), Path.t(), String.t(), number(), Layout.alignment()) ::
+          Xairo.or_error(image())
+  def text_path_along_path(
+        %Image{context: ctx} = image,
+        %Path{path: path},
+        text,
+        offset \\ 0,
+        alignment \\ :left
+      ) do
+    with {:ok, _} <-
+           N.context_text_path_along_path(ctx.context, path, text, offset / 1, alignment),
+         do: image
+  end
+
+  @doc """
+  Renders a `Xairo.Layout` to the surface, with its top left corner at
+  the current point.
+
+  Like `Xairo.show_text/2`, the layout is rendered immediately using the
+  current source, but unlike `Xairo.show_text/2` the current point is not
+  advanced.
+
+  Requires the `pango` feature. See `Xairo.Layout` for details.
+  """
+  @doc section: :text
+  @spec show_layout(image(), Layout.t()) :: image()
+  def show_layout(%Image{context: ctx} = image, %Layout{layout: layout}) do
+    N.layout_show(layout, ctx.context)
+    image
+  end
+
+  @doc """
+  Adds the outlines of the text in a `Xairo.Layout` to the current path,
+  with the layout's top left corner at the current point.
+
+  This is to `Xairo.show_layout/2` what `Xairo.text_path/2` is to
+  `Xairo.show_text/2`.
+
+  Requires the `pango` feature. See `Xairo.Layout` for details.
+  """
+  @doc section: :text
+  @spec layout_path(image(), Layout.t()) :: image()
+  def layout_path(%Image{context: ctx} = image, %Layout{layout: layout}) do
+    N.layout_path(layout, ctx.context)
+    image
+  end
+
   @doc """
   Returns the current font face for the context.
   """
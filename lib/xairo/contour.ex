@@ -0,0 +1,102 @@
+defmodule Xairo.Contour do
+  @moduledoc """
+  Traces contours of a grid of values, such as a height map or one channel
+  of an image, by marching squares, for topographic drawings and plots.
+
+  A grid is given as a list of rows of numbers, all the same length, with
+  the value in column `x` of row `y` placed at the point `{x, y}`. An
+  `Xairo.ImageSurface` can be given instead, with each pixel's value, from
+  `0` to `1`, placed at the pixel's centre, so that the contours line up
+  with the image. Values are read from the channel given by the `channel`
+  option, one of `:alpha`, `:red`, `:green`, `:blue` or `:luminance`, which
+  defaults to `:luminance`. Colors are read unpremultiplied, and `:a8`
+  surfaces always give their alpha. Surfaces in formats other than `:a8`,
+  `:argb32` and `:rgb24` return `{:error, :invalid_format}`, and grids with
+  rows of different lengths return `{:error, :invalid_size}`.
+
+  Contours are interpolated linearly between neighbouring values. Where
+  only the diagonally opposite corners of a square of four values are above
+  a level, the average of the four decides whether the contour joins them
+  or keeps them apart.
+
+  Contours are straight lines between grid squares, unless the `smooth`
+  option is set, in which case they are smoothed into curves through the
+  same points, as by `Xairo.Path.smooth/2`.
+  """
+
+  alias Xairo.{ImageSurface, Path}
+  alias Xairo.Native, as: N
+
+  @type grid :: [[number()]]
+
+  @type channel :: :alpha | :red | :green | :blue | :luminance
+
+  @type option :: {:smooth, boolean()} | {:channel, channel()}
+
+  @doc """
+  Returns the contour lines at each of the given levels, as one
+  `Xairo.Path` for each level, in the same order.
+
+  The lines run clockwise around the values at or above their level, with
+  the y-axis pointing down. Lines that loop back on themselves are closed,
+  and the others end at the edges of the grid.
+
+  ## Examples
+
+      iex> [path] = Contour.isolines([[0, 0, 0], [0, 1, 0], [0, 0, 0]], [0.5])
+      iex> Path.to_svg(path)
+      "M0.5 1L1 0.5 1.5 1 1 1.5Z"
+
+  """
+  @spec isolines(grid() | ImageSurface.t(), [number()], [option()]) ::
+          Xairo.or_error([Path.t()])
+  def isolines(source, levels, opts \\ []) do
+    contours(source, levels, false, opts)
+  end
+
+  @doc """
+  Returns the filled bands between each of the given levels and the next,
+  as one `Xairo.Path` for each pair of levels, covering the area where the
+  values are at or above the lower level of the two and below the higher.
+
+  Bands are closed along the edges of the grid, and their holes run the
+  opposite way to their outlines, so they fill correctly under either fill
+  rule.
+
+  ## Examples
+
+      iex> [path] = Contour.isobands([[0, 1, 2], [0, 1, 2]], [0.5, 1.5])
+      iex> Path.to_svg(path)
+      "M0.5 0L2 0 2 1 0.5 1ZM1.5 1L2 1 2 0 1.5 0Z"
+
+  """
+  @spec isobands(grid() | ImageSurface.t(), [number()], [option()]) ::
+          Xairo.or_error([Path.t()])
+  def isobands(source, levels, opts \\ []) do
+    contours(source, levels, true, opts)
+  end
+
+  defp contours(%ImageSurface{surface: surface}, levels, bands, opts) do
+    channel = Keyword.get(opts, :channel, :luminance)
+
+    surface
+    |> N.contour_image_surface(channel, floats(levels), options(bands, opts))
+    |> paths()
+  end
+
+  defp contours(grid, levels, bands, opts) when is_list(grid) do
+    grid
+    |> Enum.map(&floats/1)
+    |> N.contour_grid(floats(levels), options(bands, opts))
+    |> paths()
+  end
+
+  defp options(bands, opts) do
+    %{bands: bands, smooth: Keyword.get(opts, :smooth, false)}
+  end
+
+  defp paths({:ok, paths}), do: Enum.map(paths, &Path.from/1)
+  defp paths(error), do: error
+
+  defp floats(numbers), do: Enum.map(numbers, &(&1 / 1))
+end
@@ -0,0 +1,80 @@
+defmodule Xairo.Dxf do
+  @moduledoc """
+  Exports paths as DXF files, for CAD programs and laser cutters.
+
+  Paths are grouped into named layers, each optionally given a color, such
+  as one layer for cutting and another for engraving. Each subpath becomes a
+  polyline, closed if the subpath is. Curves are either flattened into the
+  polylines, or, in an R2000 file, written as splines following the same
+  cubic Bézier curves exactly. R12 has no splines, so curves in an R12 file
+  are always flattened.
+
+  DXF's y-axis points up, so y coordinates are negated, to keep the drawing
+  the right way up.
+
+  Layer colors are written as the nearest of the basic AutoCAD Color Index
+  colors, and, in an R2000 file, as the exact color as well. Layers without
+  a color are drawn in black, or in white on a dark background, as is the
+  default layer `"0"`.
+  """
+
+  alias Xairo.Native, as: N
+  alias Xairo.{Path, Rgba}
+
+  @type paths :: Path.t() | [Path.t()]
+
+  @type layer :: {String.t(), paths()} | {String.t(), paths(), Rgba.t()}
+
+  @type option ::
+          {:version, :r12 | :r2000}
+          | {:splines, boolean()}
+          | {:unit, Xairo.Plotter.unit()}
+          | {:tolerance, number()}
+
+  @doc """
+  Returns a DXF file drawing the given layers.
+
+  The layers are given as a list of `{name, paths}` or `{name, paths, color}`
+  tuples, where `paths` is a `Xairo.Path` or a list of them. A path, or list
+  of paths, given on its own is drawn on the default layer `"0"`.
+
+  Takes the following options:
+
+  * `version` - `:r2000` or `:r12`. Defaults to `:r2000`.
+  * `splines` - whether curves are written as splines, rather than flattened
+    to lines. Ignored for R12 files. Defaults to `true`.
+  * `unit` - the unit of the paths' coordinates, one of `:mm`, `:cm` or
+    `:in`, which R2000 files record, or `:pt` or `:px`, which are converted
+    to millimetres. Defaults to `:mm`.
+  * `tolerance` - the maximum distance between a curve and the lines it is
+    flattened to. Defaults to `0.1`.
+
+  Returns `{:error, :invalid_layer_name}` if a layer's name is empty, or
+  contains a control character, a backtick, or any of `<>/\\":;?*|=`.
+  """
+  @spec encode(layer() | [layer()] | paths(), [option()]) :: Xairo.or_error(String.t())
+  def encode(layers, opts \\ []) do
+    options = %{
+      version: Keyword.get(opts, :version, :r2000),
+      splines: Keyword.get(opts, :splines, true),
+      unit: Keyword.get(opts, :unit, :mm),
+      tolerance: Keyword.get(opts, :tolerance, 0.1) / 1
+    }
+
+    with {:ok, dxf} <- N.dxf_encode(layers |> List.wrap() |> native_layers(), options),
+         do: dxf
+  end
+
+  defp native_layers([%Path{} | _] = paths), do: native_layers([{"0", paths}])
+
+  defp native_layers(layers) do
+    Enum.map(layers, fn
+      {name, paths} -> native_layer(name, paths, nil)
+      {name, paths, color} -> native_layer(name, paths, color)
+    end)
+  end
+
+  defp native_layer(name, paths, color) do
+    %{name: name, color: color, paths: paths |> List.wrap() |> Enum.map(& &1.path)}
+  end
+end
@@ -17,11 +17,16 @@ defmodule Xairo.FontFace do
   @type slant :: :normal | :italic | :oblique
   @type weight :: :normal | :bold
 
+  alias Xairo.FontMatch
   alias Xairo.Native, as: N
 
   @doc """
   Creates a "toy" font from a font family, a slant value, and a font weight.
 
+  Use `families/0` and `styles/1` to find the fonts installed on the system,
+  and `Xairo.FontMatch.for/4` to see which installed font the given values
+  will resolve to.
+
   ### Family
 
   The `family` can be a specific font name, or else a more generic
@@ -29,7 +34,9 @@ defmodule Xairo.FontFace do
 
   If a font that is not installed is specified, the given value will be stored
   in the font face, but the font's display will fallback to the system's default
-  sans-serif font.
+  sans-serif font. To return `{:error, :font_not_found}` instead, pass
+  `strict: true` as an option. See `Xairo.FontMatch.for/4` for the rules
+  strict matching follows.
 
   ### Slant
 
@@ -46,12 +53,39 @@ defmodule Xairo.FontFace do
   * `:normal`
   * `:bold`
   """
-  @spec toy_create(String.t(), slant(), weight()) :: t()
-  def toy_create(family, slant, weight) do
-    with {:ok, font_face} <- N.font_face_toy_create(family, slant, weight),
+  @spec toy_create(String.t(), slant(), weight(), Keyword.t()) :: Xairo.or_error(t())
+  def toy_create(family, slant, weight, opts \\ []) do
+    with :ok <- check_installed(family, slant, weight, Keyword.get(opts, :strict, false)),
+         {:ok, font_face} <- N.font_face_toy_create(family, slant, weight),
          do: %__MODULE__{font_face: font_face}
   end
 
+  defp check_installed(_family, _slant, _weight, false), do: :ok
+
+  defp check_installed(family, slant, weight, true) do
+    with %FontMatch{} <- FontMatch.for(family, slant, weight, strict: true), do: :ok
+  end
+
+  @doc """
+  Returns the family names of all fonts installed on the system, sorted
+  alphabetically.
+  """
+  @spec families() :: Xairo.or_error([String.t()])
+  def families do
+    with {:ok, families} <- N.font_face_families(), do: families
+  end
+
+  @doc """
+  Returns the style names, e.g. "Bold" or "Condensed Oblique", available
+  for an installed font family.
+
+  Returns an empty list if the family is not installed.
+  """
+  @spec styles(String.t()) :: Xairo.or_error([String.t()])
+  def styles(family) do
+    with {:ok, styles} <- N.font_face_styles(family), do: styles
+  end
+
   @doc """
   Returns the `family` value for the font
 
@@ -0,0 +1,56 @@
+defmodule Xairo.FontMatch do
+  @moduledoc """
+  Models the installed font that the system's font configuration
+  ([fontconfig](https://www.freedesktop.org/wiki/Software/fontconfig/))
+  resolves a font family, slant, and weight to.
+
+  This is the same resolution cairo performs when creating a "toy" font
+  face (see `Xairo.FontFace.toy_create/4`), so it can be used to see which
+  font will actually be used to render text.
+
+  The font match consists of six parameters:
+
+  * `family` - the family name of the matched font
+  * `style` - the style name of the matched font, e.g. "Bold Oblique"
+  * `slant` - the slant of the matched font, one of `:normal`, `:italic` or `:oblique`
+  * `weight` - the weight of the matched font, `:bold` for any weight of semibold or heavier, otherwise `:normal`
+  * `file` - the path of the font file on the filesystem
+  * `index` - the index of the font within the file, for font files that contain more than one font
+  """
+
+  defstruct [:family, :style, :slant, :weight, :file, :index]
+
+  @type t :: %__MODULE__{
+          family: String.t(),
+          style: String.t(),
+          slant: FontFace.slant(),
+          weight: FontFace.weight(),
+          file: String.t(),
+          index: integer()
+        }
+
+  alias Xairo.FontFace
+  alias Xairo.Native, as: N
+
+  @doc """
+  Returns the installed font that best matches the given font family,
+  slant and weight.
+
+  When there is no installed font with the given family, this will return
+  the system's fallback font. To return `{:error, :font_not_found}` instead,
+  pass `strict: true` as an option. In strict mode, the matched font must have
+  the requested family name (ignoring case) and weight, and must be slanted
+  if a slanted font was requested, although italic and oblique faces are
+  treated as equivalent.
+
+  Because generic family names like "serif" or "monospace" are resolved to
+  a specific font, they will never match in strict mode.
+  """
+  @spec for(String.t(), FontFace.slant(), FontFace.weight(), Keyword.t()) ::
+          Xairo.or_error(t())
+  def for(family, slant, weight, opts \\ []) do
+    with {:ok, %__MODULE__{} = font_match} <-
+           N.font_match_font_match(family, slant, weight, Keyword.get(opts, :strict, false)),
+         do: font_match
+  end
+end
@@ -25,7 +25,15 @@ defmodule Xairo.ImageSurface do
         }
   @type format :: :argb32 | :rgb24 | :a8 | :a1 | :rgb16_565 | :rgb30
 
+  @type trace_option ::
+          {:threshold, number()}
+          | {:turd_size, non_neg_integer()}
+          | {:corner_threshold, number()}
+          | {:optimize_curves, boolean()}
+          | {:optimize_tolerance, number()}
+
   alias Xairo.Native, as: N
+  alias Xairo.Path
 
   @doc """
   Creates a new image surface that can be attached to a `Xairo.Context`
@@ -108,4 +116,52 @@ defmodule Xairo.ImageSurface do
   def format(%__MODULE__{surface: surface}) do
     N.image_surface_format(surface)
   end
+
+  @doc """
+  Traces the contents of the surface into smooth vector outlines, in the
+  manner of potrace.
+
+  The surface is first split into ink and background. For `:a8` and `:a1`
+  surfaces, a pixel is ink if its alpha is greater than the `threshold`
+  option, and for `:argb32` and `:rgb24` surfaces, if its darkness (one minus
+  its luminance, once composited over white) is. The boundaries of the ink
+  are then fitted with polygons, whose corners are smoothed into cubic curves.
+
+  Returns a list of `Xairo.Path` structs, in pixel coordinates, one for each
+  separate shape found, reading the surface from the top. Each holds the
+  outline of the shape followed by the outlines of any holes in it, which
+  run the opposite way, so the shape fills correctly under either fill rule.
+  Shapes inside holes are returned as paths of their own.
+
+  Returns `{:error, :invalid_format}` for surfaces in any other format.
+
+  ## Options
+
+  * `threshold` - from `0` to `1`, the alpha or darkness above which a pixel
+    is ink. Defaults to `0.5`.
+  * `turd_size` - outlines enclosing no more than this many pixels are
+    dropped as specks. Defaults to `2`.
+  * `corner_threshold` - how sharp a corner must be to be kept as a corner
+    rather than smoothed into a curve, from `0`, which keeps every corner
+    and so returns polygons, to `4/3`, which smooths them all. Defaults to
+    `1`.
+  * `optimize_curves` - whether to join neighbouring curves bending the same
+    way into fewer curves. Defaults to `true`.
+  * `optimize_tolerance` - how far, in pixels, a joined curve may stray from
+    the curves it replaces. Defaults to `0.2`.
+  """
+  @spec trace(t(), [trace_option()]) :: Xairo.or_error([Path.t()])
+  def trace(%__MODULE__{surface: surface}, opts \\ []) do
+    options = %{
+      threshold: Keyword.get(opts, :threshold, 0.5) / 1,
+      turd_size: Keyword.get(opts, :turd_size, 2),
+      corner_threshold: Keyword.get(opts, :corner_threshold, 1) / 1,
+      optimize_curves: Keyword.get(opts, :optimize_curves, true),
+      optimize_tolerance: Keyword.get(opts, :optimize_tolerance, 0.2) / 1
+    }
+
+    with {:ok, paths} <- N.image_surface_trace(surface, options) do
+      Enum.map(paths, &Path.from/1)
+    end
+  end
 end
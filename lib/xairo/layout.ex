@@ -0,0 +1,153 @@
+defmodule Xairo.Layout do
+  @moduledoc """
+  Models a block of text laid out by [Pango](https://docs.gtk.org/Pango/).
+
+  Unlike Cairo's "toy" text API (see `Xairo.show_text/2`), a layout performs
+  full text shaping, so complex scripts (Arabic, Devanagari, Thai, etc.) and
+  mixed left-to-right and right-to-left text render correctly. A layout can
+  also wrap text to a given width, align its lines, and apply styling from
+  [Pango markup](https://docs.gtk.org/Pango/pango_markup.html).
+
+  A layout is rendered with `Xairo.show_layout/2`, or its outlines added to
+  the current path with `Xairo.layout_path/2`.
+
+  ### Enabling Pango
+
+  Pango support is provided by the optional `pango` cargo feature, which
+  requires `pango` and `pangocairo` to be installed on the system. To enable
+  it, add the feature to the NIF's configuration:
+
+      config :xairo, Xairo.Native, features: ["pango"]
+
+  When the feature is not enabled, calling any function in this module
+  will raise a `:nif_not_loaded` error.
+  """
+
+  defstruct [:layout]
+
+  alias Xairo.{Image, Point}
+  alias Xairo.Native, as: N
+
+  @typedoc """
+  Elixir model of a Pango layout. Stores a reference to the in-memory
+  representation of the layout.
+  """
+  @type t :: %__MODULE__{
+          layout: reference()
+        }
+
+  @type wrap_mode :: :word | :char | :word_char
+  @type alignment :: :left | :center | :right
+
+  @typedoc """
+  A rectangle described by its top left corner, its width, and its height.
+  """
+  @type rectangle :: {Point.t(), number(), number()}
+
+  @doc """
+  Creates a new, empty layout set up to render on the given image.
+
+  The layout can still be rendered on other images, in which case it
+  will be updated to match their settings before drawing.
+  """
+  @spec new(Image.t()) :: Xairo.or_error(t())
+  def new(%Image{context: ctx}) do
+    with {:ok, layout} <- N.layout_new(ctx.context), do: %__MODULE__{layout: layout}
+  end
+
+  @doc """
+  Sets the text of the layout.
+
+  This replaces any text or markup previously set on the layout.
+  """
+  @spec set_text(t(), String.t()) :: t()
+  def set_text(%__MODULE__{layout: l} = layout, text) do
+    N.layout_set_text(l, text)
+    layout
+  end
+
+  @doc """
+  Sets the text of the layout from a string of Pango markup.
+
+  Returns `{:error, :invalid_markup}` if the markup cannot be parsed.
+
+      Layout.set_markup(layout, "<b>bold</b> and <i>italic</i>")
+
+  """
+  @spec set_markup(t(), String.t()) :: Xairo.or_error(t())
+  def set_markup(%__MODULE__{layout: l} = layout, markup) do
+    with {:ok, _} <- N.layout_set_markup(l, markup), do: layout
+  end
+
+  @doc """
+  Sets the width, in userspace units, at which the layout's lines will
+  be wrapped.
+
+  Passing `nil` disables wrapping, so that each paragraph is laid out
+  on a single line.
+  """
+  @spec set_width(t(), number() | nil) :: t()
+  def set_width(%__MODULE__{layout: l} = layout, nil) do
+    N.layout_set_width(l, nil)
+    layout
+  end
+
+  def set_width(%__MODULE__{layout: l} = layout, width) do
+    N.layout_set_width(l, width / 1)
+    layout
+  end
+
+  @doc """
+  Sets how lines are broken when the layout has a width set.
+
+  `wrap_mode` is an atom that can be one of the following values
+
+  * `:word` - wrap lines at word boundaries
+  * `:char` - wrap lines at character boundaries
+  * `:word_char` - wrap lines at word boundaries, falling back to
+    character boundaries if a word does not fit on a line by itself
+  """
+  @spec set_wrap(t(), wrap_mode()) :: t()
+  def set_wrap(%__MODULE__{layout: l} = layout, wrap_mode) do
+    N.layout_set_wrap(l, wrap_mode)
+    layout
+  end
+
+  @doc """
+  Sets how lines are aligned within the layout's width.
+
+  `alignment` is one of `:left`, `:center` or `:right`.
+  """
+  @spec set_alignment(t(), alignment()) :: t()
+  def set_alignment(%__MODULE__{layout: l} = layout, alignment) do
+    N.layout_set_alignment(l, alignment)
+    layout
+  end
+
+  @doc """
+  Sets the font used by the layout from a Pango font description string.
+
+  A description takes the form "[FAMILY-LIST] [STYLE-OPTIONS] [SIZE]",
+  for example "Sans Bold 12" or "Noto Naskh Arabic 16". See the
+  [Pango documentation](https://docs.gtk.org/Pango/type_func.FontDescription.from_string.html)
+  for the full syntax.
+  """
+  @spec set_font_description(t(), String.t()) :: t()
+  def set_font_description(%__MODULE__{layout: l} = layout, description) do
+    N.layout_set_font_description(l, description)
+    layout
+  end
+
+  @doc """
+  Returns the ink and logical extents of the layout, in userspace units.
+
+  The ink extents cover the area actually drawn by the glyphs, while the
+  logical extents cover the area the text occupies for layout purposes,
+  including line spacing. Both are measured relative to the layout's origin,
+  which is its top left corner.
+  """
+  @spec extents(t()) :: {rectangle(), rectangle()}
+  def extents(%__MODULE__{layout: layout}) do
+    N.layout_extents(layout)
+  end
+end
@@ -9,6 +9,7 @@ defmodule Xairo.Native do
   def image_surface_height(_surface), do: error()
   def image_surface_stride(_surface), do: error()
   def image_surface_format(_surface), do: error()
+  def image_surface_trace(_surface, _options), do: error()
 
   def pdf_surface_new(_width, _height, _path), do: error()
   def pdf_surface_finish(_surface), do: error()
@@ -38,7 +39,20 @@ defmodule Xairo.Native do
   def context_arc_negative(_context, _center, _r, _angle1, _angle2), do: error()
   def context_curve_to(_context, _point1, _point2, _point3), do: error()
   def context_rel_curve_to(_context, _vector1, _vector2, _vector3), do: error()
+  def context_quad_to(_context, _control, _point), do: error()
+  def context_rel_quad_to(_context, _vector1, _vector2), do: error()
+  def context_arc_to(_context, _radii, _rotation, _large_arc, _sweep, _point), do: error()
   def context_rectangle(_context, _origin, _width, _height), do: error()
+  def context_ellipse(_context, _center, _rx, _ry), do: error()
+  def context_rounded_rectangle(_context, _origin, _width, _height, _radii), do: error()
+  def context_regular_polygon(_context, _center, _radius, _sides, _rotation), do: error()
+  def context_star(_context, _center, _outer, _inner, _points, _rotation), do: error()
+
+  def context_superellipse(_context, _center, _radii, _exponent, _rotation, _tolerance),
+    do: error()
+
+  def context_spiral(_context, _center, _radii, _turns, _rotation, _tolerance), do: error()
+  def context_rose(_context, _center, _radius, _k, _rotation, _tolerance), do: error()
   def context_line_to(_context, _point), do: error()
   def context_rel_line_to(_context, _vector), do: error()
   def context_rel_move_to(_context, _vector), do: error()
@@ -51,6 +65,9 @@ defmodule Xairo.Native do
   def context_stroke_preserve(_context), do: error()
   def context_fill(_context), do: error()
   def context_fill_preserve(_context), do: error()
+  def context_fill_markers(_context, _style), do: error()
+  def context_sketch_stroke(_context, _path, _style), do: error()
+  def context_sketch_fill(_context, _path, _style), do: error()
   def context_paint(_context), do: error()
   def context_paint_with_alpha(_context, _alpha), do: error()
 
@@ -67,6 +84,9 @@ defmodule Xairo.Native do
 
   def context_show_text(_context, _text), do: error()
   def context_text_path(_context, _text), do: error()
+  def context_show_text_along_path(_context, _path, _text, _offset, _alignment), do: error()
+  def context_text_path_along_path(_context, _path, _text, _offset, _alignment), do: error()
+  def context_glyph_paths(_context, _text), do: error()
   def context_set_font_size(_context, _font_size), do: error()
   def context_set_font_face(_context, _font_face), do: error()
   def context_select_font_face(_context, _family, _slant, _weight), do: error()
@@ -132,6 +152,47 @@ defmodule Xairo.Native do
   ## CONTEXT END
 
   def path_iter(_path), do: error()
+  def path_from_segments(_segments), do: error()
+  def path_equal(_path1, _path2), do: error()
+  def path_segment_count(_path), do: error()
+  def path_from_svg(_d), do: error()
+  def path_to_svg(_path, _precision), do: error()
+  def path_transform(_path, _matrix), do: error()
+  def path_translate(_path, _dx, _dy), do: error()
+  def path_scale(_path, _sx, _sy), do: error()
+  def path_rotate(_path, _radians), do: error()
+  def path_reverse(_path), do: error()
+  def path_length(_path, _tolerance), do: error()
+  def path_subpath_lengths(_path, _tolerance), do: error()
+  def path_point_at_length(_path, _distance, _tolerance), do: error()
+  def path_slice(_path, _start, _end, _tolerance), do: error()
+  def path_boolean(_path1, _path2, _operation, _fill_rule, _tolerance), do: error()
+  def path_stroke_to_path(_path, _style, _tolerance), do: error()
+  def path_offset(_path, _distance, _line_join, _miter_limit, _fill_rule, _tolerance),
+    do: error()
+  def path_hatch(_path, _pattern, _angle, _spacing, _fill_rule, _tolerance), do: error()
+  def path_simplify(_path, _tolerance), do: error()
+  def path_smooth(_path, _alpha), do: error()
+  def path_in_fill(_path, _point, _fill_rule, _tolerance), do: error()
+  def path_in_stroke(_path, _point, _style, _tolerance), do: error()
+  def path_extents(_path), do: error()
+  def path_stroke_extents(_path, _style, _tolerance), do: error()
+  def path_interpolate(_path1, _path2, _t), do: error()
+  def path_regular_polygon(_center, _radius, _sides, _rotation), do: error()
+  def path_star(_center, _outer, _inner, _points, _rotation), do: error()
+  def path_superellipse(_center, _radii, _exponent, _rotation, _tolerance), do: error()
+  def path_spiral(_center, _radii, _turns, _rotation, _tolerance), do: error()
+  def path_rose(_center, _radius, _k, _rotation, _tolerance), do: error()
+  def path_markers(_path, _style, _line_width), do: error()
+  def path_brush_stroke(_path, _profile, _line_cap, _smooth, _tolerance), do: error()
+
+  def contour_grid(_grid, _levels, _options), do: error()
+  def contour_image_surface(_surface, _channel, _levels, _options), do: error()
+
+  def dxf_encode(_layers, _options), do: error()
+
+  def plotter_hpgl(_paths, _options), do: error()
+  def plotter_gcode(_paths, _options), do: error()
 
   def linear_gradient_new(_start, _stop), do: error()
   def linear_gradient_linear_points(_gradient), do: error()
@@ -171,6 +232,10 @@ defmodule Xairo.Native do
   def font_face_toy_get_family(_font), do: error()
   def font_face_toy_get_slant(_font), do: error()
   def font_face_toy_get_weight(_font), do: error()
+  def font_face_families, do: error()
+  def font_face_styles(_family), do: error()
+
+  def font_match_font_match(_family, _slant, _weight, _strict), do: error()
 
   def matrix_new(_xx, _yx, _xy_, _yy, _tx, _ty), do: error()
   def matrix_identity, do: error()
@@ -187,5 +252,20 @@ defmodule Xairo.Native do
 
   def text_extents_text_extents(_text, _context), do: error()
 
+  def text_spacing_show_text(_context, _text, _spacing), do: error()
+  def text_spacing_text_path(_context, _text, _spacing), do: error()
+  def text_spacing_text_extents(_context, _text, _spacing), do: error()
+
+  def layout_new(_context), do: error()
+  def layout_set_text(_layout, _text), do: error()
+  def layout_set_markup(_layout, _markup), do: error()
+  def layout_set_width(_layout, _width), do: error()
+  def layout_set_wrap(_layout, _wrap_mode), do: error()
+  def layout_set_alignment(_layout, _alignment), do: error()
+  def layout_set_font_description(_layout, _description), do: error()
+  def layout_extents(_layout), do: error()
+  def layout_show(_layout, _context), do: error()
+  def layout_path(_layout, _context), do: error()
+
   defp error, do: :erlang.nif_error(:nif_not_loaded)
 end
@@ -10,6 +10,9 @@ defmodule Xairo.Path do
   * `segments` - a list of path segments representing the current
     path on the drawing surface at the time the path is queried.
 
+  A path can also be built directly from a list of segments with
+  `Xairo.Path.new/1`, without drawing on an image.
+
   ### Path Segments
 
   Path segments take one of four forms:
@@ -27,11 +30,21 @@ defmodule Xairo.Path do
   functions to be called directly on the struct as a shorthand, but delegating
   through to its `segments` field.
 
+  ### Tolerance
+
+  Functions that flatten curves to straight lines take a `tolerance`, the
+  most the lines may stray from the curves. Since `stroke_to_path/2`,
+  `offset/3`, `brush_stroke/3` and the functions taking the same options as
+  them cover every line with its own piece of outline, they raise tolerances
+  below a thousandth of the stroke's size to it: half the line width, the
+  offset distance, or the widest brush width.
+
   """
 
   defstruct [:path, :segments]
 
-  alias Xairo.Point
+  alias Xairo.{Matrix, Point}
+  alias Xairo.Native, as: N
 
   @type segment ::
           {:move_to, Point.t()}
@@ -39,6 +52,37 @@ defmodule Xairo.Path do
           | {:curve_to, Point.t(), Point.t(), Point.t()}
           | :close_path
 
+  @type boolean_option :: {:fill_rule, Xairo.fill_rule()} | {:tolerance, number()}
+
+  @type stroke_option ::
+          {:line_width, number()}
+          | {:line_cap, Xairo.line_cap()}
+          | {:line_join, Xairo.line_join()}
+          | {:miter_limit, number()}
+          | {:dashes, [number()]}
+          | {:dash_offset, number()}
+          | {:tolerance, number()}
+
+  @type offset_option ::
+          {:line_join, Xairo.line_join()}
+          | {:miter_limit, number()}
+          | {:fill_rule, Xairo.fill_rule()}
+          | {:tolerance, number()}
+
+  @type hatch_pattern :: :parallel | :cross | :stipple | :concentric
+
+  @type hatch_option ::
+          {:angle, number()} | {:fill_rule, Xairo.fill_rule()} | {:tolerance, number()}
+
+  @type shape_option :: {:rotation, number()} | {:tolerance, number()}
+
+  @type brush_option ::
+          {:line_cap, Xairo.line_cap()} | {:smooth, boolean()} | {:tolerance, number()}
+
+  @type marker :: :arrow | :open_arrow | :bar | :circle | :square | t()
+
+  @type marker_option :: {:start | :mid | :end, marker()} | {:size, number()}
+
   @type t :: %__MODULE__{
           segments: [segment()]
         }
@@ -69,6 +113,856 @@ defmodule Xairo.Path do
   def from(path) when is_reference(path) do
     %__MODULE__{path: path, segments: Xairo.Native.path_iter(path)}
   end
+
+  @doc """
+  Builds a new `Xairo.Path` from a list of path segments.
+
+  Points in the segments can be given as `Xairo.Point` structs or as
+  `{x, y}` tuples.
+
+  The segments are replayed as if they had been drawn on an image, so the
+  resulting path follows the same rules as one returned by `Xairo.copy_path/1`:
+  a `:close_path` segment is always followed by a `:move_to` back to the start
+  of its subpath, and coordinates are stored with cairo's fixed-point
+  precision.
+
+      iex> Path.new([{:move_to, {10, 10}}, {:line_to, {50, 10}}, :close_path])
+      ...> |> Enum.to_list()
+      [
+        {:move_to, %Point{x: 10.0, y: 10.0}},
+        {:line_to, %Point{x: 50.0, y: 10.0}},
+        :close_path,
+        {:move_to, %Point{x: 10.0, y: 10.0}}
+      ]
+
+  """
+  @spec new([segment() | {:move_to | :line_to, {number(), number()}}]) :: Xairo.or_error(t())
+  def new(segments) when is_list(segments) do
+    segments = Enum.map(segments, &normalize_segment/1)
+
+    with {:ok, path} <- N.path_from_segments(segments), do: from(path)
+  end
+
+  @doc """
+  Builds a new `Xairo.Path` from SVG path data, as found in the `d`
+  attribute of an SVG `<path>` element.
+
+  All SVG path commands are supported, in both their absolute and relative
+  forms. Because cairo paths only contain straight lines and cubic Bézier
+  curves, horizontal and vertical lines are stored as `:line_to` segments,
+  quadratic curves are converted to the equivalent cubic curves, and
+  elliptical arcs are approximated by a cubic curve for each quarter turn.
+
+  Returns `{:error, :invalid_svg_path}` if the path data cannot be parsed.
+
+      iex> Path.from_svg("M10 10h40v40z")
+      ...> |> Enum.to_list()
+      [
+        {:move_to, %Point{x: 10.0, y: 10.0}},
+        {:line_to, %Point{x: 50.0, y: 10.0}},
+        {:line_to, %Point{x: 50.0, y: 50.0}},
+        :close_path,
+        {:move_to, %Point{x: 10.0, y: 10.0}}
+      ]
+
+  """
+  @spec from_svg(String.t()) :: Xairo.or_error(t())
+  def from_svg(d) do
+    with {:ok, path} <- N.path_from_svg(d), do: from(path)
+  end
+
+  @doc """
+  Serializes the path into compact SVG path data, suitable for the `d`
+  attribute of an SVG `<path>` element.
+
+  Coordinates are rounded to `precision` decimal places, which defaults
+  to `3`, and trailing zeros are omitted.
+
+      iex> Path.new([{:move_to, {10, 10}}, {:line_to, {50.26, 10}}, {:line_to, {50, -5}}, :close_path])
+      ...> |> Path.to_svg(1)
+      "M10 10L50.3 10 50-5Z"
+
+  """
+  @spec to_svg(t(), non_neg_integer()) :: String.t()
+  def to_svg(%__MODULE__{path: path}, precision \\ 3) do
+    N.path_to_svg(path, precision)
+  end
+
+  @doc """
+  Returns a new path with every point in the given path mapped through
+  a `Xairo.Matrix`.
+
+  Unlike drawing a path after changing the image's transformation matrix,
+  this only moves the points of the path, so a transformed path is stroked
+  with the same line width as the original.
+  """
+  @spec transform(t(), Matrix.t()) :: Xairo.or_error(t())
+  def transform(%__MODULE__{path: path}, %Matrix{matrix: matrix}) do
+    with {:ok, path} <- N.path_transform(path, matrix), do: from(path)
+  end
+
+  @doc """
+  Returns a new path with every point moved by `dx` and `dy`.
+
+      iex> Path.new([{:move_to, {10, 10}}, {:line_to, {20, 10}}])
+      ...> |> Path.translate(5, -5)
+      ...> |> Enum.to_list()
+      [{:move_to, %Point{x: 15.0, y: 5.0}}, {:line_to, %Point{x: 25.0, y: 5.0}}]
+
+  """
+  @spec translate(t(), number(), number()) :: Xairo.or_error(t())
+  def translate(%__MODULE__{path: path}, dx, dy) do
+    with {:ok, path} <- N.path_translate(path, dx / 1, dy / 1), do: from(path)
+  end
+
+  @doc """
+  Returns a new path with every point scaled, relative to the origin, by
+  `sx` horizontally and `sy` vertically.
+  """
+  @spec scale(t(), number(), number()) :: Xairo.or_error(t())
+  def scale(%__MODULE__{path: path}, sx, sy) do
+    with {:ok, path} <- N.path_scale(path, sx / 1, sy / 1), do: from(path)
+  end
+
+  @doc """
+  Returns a new path with every point rotated around the origin by the
+  given angle, in radians.
+  """
+  @spec rotate(t(), number()) :: Xairo.or_error(t())
+  def rotate(%__MODULE__{path: path}, radians) do
+    with {:ok, path} <- N.path_rotate(path, radians / 1), do: from(path)
+  end
+
+  @doc """
+  Returns a new path that traces the given path in the opposite direction.
+
+  The order of the subpaths is reversed as well, so the new path starts where
+  the original ended. A closed subpath stays closed and keeps its starting
+  point, but is traced in the opposite direction. Subpaths made of a single
+  `:move_to`, such as the one cairo adds after each `:close_path`, are dropped.
+
+      iex> Path.new([{:move_to, {0, 0}}, {:line_to, {10, 0}}, {:line_to, {10, 10}}])
+      ...> |> Path.reverse()
+      ...> |> Enum.to_list()
+      [
+        {:move_to, %Point{x: 10.0, y: 10.0}},
+        {:line_to, %Point{x: 10.0, y: 0.0}},
+        {:line_to, %Point{x: 0.0, y: 0.0}}
+      ]
+
+  """
+  @spec reverse(t()) :: Xairo.or_error(t())
+  def reverse(%__MODULE__{path: path}) do
+    with {:ok, path} <- N.path_reverse(path), do: from(path)
+  end
+
+  @doc """
+  Returns the total length of the path, in userspace units.
+
+  Lines are measured exactly, while curves are measured by flattening them
+  into lines that stray no further than `tolerance` from the curve. The
+  default tolerance of `0.1` matches cairo's default (see
+  `Xairo.set_tolerance/2`).
+
+  The jump between subpaths is not counted, but the line that closes a
+  closed subpath is.
+
+      iex> Path.from_svg("M0 0h30v40z")
+      ...> |> Path.total_length()
+      120.0
+
+  """
+  @spec total_length(t(), number()) :: float()
+  def total_length(%__MODULE__{path: path}, tolerance \\ 0.1) do
+    N.path_length(path, tolerance / 1)
+  end
+
+  @doc """
+  Returns the length of each of the path's subpaths, measured as in
+  `Xairo.Path.total_length/2`.
+  """
+  @spec subpath_lengths(t(), number()) :: [float()]
+  def subpath_lengths(%__MODULE__{path: path}, tolerance \\ 0.1) do
+    N.path_subpath_lengths(path, tolerance / 1)
+  end
+
+  @doc """
+  Returns the point at the given distance along the path, and the angle,
+  in radians, of the path's direction at that point.
+
+  Returns `nil` if the distance is negative or longer than the path.
+
+      iex> Path.from_svg("M0 0h30v40")
+      ...> |> Path.point_at_length(40)
+      {%Point{x: 30.0, y: 10.0}, :math.pi() / 2}
+
+  """
+  @spec point_at_length(t(), number(), number()) :: {Point.t(), float()} | nil
+  def point_at_length(%__MODULE__{path: path}, distance, tolerance \\ 0.1) do
+    N.path_point_at_length(path, distance / 1, tolerance / 1)
+  end
+
+  @doc """
+  Returns a new path containing the part of the path between the distances
+  `start` and `end` along it.
+
+  Lines and curves crossing either distance are split exactly where they
+  cross it. A closed subpath stays closed only if it lies entirely within
+  the range.
+
+      iex> Path.from_svg("M0 0h30v40")
+      ...> |> Path.slice(10, 40)
+      ...> |> Path.to_svg()
+      "M10 0L30 0 30 10"
+
+  """
+  @spec slice(t(), number(), number(), number()) :: Xairo.or_error(t())
+  def slice(%__MODULE__{path: path}, start, end_, tolerance \\ 0.1) do
+    with {:ok, path} <- N.path_slice(path, start / 1, end_ / 1, tolerance / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a new path filling the area filled by either of the given paths.
+
+  See "Boolean Operations" below for the options this function takes, and
+  for how the result is calculated.
+
+  ### Boolean Operations
+
+  `union/3`, `intersection/3`, `difference/3` and `xor/3` combine the areas
+  that would be filled by drawing each path and calling `Xairo.fill/1`. Open
+  subpaths are treated as closed, just as they are when filled.
+
+  Each function takes the following options:
+
+  * `fill_rule` - the `t:Xairo.fill_rule/0` used to decide which areas each
+    path fills, which allows for self-intersecting paths. Defaults to `:winding`.
+  * `tolerance` - the maximum distance between a curve and the lines it is
+    flattened to. Defaults to `0.1`.
+
+  Curves are flattened before the paths are combined, so the resulting path
+  contains only straight lines. Every subpath of the result is closed, and
+  holes are traced in the opposite direction to their outlines, so the result
+  fills the same area under either fill rule.
+
+      iex> a = Path.from_svg("M0 0H10V10H0Z")
+      iex> b = Path.from_svg("M5 0H15V10H5Z")
+      iex> Path.union(a, b) |> Path.to_svg()
+      "M0 0L15 0 15 10 0 10Z"
+
+  """
+  @spec union(t(), t(), [boolean_option()]) :: Xairo.or_error(t())
+  def union(path1, path2, opts \\ []), do: boolean(path1, path2, :union, opts)
+
+  @doc """
+  Returns a new path filling the area filled by both of the given paths.
+
+  See `union/3` for details.
+  """
+  @spec intersection(t(), t(), [boolean_option()]) :: Xairo.or_error(t())
+  def intersection(path1, path2, opts \\ []), do: boolean(path1, path2, :intersection, opts)
+
+  @doc """
+  Returns a new path filling the area filled by the first path, but not
+  by the second.
+
+  See `union/3` for details.
+  """
+  @spec difference(t(), t(), [boolean_option()]) :: Xairo.or_error(t())
+  def difference(path1, path2, opts \\ []), do: boolean(path1, path2, :difference, opts)
+
+  @doc """
+  Returns a new path filling the area filled by exactly one of the given
+  paths.
+
+  See `union/3` for details.
+  """
+  @spec xor(t(), t(), [boolean_option()]) :: Xairo.or_error(t())
+  def xor(path1, path2, opts \\ []), do: boolean(path1, path2, :xor, opts)
+
+  @doc """
+  Returns a new path filling the area that stroking the given path would
+  paint.
+
+  This converts a stroke into fillable geometry, for output such as laser
+  cutting or plotting, where only outlines can be drawn, or for documents
+  that should not rely on the viewer to draw strokes.
+
+  Takes the following options, which default to the same values as a new
+  `Xairo.Context`:
+
+  * `line_width` - defaults to `2`
+  * `line_cap` - a `t:Xairo.line_cap/0`, defaults to `:butt`
+  * `line_join` - a `t:Xairo.line_join/0`, defaults to `:miter`
+  * `miter_limit` - defaults to `10`
+  * `dashes` - a list of alternating dash and gap lengths, as given to
+    `Xairo.set_dash/3`. Defaults to `[]`, which draws solid lines.
+  * `dash_offset` - defaults to `0`
+  * `tolerance` - the maximum distance between a curve, or a round join or
+    cap, and the lines it is flattened to. Defaults to `0.1`.
+
+  As with the boolean operations, the result contains only straight lines,
+  with overlapping parts of the stroke merged, and fills the same area under
+  either fill rule.
+
+  As in cairo, the line join only applies at the ends of segments; curves are
+  always joined smoothly along their length.
+
+  Returns `{:error, :invalid_dash}` if any dash length is negative, if they
+  are all zero, or if the path would be split into more than 65536 dashes.
+
+      iex> Path.from_svg("M0 0H10") |> Path.stroke_to_path(line_width: 4) |> Path.to_svg()
+      "M0-2L10-2 10 2 0 2Z"
+
+  """
+  @spec stroke_to_path(t(), [stroke_option()]) :: Xairo.or_error(t())
+  def stroke_to_path(%__MODULE__{path: path}, opts \\ []) do
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <- N.path_stroke_to_path(path, stroke_style(opts), tolerance / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a new path filling the area the given path fills, grown outwards by
+  `distance`, or shrunk inwards if `distance` is negative.
+
+  This draws parallel outlines, such as the bleed around a shape or a series
+  of concentric insets. Open subpaths are closed first, as they are when the
+  path is filled, and parts of the area too narrow to survive an inset
+  disappear, which can split it into several pieces.
+
+  Takes the following options:
+
+  * `line_join` - the `t:Xairo.line_join/0` drawn at corners on the outside
+    of the offset, as when stroking. Defaults to `:miter`.
+  * `miter_limit` - defaults to `10`
+  * `fill_rule` - the `t:Xairo.fill_rule/0` used to decide which area the
+    path fills. Defaults to `:winding`.
+  * `tolerance` - the maximum distance between a curve, or a round join, and
+    the lines it is flattened to. Defaults to `0.1`.
+
+  As with the boolean operations, the result contains only straight lines.
+
+      iex> square = Path.from_svg("M0 0H10V10H0Z")
+      iex> square |> Path.offset(1) |> Path.to_svg()
+      "M-1-1L11-1 11 11-1 11Z"
+      iex> square |> Path.offset(-1) |> Path.to_svg()
+      "M1 1L9 1 9 9 1 9Z"
+
+  """
+  @spec offset(t(), number(), [offset_option()]) :: Xairo.or_error(t())
+  def offset(%__MODULE__{path: path}, distance, opts \\ []) do
+    line_join = Keyword.get(opts, :line_join, :miter)
+    miter_limit = Keyword.get(opts, :miter_limit, 10)
+    fill_rule = Keyword.get(opts, :fill_rule, :winding)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <-
+           N.path_offset(
+             path,
+             distance / 1,
+             line_join,
+             miter_limit / 1,
+             fill_rule,
+             tolerance / 1
+           ),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a new path hatching the area the given path fills, as vector
+  geometry that can be stroked, or exported to a format such as PDF or SVG
+  without being rasterised.
+
+  The pattern is one of
+
+  * `:parallel` - parallel lines `spacing` apart
+  * `:cross` - parallel lines with another set at right angles to them
+  * `:stipple` - dots `spacing` apart along parallel rows, staggered from
+    one row to the next. Each dot is a line of no length, which is drawn as
+    a dot when stroked with a `:round` line cap.
+  * `:concentric` - outlines of the area inset by every multiple of
+    `spacing`, until nothing of it is left, up to 1000 of them
+
+  Lines and dots are clipped to the area, and placed at whole multiples of
+  `spacing` from the origin, so that neighbouring areas hatched alike line
+  up.
+
+  Returns `{:error, :invalid_size}` if `spacing` is not positive, or if it is
+  so small that more than 65536 lines, or 262144 dots, would cross the area.
+
+  Takes the following options:
+
+  * `angle` - the angle of the lines, or of the rows of dots, in radians.
+    Ignored by `:concentric`. Defaults to `pi/4`.
+  * `fill_rule` - the `t:Xairo.fill_rule/0` used to decide which area the
+    path fills. Defaults to `:winding`.
+  * `tolerance` - the maximum distance between a curve, or the rounded
+    corners of a contour, and the lines it is flattened to. Defaults to
+    `0.1`.
+
+      iex> Path.from_svg("M0 0H10V10H0Z") |> Path.hatch(:parallel, 4, angle: 0) |> Path.to_svg()
+      "M0 4L10 4M0 8L10 8"
+
+  """
+  @spec hatch(t(), hatch_pattern(), number(), [hatch_option()]) :: Xairo.or_error(t())
+  def hatch(%__MODULE__{path: path}, pattern, spacing, opts \\ []) do
+    angle = Keyword.get(opts, :angle, :math.pi() / 4)
+    fill_rule = Keyword.get(opts, :fill_rule, :winding)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <-
+           N.path_hatch(path, pattern, angle / 1, spacing / 1, fill_rule, tolerance / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a new path with every run of consecutive lines in the given path
+  reduced to as few of its points as keep it within `tolerance` of the
+  original, using the Ramer-Douglas-Peucker algorithm.
+
+  This thins out paths with many noisy points, such as those built from data
+  or recorded from hand-drawn input. Curves are left as they are.
+
+      iex> Path.from_svg("M0 0L1 0.1 2 -0.1 3 0.05 4 0 5 3") |> Path.simplify(0.2) |> Path.to_svg()
+      "M0 0L4 0 5 3"
+
+  """
+  @spec simplify(t(), number()) :: Xairo.or_error(t())
+  def simplify(%__MODULE__{path: path}, tolerance) do
+    with {:ok, path} <- N.path_simplify(path, tolerance / 1), do: from(path)
+  end
+
+  @doc """
+  Returns a new path with every line in the given path replaced by a curve,
+  so that each run of consecutive lines becomes a smooth curve through the
+  same points.
+
+  The curves follow a Catmull-Rom spline through the points. A closed
+  subpath made only of lines is smoothed all the way around, while the open
+  ends of any other run of lines leave their points heading straight for the
+  next one. Curves already in the path are left as they are.
+
+  Takes the following options:
+
+  * `alpha` - how the spline is fitted between unevenly spaced points: `0`
+    gives a uniform spline, `0.5` a centripetal one, which never forms cusps
+    or loops between two points, and `1` a chordal one. Defaults to `0.5`.
+
+      iex> Path.from_svg("M0 0L10 0 10 10") |> Path.smooth() |> Path.to_svg()
+      "M0 0C3.332 0 8.332-1.668 10 0 11.668 1.668 10 6.668 10 10"
+
+  """
+  @spec smooth(t(), [{:alpha, number()}]) :: Xairo.or_error(t())
+  def smooth(%__MODULE__{path: path}, opts \\ []) do
+    alpha = Keyword.get(opts, :alpha, 0.5)
+
+    with {:ok, path} <- N.path_smooth(path, alpha / 1), do: from(path)
+  end
+
+  @doc """
+  Returns true if the given point lies within the area filling the path would
+  paint, including its outline.
+
+  Unlike `Xairo.in_fill/2`, this needs no image, so any number of stored
+  paths can be tested without appending them to a context.
+
+  Takes the following options:
+
+  * `fill_rule` - defaults to `:winding`
+  * `tolerance` - the maximum distance between a curve and the lines it is
+    flattened to. Defaults to `0.1`.
+
+      iex> square = Path.from_svg("M0 0H10V10H0Z")
+      iex> Path.in_fill(square, {5, 5})
+      true
+      iex> Path.in_fill(square, {15, 5})
+      false
+
+  """
+  @spec in_fill(t(), Xairo.point(), [boolean_option()]) :: boolean()
+  def in_fill(%__MODULE__{path: path}, point, opts \\ []) do
+    fill_rule = Keyword.get(opts, :fill_rule, :winding)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    N.path_in_fill(path, Point.from(point), fill_rule, tolerance / 1)
+  end
+
+  @doc """
+  Returns true if the given point lies within the area stroking the path
+  would paint.
+
+  Unlike `Xairo.in_stroke/2`, this needs no image. Takes the same options as
+  `stroke_to_path/2`, and likewise returns `{:error, :invalid_dash}` for an
+  invalid dash pattern.
+
+      iex> line = Path.from_svg("M0 0H10")
+      iex> Path.in_stroke(line, {5, 0.5})
+      true
+      iex> Path.in_stroke(line, {5, 1.5})
+      false
+      iex> Path.in_stroke(line, {5, 1.5}, line_width: 4)
+      true
+
+  """
+  @spec in_stroke(t(), Xairo.point(), [stroke_option()]) :: Xairo.or_error(boolean())
+  def in_stroke(%__MODULE__{path: path}, point, opts \\ []) do
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    N.path_in_stroke(path, Point.from(point), stroke_style(opts), tolerance / 1)
+  end
+
+  @doc """
+  Returns the smallest box containing the path, as a 2-element tuple of its
+  top-left and bottom-right corners, or `nil` for an empty path.
+
+  The box is exact: it is measured to the furthest points of any curves,
+  rather than to their control points.
+
+      iex> Path.from_svg("M0 0C0 10 10 10 10 0") |> Path.extents()
+      {%Point{x: 0.0, y: 0.0}, %Point{x: 10.0, y: 7.5}}
+
+  """
+  @spec extents(t()) :: {Point.t(), Point.t()} | nil
+  def extents(%__MODULE__{path: path}) do
+    N.path_extents(path)
+  end
+
+  @doc """
+  Returns the smallest box containing the area stroking the path would paint,
+  as a 2-element tuple of its top-left and bottom-right corners, or `nil` if
+  the stroke would paint nothing.
+
+  Takes the same options as `stroke_to_path/2`, and is accurate to within
+  `tolerance`.
+
+      iex> Path.from_svg("M0 0H10") |> Path.stroke_extents(line_cap: :square)
+      {%Point{x: -1.0, y: -1.0}, %Point{x: 11.0, y: 1.0}}
+
+  """
+  @spec stroke_extents(t(), [stroke_option()]) :: Xairo.or_error({Point.t(), Point.t()} | nil)
+  def stroke_extents(%__MODULE__{path: path}, opts \\ []) do
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    N.path_stroke_extents(path, stroke_style(opts), tolerance / 1)
+  end
+
+  @doc """
+  Returns a new path `t` of the way from `path1` to `path2`, for morphing one
+  shape into another in an animation.
+
+  `t = 0` gives a path drawing the same shape as `path1`, and `t = 1` one
+  drawing the same shape as `path2`. To give the paths the same structure,
+  their subpaths are paired up in order, and the longest segments on either
+  side are split until both have as many. A subpath without a partner grows
+  out of, or shrinks into, the start point of the one it is paired with.
+  Closed subpaths are turned to run the same way and to start at the points
+  that line up best, and stay closed if both are closed.
+
+  Pairs of lines stay lines, while a line paired with a curve becomes a curve.
+
+      iex> from = Path.from_svg("M0 0H10V10H0Z")
+      iex> to = Path.from_svg("M20 20V30H30V20Z")
+      iex> Path.interpolate(from, to, 0.5) |> Path.to_svg()
+      "M10 10L20 10 20 20 10 20Z"
+
+  """
+  @spec interpolate(t(), t(), number()) :: Xairo.or_error(t())
+  def interpolate(%__MODULE__{path: path1}, %__MODULE__{path: path2}, t) do
+    with {:ok, path} <- N.path_interpolate(path1, path2, t / 1), do: from(path)
+  end
+
+  @doc """
+  Returns a closed regular polygon with the given number of sides, its
+  corners lying on a circle with the given center and radius.
+
+  Takes the following options:
+
+  * `rotation` - the angle, in radians, of the first corner from the center.
+    The rest follow in the direction of increasing angles, as
+    `Xairo.arc/5` draws. Defaults to `0`, putting the first corner directly
+    to the right of the center.
+
+  Returns an empty path for fewer than 3 sides, and
+  `{:error, :invalid_size}` for more than 65536.
+
+      iex> Path.regular_polygon({0, 0}, 10, 6, rotation: -:math.pi() / 2) |> Path.to_svg()
+      "M0-10L8.66-5 8.66 5 0 10-8.66 5-8.66-5Z"
+
+  """
+  @spec regular_polygon(Xairo.point(), number(), pos_integer(), [shape_option()]) ::
+          Xairo.or_error(t())
+  def regular_polygon(center, radius, sides, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+
+    with {:ok, path} <-
+           N.path_regular_polygon(Point.from(center), radius / 1, sides, rotation / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a closed star with the given number of points, its tips at
+  `outer_radius` from the given center and the corners between them at
+  `inner_radius`.
+
+  Takes the same `rotation` option as `regular_polygon/4`, giving the angle
+  of the first tip.
+
+  Returns an empty path for fewer than 2 points, and
+  `{:error, :invalid_size}` for more than 32768.
+
+      iex> Path.star({0, 0}, 10, 5, 4) |> Path.to_svg()
+      "M10 0L3.535 3.535 0 10-3.535 3.535-10 0-3.535-3.535 0-10 3.535-3.535Z"
+
+  """
+  @spec star(Xairo.point(), number(), number(), pos_integer(), [shape_option()]) ::
+          Xairo.or_error(t())
+  def star(center, outer_radius, inner_radius, points, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+
+    with {:ok, path} <-
+           N.path_star(
+             Point.from(center),
+             outer_radius / 1,
+             inner_radius / 1,
+             points,
+             rotation / 1
+           ),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a closed superellipse, the curve `|x / rx|^n + |y / ry|^n = 1` for
+  the given exponent `n`, around the given center.
+
+  An exponent of `2` gives an ellipse, larger exponents give squarer shapes
+  with rounded corners, and ones between `0` and `1` give shapes with sides
+  curving inwards to sharp points, such as an astroid at `2/3`.
+
+  Takes the following options:
+
+  * `rotation` - the angle, in radians, the shape's axes are turned by.
+    Defaults to `0`.
+  * `tolerance` - the maximum distance between the curve and the lines it is
+    drawn with. Defaults to `0.1`.
+
+  Returns an empty path for an exponent that is not positive.
+  """
+  @spec superellipse(Xairo.point(), {number(), number()}, number(), [shape_option()]) ::
+          Xairo.or_error(t())
+  def superellipse(center, {rx, ry}, exponent, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <-
+           N.path_superellipse(
+             Point.from(center),
+             {rx / 1, ry / 1},
+             exponent / 1,
+             rotation / 1,
+             tolerance / 1
+           ),
+         do: from(path)
+  end
+
+  @doc """
+  Returns an open Archimedean spiral around the given center, starting at the
+  first of the `{inner, outer}` radii and moving steadily out to the second
+  over the given number of turns.
+
+  A positive number of turns runs in the direction of increasing angles, and
+  a negative number the other way. Takes the same options as
+  `superellipse/4`, with `rotation` giving the angle the spiral starts at.
+
+  Returns `{:error, :invalid_size}` for more than 4096 turns either way.
+  """
+  @spec spiral(Xairo.point(), {number(), number()}, number(), [shape_option()]) ::
+          Xairo.or_error(t())
+  def spiral(center, {inner, outer}, turns, opts \\ []) do
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <-
+           N.path_spiral(
+             Point.from(center),
+             {inner / 1, outer / 1},
+             turns / 1,
+             rotation / 1,
+             tolerance / 1
+           ),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a closed rose curve, `r = radius * cos(k * θ)`, around the given
+  center.
+
+  `k` is given as an integer, or as an `{n, d}` tuple for `n / d`. For an
+  integer `k` the rose has `k` petals if `k` is odd and `2k` if it is even,
+  while fractions give overlapping petals, with the curve drawn over as many
+  turns as it takes to return to its start.
+
+  Takes the same options as `superellipse/4`, with `rotation` giving the
+  angle the first petal points at. Returns an empty path if `d` is `0`, and
+  `{:error, :invalid_size}` if `n` or `d`, once the fraction is reduced to
+  its lowest terms, is above 64.
+  """
+  @spec rose(
+          Xairo.point(),
+          number(),
+          non_neg_integer() | {non_neg_integer(), pos_integer()},
+          [shape_option()]
+        ) :: Xairo.or_error(t())
+  def rose(center, radius, k, opts \\ []) do
+    {n, d} = if is_integer(k), do: {k, 1}, else: k
+    rotation = Keyword.get(opts, :rotation, 0)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <-
+           N.path_rose(Point.from(center), radius / 1, {n, d}, rotation / 1, tolerance / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns the outlines of the markers placed at the vertices of the path,
+  to be filled after the path is stroked.
+
+  Markers are placed at the start and end of each open subpath, and at
+  every vertex between its segments, and turned to follow the path's
+  direction there, including along curves. A start marker points back,
+  away from the subpath, so that arrows at either end of a line both point
+  outwards, and a mid marker follows the direction halfway between those of
+  the segments meeting at its vertex. Every vertex of a closed subpath,
+  including its start, gets a mid marker.
+
+  Takes the following options:
+
+  * `start`, `mid` and `end` - the marker to place at each kind of vertex,
+    one of `:arrow`, `:open_arrow`, `:bar`, `:circle` or `:square`, or a
+    path to use as a custom marker. Defaults to no marker.
+  * `size` - the length of the markers, in line widths. Defaults to `4`.
+  * `line_width` - the width the path is stroked with. Defaults to `2`.
+
+  Each marker is drawn with its origin at the vertex, its x-axis along the
+  path's direction, and a unit of `size` line widths, so a custom marker
+  path should be drawn around its origin and about one unit long to match
+  the built-in ones. Arrowheads reach a line width past the vertex, so that
+  they cover the end of a line stroked with butt caps.
+
+      iex> Path.from_svg("M0 0H10") |> Path.markers(end: :arrow) |> Path.to_svg()
+      "M12 0L4-4 4 4Z"
+
+  """
+  @spec markers(t(), [marker_option() | {:line_width, number()}]) :: Xairo.or_error(t())
+  def markers(%__MODULE__{path: path}, opts \\ []) do
+    line_width = Keyword.get(opts, :line_width, 2)
+
+    with {:ok, path} <- N.path_markers(path, marker_style(opts), line_width / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns a new path filling the area covered by stroking the given path
+  with a width that varies along it, as with a brush or a pen responding to
+  pressure.
+
+  The widths are given either as a list of `{t, width}` stops, where `t`
+  runs from `0` at the start of each subpath to `1` at its end, or as a list
+  of numbers, one for each point of the path: the start of each subpath and
+  the end of each of its lines and curves, in order. If fewer widths than
+  points are given, the last one repeats. Between the given widths the
+  width changes steadily, and the outline is rounded at every corner.
+
+  Takes the following options:
+
+  * `line_cap` - how the open ends of subpaths are drawn. Defaults to
+    `:round`.
+  * `smooth` - whether the width eases in and out of each given width,
+    rather than changing linearly between them. Defaults to `false`.
+  * `tolerance` - the maximum distance between the path's curves, or the
+    rounded parts of the outline, and the lines they are drawn with.
+    Defaults to `0.1`.
+
+      iex> Path.from_svg("M0 0H10") |> Path.brush_stroke([{0, 4}, {1, 0}], line_cap: :butt) |> Path.to_svg()
+      "M0-2L10 0 0 2Z"
+
+  """
+  @spec brush_stroke(t(), [{number(), number()}] | [number()], [brush_option()]) ::
+          Xairo.or_error(t())
+  def brush_stroke(%__MODULE__{path: path}, widths, opts \\ []) do
+    profile =
+      if Enum.all?(widths, &is_tuple/1),
+        do: {:stops, Enum.map(widths, fn {t, width} -> {t / 1, width / 1} end)},
+        else: {:points, Enum.map(widths, &(&1 / 1))}
+
+    line_cap = Keyword.get(opts, :line_cap, :round)
+    smooth = Keyword.get(opts, :smooth, false)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <- N.path_brush_stroke(path, profile, line_cap, smooth, tolerance / 1),
+         do: from(path)
+  end
+
+  @doc false
+  def marker_style(opts) do
+    %{
+      start: marker(Keyword.get(opts, :start)),
+      mid: marker(Keyword.get(opts, :mid)),
+      end: marker(Keyword.get(opts, :end)),
+      size: Keyword.get(opts, :size, 4) / 1
+    }
+  end
+
+  defp marker(%__MODULE__{path: path}), do: {:path, path}
+  defp marker(marker), do: marker
+
+  defp stroke_style(opts) do
+    %{
+      line_width: Keyword.get(opts, :line_width, 2) / 1,
+      line_cap: Keyword.get(opts, :line_cap, :butt),
+      line_join: Keyword.get(opts, :line_join, :miter),
+      miter_limit: Keyword.get(opts, :miter_limit, 10) / 1,
+      dashes: Enum.map(Keyword.get(opts, :dashes, []), &(&1 / 1)),
+      dash_offset: Keyword.get(opts, :dash_offset, 0) / 1
+    }
+  end
+
+  defp boolean(%__MODULE__{path: path1}, %__MODULE__{path: path2}, operation, opts) do
+    fill_rule = Keyword.get(opts, :fill_rule, :winding)
+    tolerance = Keyword.get(opts, :tolerance, 0.1)
+
+    with {:ok, path} <- N.path_boolean(path1, path2, operation, fill_rule, tolerance / 1),
+         do: from(path)
+  end
+
+  @doc """
+  Returns true if the two paths contain the same segments.
+
+  The comparison is made against the in-memory representations of the paths,
+  rather than their cached `segments`.
+  """
+  @spec equal?(t(), t()) :: boolean()
+  def equal?(%__MODULE__{path: path1}, %__MODULE__{path: path2}) do
+    N.path_equal(path1, path2)
+  end
+
+  @doc """
+  Returns the number of segments in the path.
+  """
+  @spec segment_count(t()) :: non_neg_integer()
+  def segment_count(%__MODULE__{path: path}) do
+    N.path_segment_count(path)
+  end
+
+  defp normalize_segment({:move_to, point}), do: {:move_to, Point.from(point)}
+  defp normalize_segment({:line_to, point}), do: {:line_to, Point.from(point)}
+
+  defp normalize_segment({:curve_to, point1, point2, point3}),
+    do: {:curve_to, Point.from(point1), Point.from(point2), Point.from(point3)}
+
+  defp normalize_segment(:close_path), do: :close_path
 end
 
 defimpl Enumerable, for: Xairo.Path do
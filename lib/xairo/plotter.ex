@@ -0,0 +1,124 @@
+defmodule Xairo.Plotter do
+  @moduledoc """
+  Exports paths as programs for pen plotters, in HPGL or G-code.
+
+  Each subpath of the given paths is drawn as one continuous stroke with the
+  pen down, with curves flattened to lines. To export a drawing made on an
+  image, build it up as the image's current path and pass the result of
+  `Xairo.copy_path/1`.
+
+  ## Units
+
+  The paths' coordinates are taken to be in the unit given by the `unit`
+  option, one of `:mm`, `:cm`, `:in`, `:pt` (1/72 inch) or `:px` (1/96
+  inch), which defaults to `:mm`. Plotters usually put the origin at the
+  bottom left of the page, with the y-axis pointing up, so, if given a
+  `page_height` in the same unit, the exporters measure the y-axis up from
+  the bottom of the page rather than down from the top, so that the drawing
+  is not mirrored.
+
+  ## Travel order
+
+  By default, the strokes are reordered to cut down the distance the pen
+  travels while raised: starting from the origin, the stroke drawn next is
+  always the one that can be started nearest to the pen, drawing open
+  strokes from either end and closed ones from any of up to 16 points spread
+  evenly around them. Strokes that start where the previous one ended are
+  drawn without raising the pen.
+  Pass `optimize: false` to draw the strokes in the order they are given.
+  """
+
+  alias Xairo.Native, as: N
+  alias Xairo.Path
+
+  @type unit :: :mm | :cm | :in | :pt | :px
+
+  @type option ::
+          {:unit, unit()}
+          | {:page_height, number()}
+          | {:optimize, boolean()}
+          | {:tolerance, number()}
+
+  @type hpgl_option :: option() | {:pen, pos_integer()} | {:velocity, number()}
+
+  @type gcode_option ::
+          option()
+          | {:output_unit, :mm | :in}
+          | {:pen_up | :pen_down, String.t()}
+          | {:feed_rate | :travel_rate, number()}
+
+  @doc """
+  Returns an HPGL program drawing the given path, or list of paths.
+
+  Coordinates are written in plotter units of 0.025mm. Takes the `unit`,
+  `page_height`, and `optimize` options described above, as well as
+
+  * `pen` - the number of the pen to select. Defaults to `1`.
+  * `velocity` - the pen speed, in centimetres per second. Left to the
+    plotter if not given.
+  * `tolerance` - the maximum distance between a curve and the lines it is
+    flattened to, in `unit`s. Defaults to `0.1`.
+
+      iex> Path.from_svg("M0 0H10") |> Plotter.to_hpgl()
+      "IN;SP1;\\nPU0,0;PD400,0;\\nPU;SP0;\\n"
+
+  """
+  @spec to_hpgl(Path.t() | [Path.t()], [hpgl_option()]) :: String.t()
+  def to_hpgl(paths, opts \\ []) do
+    options = %{
+      unit: Keyword.get(opts, :unit, :mm),
+      page_height: float_or_nil(Keyword.get(opts, :page_height)),
+      pen: Keyword.get(opts, :pen, 1),
+      velocity: float_or_nil(Keyword.get(opts, :velocity)),
+      optimize: Keyword.get(opts, :optimize, true),
+      tolerance: Keyword.get(opts, :tolerance, 0.1) / 1
+    }
+
+    N.plotter_hpgl(native_paths(paths), options)
+  end
+
+  @doc """
+  Returns a G-code program drawing the given path, or list of paths.
+
+  Takes the `unit`, `page_height`, and `optimize` options described above,
+  as well as
+
+  * `output_unit` - `:mm` to write coordinates in millimetres (`G21`), or
+    `:in` for inches (`G20`). Defaults to `:mm`.
+  * `pen_up` - the command that raises the pen. Defaults to `"M5"`.
+  * `pen_down` - the command that lowers the pen. Defaults to `"M3"`.
+  * `feed_rate` - the speed of moves with the pen down, in `output_unit`s
+    per minute. Defaults to `1000`.
+  * `travel_rate` - the speed of moves with the pen up. If not given, these
+    are rapid (`G0`) moves.
+  * `tolerance` - the maximum distance between a curve and the lines it is
+    flattened to, in `unit`s. Defaults to `0.1`.
+
+  The feed rate is only written when it changes.
+
+      iex> Path.from_svg("M0 0H10V10") |> Plotter.to_gcode()
+      "G21\\nG90\\nM5\\nG0 X0 Y0\\nM3\\nG1 X10 Y0 F1000\\nG1 X10 Y10\\nM5\\n"
+
+  """
+  @spec to_gcode(Path.t() | [Path.t()], [gcode_option()]) :: String.t()
+  def to_gcode(paths, opts \\ []) do
+    options = %{
+      unit: Keyword.get(opts, :unit, :mm),
+      output_unit: Keyword.get(opts, :output_unit, :mm),
+      page_height: float_or_nil(Keyword.get(opts, :page_height)),
+      pen_up: Keyword.get(opts, :pen_up, "M5"),
+      pen_down: Keyword.get(opts, :pen_down, "M3"),
+      feed_rate: Keyword.get(opts, :feed_rate, 1000) / 1,
+      travel_rate: float_or_nil(Keyword.get(opts, :travel_rate)),
+      optimize: Keyword.get(opts, :optimize, true),
+      tolerance: Keyword.get(opts, :tolerance, 0.1) / 1
+    }
+
+    N.plotter_gcode(native_paths(paths), options)
+  end
+
+  defp native_paths(paths), do: paths |> List.wrap() |> Enum.map(& &1.path)
+
+  defp float_or_nil(nil), do: nil
+  defp float_or_nil(number), do: number / 1
+end
@@ -30,16 +30,58 @@ defmodule Xairo.TextExtents do
           text: String.t()
         }
 
+  @typedoc """
+  Options controlling how `Xairo.show_text/3` and `Xairo.text_path/3` space
+  the glyphs of a string.
+
+  * `letter_spacing` - extra space added between each character. Defaults to `0`.
+  * `word_spacing` - extra space added after each space character. Defaults to `0`.
+  * `kerning` - whether to apply the font's pair kerning, from the `kern`
+    feature of its `GPOS` table, or from its legacy `kern` table if it has
+    no such feature. Defaults to `true`.
+  * `vertical` - lays the text out from top to bottom, with each character
+    centered horizontally on the current point. Defaults to `false`.
+
+  Negative spacing values draw characters closer together.
+  """
+  @type spacing_option ::
+          {:letter_spacing, number()}
+          | {:word_spacing, number()}
+          | {:kerning, boolean()}
+          | {:vertical, boolean()}
+
   alias Xairo.Image
   alias Xairo.Native, as: N
 
   @doc """
   Returns the extents for the text string using the context's current
   font settings.
+
+  If any spacing options are given (see `t:spacing_option/0`), the extents
+  are those of the text as laid out by `Xairo.show_text/3` with the same
+  options.
   """
-  @spec for(Image.t(), String.t()) :: Xairo.or_error(t())
-  def for(%Image{context: ctx}, text) do
+  @spec for(Image.t(), String.t(), [spacing_option()]) :: Xairo.or_error(t())
+  def for(image, text, opts \\ [])
+
+  def for(%Image{context: ctx}, text, []) do
     with {:ok, %__MODULE__{} = extents} <- N.text_extents_text_extents(text, ctx.context),
          do: extents
   end
+
+  def for(%Image{context: ctx}, text, opts) do
+    with {:ok, %__MODULE__{} = extents} <-
+           N.text_spacing_text_extents(ctx.context, text, spacing(opts)),
+         do: extents
+  end
+
+  @doc false
+  def spacing(opts) do
+    %{
+      letter_spacing: Keyword.get(opts, :letter_spacing, 0) / 1,
+      word_spacing: Keyword.get(opts, :word_spacing, 0) / 1,
+      kerning: Keyword.get(opts, :kerning, true),
+      vertical: Keyword.get(opts, :vertical, false)
+    }
+  end
 end
@@ -41,8 +41,11 @@ defmodule Xairo.MixProject do
       groups_for_modules: [
         Drawing: [
           Xairo.Context,
+          Xairo.Contour,
+          Xairo.Dxf,
           Xairo.Matrix,
-          Xairo.Path
+          Xairo.Path,
+          Xairo.Plotter
         ],
         Surfaces: [
           Xairo.ImageSurface,
@@ -60,7 +63,9 @@ defmodule Xairo.MixProject do
         Text: [
           Xairo.TextExtents,
           Xairo.FontExtents,
-          Xairo.FontFace
+          Xairo.FontFace,
+          Xairo.FontMatch,
+          Xairo.Layout
         ],
         Utility: [
           Xairo.Point,
@@ -0,0 +1,368 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// A cubic Bézier curve, from `p0` to `p3` with control points `p1` and `p2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cubic {
+    pub p0: (f64, f64),
+    pub p1: (f64, f64),
+    pub p2: (f64, f64),
+    pub p3: (f64, f64),
+}
+
+// flattening stops subdividing at this depth, which splits a curve into at
+// most 65536 lines, however small the tolerance
+const MAX_DEPTH: u32 = 16;
+
+/// The smallest tolerance that strokes, brush strokes and offsets are
+/// flattened to, as a fraction of the pen's radius, the widest width or the
+/// distance. Each line of the flattened path is covered by its own piece, so
+/// finer tolerances only add pieces too small to see, and below it the step
+/// angle of round joins and caps rounds to nothing.
+pub const MIN_TOLERANCE: f64 = 1e-3;
+
+pub fn lerp((x0, y0): (f64, f64), (x1, y1): (f64, f64), t: f64) -> (f64, f64) {
+    (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t)
+}
+
+pub fn distance((x0, y0): (f64, f64), (x1, y1): (f64, f64)) -> f64 {
+    (x1 - x0).hypot(y1 - y0)
+}
+
+// Distance from `point` to the line segment between `start` and `end`.
+pub fn distance_to_segment(point: (f64, f64), start: (f64, f64), end: (f64, f64)) -> f64 {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        return distance(point, start);
+    }
+    let t =
+        (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+    distance(point, lerp(start, end, t))
+}
+
+/// Approximates an elliptical arc given as in SVG path data, from `from` to
+/// `to` on an ellipse with radii `radii`, rotated by `rotation` radians, with
+/// one cubic per quarter turn.
+///
+/// `large_arc` picks the longer of the two arcs between the points, and
+/// `sweep` the arc running in the direction of increasing angles. Radii too
+/// small to span the points are scaled up until they do. Returns no curves if
+/// the points coincide, and `None` if either radius is zero, in which case
+/// the arc is drawn as a straight line.
+pub fn endpoint_arc(
+    from: (f64, f64),
+    radii: (f64, f64),
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: (f64, f64),
+) -> Option<Vec<Cubic>> {
+    let (x1, y1) = from;
+    let (x2, y2) = to;
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+
+    if (x1 - x2).abs() < f64::EPSILON && (y1 - y2).abs() < f64::EPSILON {
+        return Some(Vec::new());
+    }
+    if rx == 0.0 || ry == 0.0 {
+        return None;
+    }
+
+    // converts the arc to its center parameterization, following the SVG
+    // implementation notes
+    let (sin_phi, cos_phi) = rotation.sin_cos();
+    let dx = (x1 - x2) / 2.0;
+    let dy = (y1 - y2) / 2.0;
+    let x1p = cos_phi * dx + sin_phi * dy;
+    let y1p = -sin_phi * dx + cos_phi * dy;
+
+    let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+    let denominator = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let cxp = coefficient * rx * y1p / ry;
+    let cyp = -coefficient * ry * x1p / rx;
+
+    let center_x = cos_phi * cxp - sin_phi * cyp + (x1 + x2) / 2.0;
+    let center_y = sin_phi * cxp + cos_phi * cyp + (y1 + y2) / 2.0;
+
+    let start_angle = ((y1p - cyp) / ry).atan2((x1p - cxp) / rx);
+    let end_angle = ((-y1p - cyp) / ry).atan2((-x1p - cxp) / rx);
+    let mut sweep_angle = end_angle - start_angle;
+    if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    } else if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    }
+
+    let count = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / count as f64;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+    // maps a point on the unit circle onto the rotated ellipse
+    let ellipse_point = |angle: f64, scale: f64| {
+        let (sin, cos) = angle.sin_cos();
+        let (ux, uy) = (rx * (cos - scale * sin), ry * (sin + scale * cos));
+        (
+            center_x + cos_phi * ux - sin_phi * uy,
+            center_y + sin_phi * ux + cos_phi * uy,
+        )
+    };
+
+    let mut start = from;
+    let curves = (0..count)
+        .map(|i| {
+            let theta1 = start_angle + step * i as f64;
+            let theta2 = theta1 + step;
+            let end = if i == count - 1 {
+                to
+            } else {
+                ellipse_point(theta2, 0.0)
+            };
+            let curve = Cubic::new(
+                start,
+                ellipse_point(theta1, k),
+                ellipse_point(theta2, -k),
+                end,
+            );
+            start = end;
+            curve
+        })
+        .collect();
+    Some(curves)
+}
+
+impl Cubic {
+    pub const fn new(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64)) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    /// The cubic tracing the same curve as the quadratic Bézier curve from
+    /// `p0` to `p2` with control point `control`.
+    pub fn from_quadratic(p0: (f64, f64), control: (f64, f64), p2: (f64, f64)) -> Self {
+        Self::new(
+            p0,
+            lerp(p0, control, 2.0 / 3.0),
+            lerp(p2, control, 2.0 / 3.0),
+            p2,
+        )
+    }
+
+    pub fn point_at(&self, t: f64) -> (f64, f64) {
+        let mt = 1.0 - t;
+        let a = mt * mt * mt;
+        let b = 3.0 * mt * mt * t;
+        let c = 3.0 * mt * t * t;
+        let d = t * t * t;
+        (
+            a * self.p0.0 + b * self.p1.0 + c * self.p2.0 + d * self.p3.0,
+            a * self.p0.1 + b * self.p1.1 + c * self.p2.1 + d * self.p3.1,
+        )
+    }
+
+    pub fn derivative_at(&self, t: f64) -> (f64, f64) {
+        let mt = 1.0 - t;
+        let a = 3.0 * mt * mt;
+        let b = 6.0 * mt * t;
+        let c = 3.0 * t * t;
+        (
+            a * (self.p1.0 - self.p0.0) + b * (self.p2.0 - self.p1.0) + c * (self.p3.0 - self.p2.0),
+            a * (self.p1.1 - self.p0.1) + b * (self.p2.1 - self.p1.1) + c * (self.p3.1 - self.p2.1),
+        )
+    }
+
+    /// The direction of the curve at `t`, as an angle in radians.
+    ///
+    /// Where the derivative vanishes, because a control point coincides with
+    /// an end point, the direction towards the next distinct point is used.
+    pub fn angle_at(&self, t: f64) -> f64 {
+        let (dx, dy) = self.derivative_at(t);
+        if dx.hypot(dy) > f64::EPSILON {
+            return dy.atan2(dx);
+        }
+
+        let (from, to) = if t <= 0.0 {
+            (self.p0, if self.p2 == self.p0 { self.p3 } else { self.p2 })
+        } else if t >= 1.0 {
+            (if self.p1 == self.p3 { self.p0 } else { self.p1 }, self.p3)
+        } else {
+            (self.p0, self.p3)
+        };
+        (to.1 - from.1).atan2(to.0 - from.0)
+    }
+
+    /// Splits the curve in two at `t`, using de Casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let p01 = lerp(self.p0, self.p1, t);
+        let p12 = lerp(self.p1, self.p2, t);
+        let p23 = lerp(self.p2, self.p3, t);
+        let p012 = lerp(p01, p12, t);
+        let p123 = lerp(p12, p23, t);
+        let point = lerp(p012, p123, t);
+        (
+            Self::new(self.p0, p01, p012, point),
+            Self::new(point, p123, p23, self.p3),
+        )
+    }
+
+    /// The part of the curve between `t0` and `t1`.
+    pub fn subsegment(&self, t0: f64, t1: f64) -> Self {
+        if t1 <= t0 {
+            let point = self.point_at(t0);
+            return Self::new(point, point, point, point);
+        }
+        let (head, _) = self.split(t1);
+        if t0 <= 0.0 {
+            return head;
+        }
+        head.split(t0 / t1).1
+    }
+
+    /// The curve traced in the opposite direction.
+    pub const fn reversed(&self) -> Self {
+        Self::new(self.p3, self.p2, self.p1, self.p0)
+    }
+
+    /// The parameters, strictly between 0 and 1, where the curve turns back
+    /// horizontally or vertically, so that together with its end points they
+    /// give the curve's exact bounds.
+    pub fn extrema(&self) -> Vec<f64> {
+        let axis = |p0: f64, p1: f64, p2: f64, p3: f64| {
+            // the derivative, divided by 3, is a t^2 + b t + c
+            let a = p3 - 3.0 * p2 + 3.0 * p1 - p0;
+            let b = 2.0 * (p2 - 2.0 * p1 + p0);
+            let c = p1 - p0;
+            if a.abs() <= f64::EPSILON {
+                if b.abs() <= f64::EPSILON {
+                    return Vec::new();
+                }
+                return vec![-c / b];
+            }
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return Vec::new();
+            }
+            let root = discriminant.sqrt();
+            vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+        };
+
+        let mut extrema = axis(self.p0.0, self.p1.0, self.p2.0, self.p3.0);
+        extrema.extend(axis(self.p0.1, self.p1.1, self.p2.1, self.p3.1));
+        extrema.retain(|t| *t > 0.0 && *t < 1.0);
+        extrema
+    }
+
+    /// Whether both control points lie within `tolerance` of the line
+    /// between the curve's end points, so that the line can stand in for it.
+    pub fn is_flat(&self, tolerance: f64) -> bool {
+        distance_to_segment(self.p1, self.p0, self.p3) <= tolerance
+            && distance_to_segment(self.p2, self.p0, self.p3) <= tolerance
+    }
+
+    /// Approximates the curve with lines that stray no further than
+    /// `tolerance` from it, subdividing only where the curve bends.
+    ///
+    /// `f` is called with the parameter and position of the end of each line,
+    /// in order, ending with the curve's end point at `t = 1`.
+    pub fn flatten<F: FnMut(f64, (f64, f64))>(&self, tolerance: f64, f: &mut F) {
+        self.flatten_range(0.0, 1.0, tolerance.max(f64::EPSILON), 0, f);
+    }
+
+    fn flatten_range<F: FnMut(f64, (f64, f64))>(
+        &self,
+        t0: f64,
+        t1: f64,
+        tolerance: f64,
+        depth: u32,
+        f: &mut F,
+    ) {
+        if depth >= MAX_DEPTH || self.is_flat(tolerance) {
+            f(t1, self.p3);
+            return;
+        }
+        let (head, tail) = self.split(0.5);
+        let t = (t0 + t1) / 2.0;
+        head.flatten_range(t0, t, tolerance, depth + 1, f);
+        tail.flatten_range(t, t1, tolerance, depth + 1, f);
+    }
+}
+
+/// A straight line or cubic curve between two points of a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Segment {
+    Line((f64, f64), (f64, f64)),
+    Curve(Cubic),
+}
+
+impl Segment {
+    pub const fn start(&self) -> (f64, f64) {
+        match self {
+            Self::Line(start, _) => *start,
+            Self::Curve(cubic) => cubic.p0,
+        }
+    }
+
+    pub const fn end(&self) -> (f64, f64) {
+        match self {
+            Self::Line(_, end) => *end,
+            Self::Curve(cubic) => cubic.p3,
+        }
+    }
+
+    pub fn point_at(&self, t: f64) -> (f64, f64) {
+        match self {
+            Self::Line(start, end) => lerp(*start, *end, t),
+            Self::Curve(cubic) => cubic.point_at(t),
+        }
+    }
+
+    pub fn angle_at(&self, t: f64) -> f64 {
+        match self {
+            Self::Line(start, end) => (end.1 - start.1).atan2(end.0 - start.0),
+            Self::Curve(cubic) => cubic.angle_at(t),
+        }
+    }
+
+    pub fn subsegment(&self, t0: f64, t1: f64) -> Self {
+        match self {
+            Self::Line(start, end) => Self::Line(lerp(*start, *end, t0), lerp(*start, *end, t1)),
+            Self::Curve(cubic) => Self::Curve(cubic.subsegment(t0, t1)),
+        }
+    }
+
+    pub const fn reversed(&self) -> Self {
+        match self {
+            Self::Line(start, end) => Self::Line(*end, *start),
+            Self::Curve(cubic) => Self::Curve(cubic.reversed()),
+        }
+    }
+
+    /// The smallest rectangle containing the segment, as its top-left and
+    /// bottom-right corners.
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let mut points = vec![self.start(), self.end()];
+        if let Self::Curve(cubic) = self {
+            points.extend(cubic.extrema().into_iter().map(|t| cubic.point_at(t)));
+        }
+        points.iter().fold(
+            (self.start(), self.start()),
+            |((x0, y0), (x1, y1)), (x, y)| ((x0.min(*x), y0.min(*y)), (x1.max(*x), y1.max(*y))),
+        )
+    }
+
+    /// See `Cubic::flatten`. A line is passed through as a single call.
+    pub fn flatten<F: FnMut(f64, (f64, f64))>(&self, tolerance: f64, f: &mut F) {
+        match self {
+            Self::Line(_, end) => f(1.0, *end),
+            Self::Curve(cubic) => cubic.flatten(tolerance, f),
+        }
+    }
+}
@@ -0,0 +1,480 @@
+// Boolean operations on the areas filled by two paths.
+//
+// Both paths are flattened into polygons, and the plane is cut into
+// horizontal slabs at every vertex and every crossing of two edges, so that
+// within a slab no two edges cross and they can be ordered from left to
+// right. Walking along each slab while counting windings, with respect to
+// each path, finds the intervals where the operation's result is filled.
+// Their sides, and the parts of the lines between slabs that are filled on
+// one side only, form the boundary of the result, and are linked into closed
+// loops.
+
+use crate::{
+    bezier,
+    enums::{BooleanOperation, FillRule, PathSegment},
+    point::Point,
+    subpath::Subpath,
+};
+use std::collections::HashMap;
+
+type Vertex = (f64, f64);
+// a vertex's coordinates as multiples of `GRID`, so that vertices can be
+// compared and hashed exactly
+type Key = (i64, i64);
+
+// the spacing of the grid vertices are snapped to, which merges intersections
+// computed from different pairs of edges that should meet at the same point
+const GRID: f64 = 1e-6;
+
+fn key((x, y): Vertex) -> Key {
+    ((x / GRID).round() as i64, (y / GRID).round() as i64)
+}
+
+fn snap(vertex: Vertex) -> Vertex {
+    let (x, y) = key(vertex);
+    (x as f64 * GRID, y as f64 * GRID)
+}
+
+fn cross((x0, y0): Vertex, (x1, y1): Vertex) -> f64 {
+    x0 * y1 - y0 * x1
+}
+
+fn dot((x0, y0): Vertex, (x1, y1): Vertex) -> f64 {
+    x0 * x1 + y0 * y1
+}
+
+fn sub((x0, y0): Vertex, (x1, y1): Vertex) -> Vertex {
+    (x0 - x1, y0 - y1)
+}
+
+// A polygon edge that is not horizontal, stored from its top to its bottom.
+struct Edge {
+    top: Vertex,
+    bottom: Vertex,
+    // +1 if the polygon runs down the edge, and -1 if it runs up it
+    winding: i32,
+    // 0 for the first path, 1 for the second
+    operand: usize,
+}
+
+impl Edge {
+    fn new(from: Vertex, to: Vertex, operand: usize) -> Option<Self> {
+        if from.1 < to.1 {
+            Some(Self {
+                top: from,
+                bottom: to,
+                winding: 1,
+                operand,
+            })
+        } else if from.1 > to.1 {
+            Some(Self {
+                top: to,
+                bottom: from,
+                winding: -1,
+                operand,
+            })
+        } else {
+            None
+        }
+    }
+
+    // the end points are returned exactly, so that the edges meeting there
+    // agree on where they meet
+    fn x_at(&self, y: f64) -> f64 {
+        if y <= self.top.1 {
+            self.top.0
+        } else if y >= self.bottom.1 {
+            self.bottom.0
+        } else {
+            let t = (y - self.top.1) / (self.bottom.1 - self.top.1);
+            self.top.0 + (self.bottom.0 - self.top.0) * t
+        }
+    }
+
+    // how much further apart two points are horizontally than they are from
+    // each other's edge, when both lie on lines parallel to this edge
+    fn horizontal_stretch(&self) -> f64 {
+        let (dx, dy) = sub(self.bottom, self.top);
+        dx.hypot(dy) / dy
+    }
+
+    fn left(&self) -> f64 {
+        self.top.0.min(self.bottom.0)
+    }
+
+    fn right(&self) -> f64 {
+        self.top.0.max(self.bottom.0)
+    }
+}
+
+/// Returns the segments of a path filling the area given by applying
+/// `operation` to the areas filled by `a` and `b` under `fill_rule`.
+///
+/// Curves are flattened to within `tolerance`, so the result is made up of
+/// straight lines only. Every subpath of the result is closed, and oriented
+/// so that the result fills the same area under either fill rule.
+pub fn apply(
+    a: &cairo::Path,
+    b: &cairo::Path,
+    operation: BooleanOperation,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Vec<PathSegment> {
+    let mut edges = polygon_edges(a, 0, tolerance);
+    edges.extend(polygon_edges(b, 1, tolerance));
+
+    let boundary = sweep(&edges, operation, [fill_rule, fill_rule]);
+    link_loops(&boundary)
+}
+
+/// Returns the segments of a path filling the same area as the closed
+/// `polygons` do under `fill_rule`, with overlaps merged and
+/// self-intersections resolved.
+pub fn fill(polygons: &[Vec<(f64, f64)>], fill_rule: FillRule) -> Vec<PathSegment> {
+    let mut edges = Vec::new();
+    for polygon in polygons {
+        push_polygon_edges(&mut edges, polygon, 0);
+    }
+
+    let boundary = sweep(&edges, BooleanOperation::Union, [fill_rule, fill_rule]);
+    link_loops(&boundary)
+}
+
+/// Returns the segments of a path filling the area given by applying
+/// `operation` to the areas the closed polygons `a` and `b` fill, each under
+/// its own fill rule.
+pub fn apply_polygons(
+    (a, a_fill_rule): (&[Vec<(f64, f64)>], FillRule),
+    (b, b_fill_rule): (&[Vec<(f64, f64)>], FillRule),
+    operation: BooleanOperation,
+) -> Vec<PathSegment> {
+    let mut edges = Vec::new();
+    for polygon in a {
+        push_polygon_edges(&mut edges, polygon, 0);
+    }
+    for polygon in b {
+        push_polygon_edges(&mut edges, polygon, 1);
+    }
+
+    let boundary = sweep(&edges, operation, [a_fill_rule, b_fill_rule]);
+    link_loops(&boundary)
+}
+
+// Flattens every subpath of the path into a closed polygon.
+fn polygon_edges(path: &cairo::Path, operand: usize, tolerance: f64) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for subpath in Subpath::from_path(path) {
+        push_polygon_edges(&mut edges, &subpath.flatten(tolerance), operand);
+    }
+    edges
+}
+
+// Horizontal edges are left out, as they never separate two parts of a slab.
+fn push_polygon_edges(edges: &mut Vec<Edge>, polygon: &[Vertex], operand: usize) {
+    let points: Vec<Vertex> = polygon.iter().copied().map(snap).collect();
+    for (i, from) in points.iter().enumerate() {
+        let to = points[(i + 1) % points.len()];
+        edges.extend(Edge::new(*from, to, operand));
+    }
+}
+
+// The heights of the lines the plane is cut along: those of every vertex and
+// of every point where two edges cross, snapped to the grid.
+fn sweep_lines(edges: &[Edge], order: &[usize]) -> Vec<f64> {
+    let mut lines: Vec<f64> = edges
+        .iter()
+        .flat_map(|edge| vec![edge.top.1, edge.bottom.1])
+        .collect();
+
+    // edges are visited from top to bottom, each compared with those that
+    // start above its bottom
+    for (n, i) in order.iter().enumerate() {
+        let a = &edges[*i];
+        for j in &order[(n + 1)..] {
+            let b = &edges[*j];
+            if b.top.1 >= a.bottom.1 {
+                break;
+            }
+            if b.left() > a.right() || a.left() > b.right() {
+                continue;
+            }
+            lines.extend(crossing_height(a, b));
+        }
+    }
+
+    lines.sort_by(|y0, y1| y0.partial_cmp(y1).unwrap());
+    lines.dedup_by(|y0, y1| key((0.0, *y0)) == key((0.0, *y1)));
+    lines
+}
+
+// The height at which two edges cross, if they cross strictly between the
+// tops and bottoms of both.
+fn crossing_height(a: &Edge, b: &Edge) -> Option<f64> {
+    let r = sub(a.bottom, a.top);
+    let s = sub(b.bottom, b.top);
+    let denominator = cross(r, s);
+    if denominator.abs() <= 1e-12 * dot(r, r).sqrt() * dot(s, s).sqrt() {
+        return None;
+    }
+    let qp = sub(b.top, a.top);
+    let t = cross(qp, s) / denominator;
+    let u = cross(qp, r) / denominator;
+    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+        Some(snap((0.0, a.top.1 + r.1 * t)).1)
+    } else {
+        None
+    }
+}
+
+// Finds the boundary of the result, as edges oriented so that the result lies
+// on their left.
+fn sweep(
+    edges: &[Edge],
+    operation: BooleanOperation,
+    fill_rules: [FillRule; 2],
+) -> Vec<(Vertex, Vertex)> {
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|i, j| edges[*i].top.1.partial_cmp(&edges[*j].top.1).unwrap());
+    let lines = sweep_lines(edges, &order);
+
+    let inside = |winding: [i32; 2]| {
+        operation.apply(
+            fill_rules[0].is_inside(winding[0]),
+            fill_rules[1].is_inside(winding[1]),
+        )
+    };
+
+    let mut boundary = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    let mut next = 0;
+    // the filled intervals along the bottom of the previous slab
+    let mut above: Vec<(f64, f64)> = Vec::new();
+
+    for slab in lines.windows(2) {
+        let (top, bottom) = (slab[0], slab[1]);
+        active.retain(|i| edges[*i].bottom.1 > top);
+        while next < order.len() && edges[order[next]].top.1 <= top {
+            if edges[order[next]].bottom.1 > top {
+                active.push(order[next]);
+            }
+            next += 1;
+        }
+
+        // no two edges cross within the slab, so they keep the order they
+        // have halfway down it
+        let middle = (top + bottom) / 2.0;
+        let mut crossings: Vec<(f64, f64, f64, usize)> = active
+            .iter()
+            .map(|i| {
+                let edge = &edges[*i];
+                (edge.x_at(top), edge.x_at(middle), edge.x_at(bottom), *i)
+            })
+            .collect();
+        crossings.sort_by(|c0, c1| c0.1.partial_cmp(&c1.1).unwrap());
+        // edges crossing right by one of the lines may appear slightly out of
+        // order there, as the line is snapped to the grid, so they are moved
+        // to meet instead, to keep the filled intervals from overlapping
+        for n in 1..crossings.len() {
+            crossings[n].0 = crossings[n].0.max(crossings[n - 1].0);
+            crossings[n].2 = crossings[n].2.max(crossings[n - 1].2);
+        }
+
+        // filled intervals, as the crossings of their left and right sides
+        let mut intervals: Vec<((f64, f64), (f64, f64))> = Vec::new();
+        let mut winding = [0, 0];
+        let mut start = None;
+        let mut n = 0;
+        while n < crossings.len() {
+            let (x_top, _, x_bottom, _) = crossings[n];
+            let was_inside = inside(winding);
+            // edges that coincide within the slab, to within the grid
+            // spacing, are crossed together
+            let tolerance = GRID * edges[crossings[n].3].horizontal_stretch();
+            while n < crossings.len()
+                && (crossings[n].0 - x_top).abs() <= tolerance
+                && (crossings[n].2 - x_bottom).abs() <= tolerance
+            {
+                let edge = &edges[crossings[n].3];
+                winding[edge.operand] += edge.winding;
+                n += 1;
+            }
+            match (was_inside, inside(winding)) {
+                (false, true) => start = Some((x_top, x_bottom)),
+                (true, false) => {
+                    if let Some(left) = start.take() {
+                        intervals.push((left, (x_top, x_bottom)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for ((left_top, left_bottom), (right_top, right_bottom)) in &intervals {
+            boundary.push(((*right_top, top), (*right_bottom, bottom)));
+            boundary.push(((*left_bottom, bottom), (*left_top, top)));
+        }
+
+        let below: Vec<(f64, f64)> = intervals
+            .iter()
+            .map(|((left, _), (right, _))| (*left, *right))
+            .collect();
+        push_horizontal_edges(&mut boundary, top, &above, &below);
+        above = intervals
+            .iter()
+            .map(|((_, left), (_, right))| (*left, *right))
+            .collect();
+    }
+    if let Some(last) = lines.last() {
+        push_horizontal_edges(&mut boundary, *last, &above, &[]);
+    }
+    boundary
+}
+
+// Adds the parts of the line at height `y` that are filled only above it or
+// only below it, given the filled intervals on either side.
+fn push_horizontal_edges(
+    boundary: &mut Vec<(Vertex, Vertex)>,
+    y: f64,
+    above: &[(f64, f64)],
+    below: &[(f64, f64)],
+) {
+    let mut xs: Vec<f64> = above
+        .iter()
+        .chain(below)
+        .flat_map(|(left, right)| vec![*left, *right])
+        .collect();
+    xs.sort_by(|x0, x1| x0.partial_cmp(x1).unwrap());
+    xs.dedup();
+
+    let covers = |intervals: &[(f64, f64)], x: f64| {
+        intervals
+            .iter()
+            .any(|(left, right)| *left <= x && x <= *right)
+    };
+    for pair in xs.windows(2) {
+        let (left, right) = (pair[0], pair[1]);
+        let middle = (left + right) / 2.0;
+        match (covers(above, middle), covers(below, middle)) {
+            (false, true) => boundary.push(((left, y), (right, y))),
+            (true, false) => boundary.push(((right, y), (left, y))),
+            _ => {}
+        }
+    }
+}
+
+// Links the boundary edges into closed loops. Where several loops meet at a
+// vertex, the sharpest left turn is taken, which keeps areas touching only at
+// a corner in separate loops.
+fn link_loops(boundary: &[(Vertex, Vertex)]) -> Vec<PathSegment> {
+    let mut outgoing: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (i, (from, _)) in boundary.iter().enumerate() {
+        outgoing.entry(key(*from)).or_default().push(i);
+    }
+
+    let mut used = vec![false; boundary.len()];
+    let mut segments = Vec::new();
+
+    for first in 0..boundary.len() {
+        if used[first] {
+            continue;
+        }
+
+        let mut points = vec![boundary[first].0];
+        let mut current = first;
+        used[current] = true;
+
+        loop {
+            let (from, to) = boundary[current];
+            let incoming = sub(to, from);
+            let next = outgoing.get(&key(to)).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|candidate| !used[*candidate] || *candidate == first)
+                    .max_by(|c0, c1| {
+                        let turn = |candidate: usize| {
+                            let (from, to) = boundary[candidate];
+                            let outgoing = sub(to, from);
+                            cross(incoming, outgoing).atan2(dot(incoming, outgoing))
+                        };
+                        turn(*c0).partial_cmp(&turn(*c1)).unwrap()
+                    })
+            });
+
+            match next {
+                // returning to the first edge closes the loop, even where
+                // other boundary edges also meet at its start
+                Some(next) if next == first => break,
+                Some(next) => {
+                    points.push(to);
+                    used[next] = true;
+                    current = next;
+                }
+                None => break,
+            }
+        }
+
+        // loops of next to no area are left over where edges nearly meet
+        let mut points = remove_collinear(&points);
+        if points.len() < 3 || signed_area(&points).abs() <= GRID {
+            continue;
+        }
+        // each loop starts from its top left corner, so that the result does
+        // not depend on the order the edges were found in
+        let first = (0..points.len())
+            .min_by(|i, j| {
+                let (a, b) = (points[*i], points[*j]);
+                (a.1, a.0).partial_cmp(&(b.1, b.0)).unwrap()
+            })
+            .unwrap_or(0);
+        points.rotate_left(first);
+        segments.push(PathSegment::MoveTo(Point::from(points[0])));
+        for point in &points[1..] {
+            segments.push(PathSegment::LineTo(Point::from(*point)));
+        }
+        segments.push(PathSegment::ClosePath);
+    }
+    segments
+}
+
+fn signed_area(points: &[Vertex]) -> f64 {
+    let count = points.len();
+    (0..count)
+        .map(|i| cross(points[i], points[(i + 1) % count]))
+        .sum::<f64>()
+        / 2.0
+}
+
+// Drops vertices that lie on the straight line between their neighbours in a
+// closed loop, to within the grid spacing, which cutting the plane into slabs
+// leaves behind along every slanted edge.
+fn remove_collinear(points: &[Vertex]) -> Vec<Vertex> {
+    let redundant = |previous: Vertex, point: Vertex, next: Vertex| {
+        bezier::distance_to_segment(point, previous, next) <= GRID
+    };
+
+    let mut kept: Vec<Vertex> = Vec::with_capacity(points.len());
+    for point in points {
+        while kept.len() >= 2 && redundant(kept[kept.len() - 2], kept[kept.len() - 1], *point) {
+            kept.pop();
+        }
+        kept.push(*point);
+    }
+
+    // the loop's first and last vertices are only checked against each other
+    // once the rest have been
+    loop {
+        let count = kept.len();
+        if count < 3 {
+            break;
+        }
+        if redundant(kept[count - 2], kept[count - 1], kept[0]) {
+            kept.pop();
+        } else if redundant(kept[count - 1], kept[0], kept[1]) {
+            kept.remove(0);
+        } else {
+            break;
+        }
+    }
+    kept
+}
@@ -0,0 +1,336 @@
+// Converts a stroke whose width varies along the path into the outline of
+// the area it covers.
+//
+// Each subpath is flattened, and the width is looked up at each of its
+// points by the distance along the subpath. Each line is then covered by the
+// convex hull of the circles the width gives at either end, which rounds
+// every join and tapers smoothly between widths, and the caps at the ends of
+// open subpaths are cut to shape. The pieces are merged into a single fill
+// with the nonzero winding rule, as for a stroke of constant width.
+
+use crate::{
+    bezier::{self, MIN_TOLERANCE},
+    boolean,
+    enums::{FillRule, LineCap, PathSegment, WidthProfile},
+    subpath::Subpath,
+};
+use std::f64::consts::PI;
+
+type Vertex = (f64, f64);
+
+// circles are never split into more lines than this, nor the easing between
+// two widths into more samples, however small the tolerance
+const MAX_CIRCLE_STEPS: usize = 256;
+const MAX_EASING_STEPS: usize = 64;
+
+/// Returns the segments of a path filling the area covered by stroking `path`
+/// with a width that follows `profile`.
+///
+/// Widths given as stops are placed by the fraction of the way along each
+/// subpath they apply at, and widths given for points apply at the start of
+/// each subpath and at the end of each of its lines and curves, in the order
+/// they appear in the path, with the last width repeating if too few are
+/// given. Between them the width changes linearly, or, if `smooth` is true,
+/// eases in and out of each given width. The open ends of subpaths are drawn
+/// with `line_cap`, and curves and rounded parts of the outline are
+/// flattened to within `tolerance`, or a thousandth of the widest width if
+/// that is larger.
+pub fn outline(
+    path: &cairo::Path,
+    profile: &WidthProfile,
+    line_cap: LineCap,
+    smooth: bool,
+    tolerance: f64,
+) -> Vec<PathSegment> {
+    let widest = match profile {
+        WidthProfile::Stops(stops) => stops.iter().map(|(_, width)| *width).fold(0.0, f64::max),
+        WidthProfile::Points(widths) => widths.iter().copied().fold(0.0, f64::max),
+    };
+    let tolerance = tolerance.max(widest * MIN_TOLERANCE).max(f64::EPSILON);
+    let mut pieces = Vec::new();
+    let mut point_widths = match profile {
+        WidthProfile::Points(widths) => widths.clone(),
+        WidthProfile::Stops(_) => Vec::new(),
+    };
+    point_widths.reverse();
+    let mut last_width = 0.0;
+
+    for subpath in Subpath::from_path(path) {
+        let (points, lengths, vertices) = flatten(&subpath, tolerance);
+        let total = lengths[lengths.len() - 1];
+        let stops: Vec<(f64, f64)> = match profile {
+            WidthProfile::Stops(stops) => {
+                let mut stops: Vec<(f64, f64)> = stops
+                    .iter()
+                    .map(|(t, width)| (t.clamp(0.0, 1.0) * total, *width))
+                    .collect();
+                stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+                stops
+            }
+            WidthProfile::Points(_) => {
+                let mut stops: Vec<(f64, f64)> = vertices
+                    .iter()
+                    .map(|index| {
+                        last_width = point_widths.pop().unwrap_or(last_width);
+                        (lengths[*index], last_width)
+                    })
+                    .collect();
+                // the line closing a subpath ends at its start, and takes its
+                // width from there
+                if subpath.closed {
+                    stops.push((total, stops[0].1));
+                }
+                stops
+            }
+        };
+        let (points, lengths) = insert_points(
+            &points,
+            &lengths,
+            &samples(&stops, total, smooth, tolerance),
+        );
+        let radii: Vec<f64> = lengths
+            .iter()
+            .map(|length| (width_at(&stops, *length, smooth) / 2.0).max(0.0))
+            .collect();
+        brush_run(
+            &mut pieces,
+            &points,
+            &radii,
+            subpath.closed,
+            line_cap,
+            tolerance,
+        );
+    }
+
+    boolean::fill(&pieces, FillRule::Winding)
+}
+
+// The points a subpath is flattened to, including the end of the line
+// closing it, along with the distance along the subpath to each of them and
+// the indices of the ones at the start and at the ends of its segments.
+fn flatten(subpath: &Subpath, tolerance: f64) -> (Vec<Vertex>, Vec<f64>, Vec<usize>) {
+    let mut points = vec![subpath.start];
+    let mut lengths = vec![0.0];
+    let mut vertices = vec![0];
+    for (i, segment) in subpath.closed_segments().iter().enumerate() {
+        segment.flatten(tolerance, &mut |_, point| {
+            let length =
+                lengths[lengths.len() - 1] + bezier::distance(points[points.len() - 1], point);
+            points.push(point);
+            lengths.push(length);
+        });
+        // the end of the closing line is the start again
+        if i < subpath.segments.len() {
+            vertices.push(points.len() - 1);
+        }
+    }
+    (points, lengths, vertices)
+}
+
+// The lengths along a subpath, strictly between its ends, where the width
+// has to be sampled for lines between the samples to follow it: at each stop,
+// and, where the width eases between stops, often enough to follow the
+// easing to within `tolerance`.
+fn samples(stops: &[(f64, f64)], total: f64, smooth: bool, tolerance: f64) -> Vec<f64> {
+    let mut samples = Vec::new();
+    for (i, (length, width)) in stops.iter().enumerate() {
+        if let Some((next, next_width)) = stops.get(i + 1) {
+            // the error of a line across a stretch of the easing is at most
+            // an eighth of its length squared times the easing's greatest
+            // second derivative
+            let steps = if smooth {
+                let change = (next_width - width).abs() / 2.0;
+                ((3.0 * change / (8.0 * tolerance)).sqrt().ceil() as usize)
+                    .clamp(1, MAX_EASING_STEPS)
+            } else {
+                1
+            };
+            samples.extend(
+                (0..steps).map(|step| length + (next - length) * step as f64 / steps as f64),
+            );
+        } else {
+            samples.push(*length);
+        }
+    }
+    samples.retain(|length| *length > 0.0 && *length < total);
+    samples
+}
+
+// Adds points along the lines between `points` at the lengths `samples`
+// along them, returning the points and the lengths to each of them.
+fn insert_points(points: &[Vertex], lengths: &[f64], samples: &[f64]) -> (Vec<Vertex>, Vec<f64>) {
+    let mut samples = samples.to_vec();
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut samples = samples.into_iter().peekable();
+
+    let mut new_points = vec![points[0]];
+    let mut new_lengths = vec![lengths[0]];
+    for i in 1..points.len() {
+        let (l0, l1) = (lengths[i - 1], lengths[i]);
+        while let Some(length) = samples.next_if(|length| *length < l1) {
+            if length > l0 && length > new_lengths[new_lengths.len() - 1] {
+                new_points.push(bezier::lerp(
+                    points[i - 1],
+                    points[i],
+                    (length - l0) / (l1 - l0),
+                ));
+                new_lengths.push(length);
+            }
+        }
+        new_points.push(points[i]);
+        new_lengths.push(l1);
+    }
+    (new_points, new_lengths)
+}
+
+// The width at `length` along a subpath, from stops sorted by their lengths.
+fn width_at(stops: &[(f64, f64)], length: f64, smooth: bool) -> f64 {
+    if stops.is_empty() {
+        return 0.0;
+    }
+    let next = stops.iter().position(|(at, _)| *at > length);
+    match next {
+        None => stops[stops.len() - 1].1,
+        Some(0) => stops[0].1,
+        Some(i) => {
+            let ((l0, w0), (l1, w1)) = (stops[i - 1], stops[i]);
+            let mut t = (length - l0) / (l1 - l0);
+            if smooth {
+                t = t * t * (3.0 - 2.0 * t);
+            }
+            w0 + (w1 - w0) * t
+        }
+    }
+}
+
+// Adds the pieces covering the lines between `points`, with the circles of
+// `radii` around them, to `pieces`.
+fn brush_run(
+    pieces: &mut Vec<Vec<Vertex>>,
+    points: &[Vertex],
+    radii: &[f64],
+    closed: bool,
+    line_cap: LineCap,
+    tolerance: f64,
+) {
+    let mut lines: Vec<(usize, usize)> = Vec::new();
+    let mut last = 0;
+    for i in 1..points.len() {
+        if points[i] != points[last] {
+            lines.push((last, i));
+            last = i;
+        }
+    }
+
+    if lines.is_empty() {
+        // a subpath with no length is drawn as a dot with round caps, as
+        // cairo draws one, and has no direction to draw other caps in
+        if matches!(line_cap, LineCap::Round) {
+            add_piece(pieces, circle(points[0], radii[0], tolerance));
+        }
+        return;
+    }
+
+    let count = lines.len();
+    for (n, (i, j)) in lines.iter().enumerate() {
+        let (start, end) = (points[*i], points[*j]);
+        let mut hull_points = Vec::new();
+        for (point, radius, other, at_end) in [
+            (start, radii[*i], end, !closed && n == 0),
+            (end, radii[*j], start, !closed && n + 1 == count),
+        ] {
+            if at_end && !matches!(line_cap, LineCap::Round) {
+                hull_points.extend(cap(point, radius, other, line_cap));
+            } else {
+                hull_points.extend(circle(point, radius, tolerance));
+            }
+        }
+        add_piece(pieces, convex_hull(hull_points));
+    }
+}
+
+// The corners of a butt or square cap of `radius` at `point`, on the end of
+// a line from `other`.
+fn cap(point: Vertex, radius: f64, other: Vertex, line_cap: LineCap) -> Vec<Vertex> {
+    let length = bezier::distance(other, point);
+    let direction = ((point.0 - other.0) / length, (point.1 - other.1) / length);
+    let extension = if matches!(line_cap, LineCap::Square) {
+        radius
+    } else {
+        0.0
+    };
+    let end = (
+        point.0 + direction.0 * extension,
+        point.1 + direction.1 * extension,
+    );
+    let side = (-direction.1 * radius, direction.0 * radius);
+    vec![
+        (end.0 + side.0, end.1 + side.1),
+        (end.0 - side.0, end.1 - side.1),
+    ]
+}
+
+// The points of a circle of `radius` around `center`, close enough together
+// that the lines between them stay within `tolerance` of it.
+fn circle(center: Vertex, radius: f64, tolerance: f64) -> Vec<Vertex> {
+    if radius <= 0.0 {
+        return vec![center];
+    }
+    let step = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        PI / 2.0
+    };
+    let steps = ((2.0 * PI / step).ceil() as usize).clamp(4, MAX_CIRCLE_STEPS);
+    (0..steps)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / steps as f64;
+            (
+                center.0 + radius * angle.cos(),
+                center.1 + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+fn cross((x0, y0): Vertex, (x1, y1): Vertex, (x2, y2): Vertex) -> f64 {
+    (x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0)
+}
+
+// The convex hull of `points`, counter-clockwise in a y-up coordinate
+// system, by Andrew's monotone chain algorithm.
+fn convex_hull(mut points: Vec<Vertex>) -> Vec<Vertex> {
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut lower = Vec::new();
+    for point in &points {
+        extend_chain(&mut lower, *point);
+    }
+    let mut upper = Vec::new();
+    for point in points.iter().rev() {
+        extend_chain(&mut upper, *point);
+    }
+    // the last point of each chain starts the other
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+// Adds `point` to a chain of the hull, dropping the points it shows not to
+// turn left.
+fn extend_chain(chain: &mut Vec<Vertex>, point: Vertex) {
+    while chain.len() >= 2 && cross(chain[chain.len() - 2], chain[chain.len() - 1], point) <= 0.0 {
+        chain.pop();
+    }
+    chain.push(point);
+}
+
+fn add_piece(pieces: &mut Vec<Vec<Vertex>>, piece: Vec<Vertex>) {
+    if piece.len() >= 3 {
+        pieces.push(piece);
+    }
+}
@@ -1,9 +1,15 @@
 use crate::{
-    enums::{Antialias, Error, FillRule, FontSlant, FontWeight, LineCap, LineJoin, Operator},
+    bezier::{self, Cubic},
+    enums::{
+        Alignment, Antialias, Error, FillRule, FontSlant, FontWeight, LineCap, LineJoin, Operator,
+        PathSegment,
+    },
     font_face::{FontFace, Raw as FontFaceRaw},
     image_surface::ImageSurface,
     linear_gradient::LinearGradient,
+    markers::{self, MarkerStyle},
     matrix::{Matrix, Raw as MatrixRaw},
+    measure::PathMeasure,
     mesh::Mesh,
     path::{Path, Raw as PathRaw},
     pdf_surface::PdfSurface,
@@ -11,6 +17,8 @@ use crate::{
     ps_surface::PsSurface,
     radial_gradient::RadialGradient,
     rgba::Rgba,
+    shapes,
+    sketch::{self, SketchStyle},
     solid_pattern::SolidPattern,
     surface_pattern::SurfacePattern,
     svg_surface::SvgSurface,
@@ -28,6 +36,9 @@ unsafe impl Sync for Raw {}
 
 pub type Context = ResourceArc<Raw>;
 
+// cluster text, glyph index, origin, advance, outline
+type GlyphPath = (String, u64, Point, Vector, Path);
+
 #[rustler::nif]
 fn context_new(surface: ImageSurface) -> Result<Context, Error> {
     match cairo::Context::new(&surface.surface) {
@@ -156,6 +167,155 @@ fn context_rel_curve_to(ctx: Context, vec1: Vector, vec2: Vector, vec3: Vector)
     ctx.context.rel_curve_to(x1, y1, x2, y2, x3, y3);
 }
 
+// The current point, after moving to `point` if there is none, as
+// `cairo::Context::curve_to` does.
+fn current_point_or(context: &cairo::Context, point: (f64, f64)) -> Result<(f64, f64), Error> {
+    match context.has_current_point() {
+        Ok(true) => {}
+        Ok(false) => context.move_to(point.0, point.1),
+        Err(err) => return Err(err.into()),
+    }
+    match context.current_point() {
+        Ok(current) => Ok(current),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn context_quad_to(ctx: Context, control: Point, point: Point) -> Result<(), Error> {
+    let start = current_point_or(&ctx.context, control.to_tuple())?;
+    let cubic = Cubic::from_quadratic(start, control.to_tuple(), point.to_tuple());
+    let ((x1, y1), (x2, y2), (x3, y3)) = (cubic.p1, cubic.p2, cubic.p3);
+    ctx.context.curve_to(x1, y1, x2, y2, x3, y3);
+    Ok(())
+}
+
+#[rustler::nif]
+fn context_rel_quad_to(ctx: Context, vec1: Vector, vec2: Vector) {
+    let cubic = Cubic::from_quadratic((0.0, 0.0), vec1.to_tuple(), vec2.to_tuple());
+    let ((x1, y1), (x2, y2), (x3, y3)) = (cubic.p1, cubic.p2, cubic.p3);
+    ctx.context.rel_curve_to(x1, y1, x2, y2, x3, y3);
+}
+
+#[rustler::nif]
+fn context_arc_to(
+    ctx: Context,
+    radii: (f64, f64),
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    point: Point,
+) -> Result<(), Error> {
+    let start = current_point_or(&ctx.context, point.to_tuple())?;
+    match bezier::endpoint_arc(start, radii, rotation, large_arc, sweep, point.to_tuple()) {
+        Some(curves) => {
+            for curve in curves {
+                let ((x1, y1), (x2, y2), (x3, y3)) = (curve.p1, curve.p2, curve.p3);
+                ctx.context.curve_to(x1, y1, x2, y2, x3, y3);
+            }
+        }
+        None => ctx.context.line_to(point.x, point.y),
+    }
+    Ok(())
+}
+
+#[rustler::nif]
+fn context_ellipse(ctx: Context, center: Point, rx: f64, ry: f64) {
+    for segment in shapes::ellipse(center.to_tuple(), (rx, ry)) {
+        segment.append_to(&ctx.context);
+    }
+}
+
+#[rustler::nif]
+fn context_rounded_rectangle(
+    ctx: Context,
+    origin: Point,
+    width: f64,
+    height: f64,
+    radii: (f64, f64, f64, f64),
+) {
+    let radii = [radii.0, radii.1, radii.2, radii.3];
+    for segment in shapes::rounded_rectangle(origin.to_tuple(), width, height, radii) {
+        segment.append_to(&ctx.context);
+    }
+}
+
+#[rustler::nif]
+fn context_regular_polygon(
+    ctx: Context,
+    center: Point,
+    radius: f64,
+    sides: u32,
+    rotation: f64,
+) -> Result<(), Error> {
+    for segment in shapes::regular_polygon(center.to_tuple(), radius, sides, rotation)? {
+        segment.append_to(&ctx.context);
+    }
+    Ok(())
+}
+
+#[rustler::nif]
+fn context_star(
+    ctx: Context,
+    center: Point,
+    outer_radius: f64,
+    inner_radius: f64,
+    points: u32,
+    rotation: f64,
+) -> Result<(), Error> {
+    let center = center.to_tuple();
+    for segment in shapes::star(center, outer_radius, inner_radius, points, rotation)? {
+        segment.append_to(&ctx.context);
+    }
+    Ok(())
+}
+
+#[rustler::nif]
+fn context_superellipse(
+    ctx: Context,
+    center: Point,
+    radii: (f64, f64),
+    exponent: f64,
+    rotation: f64,
+    tolerance: f64,
+) {
+    let center = center.to_tuple();
+    for segment in shapes::superellipse(center, radii, exponent, rotation, tolerance) {
+        segment.append_to(&ctx.context);
+    }
+}
+
+#[rustler::nif]
+fn context_spiral(
+    ctx: Context,
+    center: Point,
+    radii: (f64, f64),
+    turns: f64,
+    rotation: f64,
+    tolerance: f64,
+) -> Result<(), Error> {
+    let center = center.to_tuple();
+    for segment in shapes::spiral(center, radii.0, radii.1, turns, rotation, tolerance)? {
+        segment.append_to(&ctx.context);
+    }
+    Ok(())
+}
+
+#[rustler::nif]
+fn context_rose(
+    ctx: Context,
+    center: Point,
+    radius: f64,
+    k: (u32, u32),
+    rotation: f64,
+    tolerance: f64,
+) -> Result<(), Error> {
+    for segment in shapes::rose(center.to_tuple(), radius, k, rotation, tolerance)? {
+        segment.append_to(&ctx.context);
+    }
+    Ok(())
+}
+
 #[rustler::nif]
 fn context_line_to(ctx: Context, point: Point) {
     let (x, y) = point.to_tuple();
@@ -223,6 +383,81 @@ fn context_fill_preserve(context: Context) -> Result<(), Error> {
     }
 }
 
+#[rustler::nif]
+fn context_fill_markers(context: Context, style: MarkerStyle) -> Result<(), Error> {
+    match fill_markers(&context.context, &style) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Fills the markers `style` places on the current path, sized for the
+// context's line width, and leaves the current path as it was to be stroked.
+// The markers are filled with the nonzero winding rule, so that overlapping
+// markers do not cancel each other out.
+fn fill_markers(context: &cairo::Context, style: &MarkerStyle) -> Result<(), cairo::Error> {
+    let current_path = context.copy_path()?;
+    let segments = markers::place(&current_path, style, context.line_width());
+
+    context.save()?;
+    context.new_path();
+    for segment in segments {
+        segment.append_to(context);
+    }
+    context.set_fill_rule(cairo::FillRule::Winding);
+    let filled = context.fill();
+    context.restore()?;
+    context.append_path(&current_path);
+    filled
+}
+
+#[rustler::nif]
+fn context_sketch_stroke(context: Context, path: Path, style: SketchStyle) -> Result<(), Error> {
+    let segments = sketch::stroke(&path.path, &style);
+    match stroke_sketch(&context.context, &segments, context.context.line_width()) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn context_sketch_fill(context: Context, path: Path, style: SketchStyle) -> Result<(), Error> {
+    let line_width = context.context.line_width();
+    let segments = sketch::hachure(
+        &path.path,
+        &style,
+        style.hachure_gap.unwrap_or(4.0 * line_width),
+        context.context.fill_rule().into(),
+        context.context.tolerance(),
+    )?;
+    let fill_weight = style.fill_weight.unwrap_or(line_width / 2.0);
+    match stroke_sketch(&context.context, &segments, fill_weight) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Strokes a sketch with the current source and line settings, but the given
+// line width, and leaves the current path as it was.
+fn stroke_sketch(
+    context: &cairo::Context,
+    segments: &[PathSegment],
+    line_width: f64,
+) -> Result<(), cairo::Error> {
+    let current_path = context.copy_path()?;
+
+    context.save()?;
+    context.new_path();
+    for segment in segments {
+        segment.append_to(context);
+    }
+    context.set_line_width(line_width);
+    let stroked = context.stroke();
+    context.restore()?;
+    context.append_path(&current_path);
+    stroked
+}
+
 #[rustler::nif]
 fn context_paint(context: Context) -> Result<(), Error> {
     match context.context.paint() {
@@ -309,6 +544,42 @@ fn context_text_path(context: Context, text: String) {
     context.context.text_path(&text);
 }
 
+#[rustler::nif]
+fn context_show_text_along_path(
+    context: Context,
+    path: Path,
+    text: String,
+    offset: f64,
+    alignment: Alignment,
+) -> Result<(), Error> {
+    match show_text_along_path(&context.context, &path.path, &text, offset, alignment) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn context_text_path_along_path(
+    context: Context,
+    path: Path,
+    text: String,
+    offset: f64,
+    alignment: Alignment,
+) -> Result<(), Error> {
+    match text_path_along_path(&context.context, &path.path, &text, offset, alignment) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn context_glyph_paths(context: Context, text: String) -> Result<Vec<GlyphPath>, Error> {
+    match glyph_paths(&context.context, &text) {
+        Ok(glyph_paths) => Ok(glyph_paths),
+        Err(err) => Err(err.into()),
+    }
+}
+
 #[rustler::nif]
 fn context_set_font_size(context: Context, font_size: f64) {
     context.context.set_font_size(font_size);
@@ -653,3 +924,169 @@ fn context_stroke_extents(context: Context) -> Result<(Point, Point), Error> {
         Err(err) => Err(err.into()),
     }
 }
+
+fn show_text_along_path(
+    context: &cairo::Context,
+    path: &cairo::Path,
+    text: &str,
+    offset: f64,
+    alignment: Alignment,
+) -> Result<(), cairo::Error> {
+    for (matrix, glyph) in glyphs_along_path(context, path, text, offset, alignment)? {
+        context.save()?;
+        context.transform(matrix);
+        context.show_glyphs(&[glyph])?;
+        context.restore()?;
+    }
+    Ok(())
+}
+
+fn text_path_along_path(
+    context: &cairo::Context,
+    path: &cairo::Path,
+    text: &str,
+    offset: f64,
+    alignment: Alignment,
+) -> Result<(), cairo::Error> {
+    for (matrix, glyph) in glyphs_along_path(context, path, text, offset, alignment)? {
+        context.save()?;
+        context.transform(matrix);
+        context.glyph_path(&[glyph]);
+        context.restore()?;
+    }
+    Ok(())
+}
+
+// Lays the glyphs for `text` out along `path`, returning each glyph positioned
+// around its own origin along with the matrix that moves it onto the path.
+//
+// Lengths along the path are measured to within the context's tolerance, and
+// points along curves are found on the curves themselves, rather than on the
+// path as `copy_path_flat` would flatten it. Each glyph is centered on the
+// point at the middle of its advance, rotated to the path's tangent there,
+// and glyphs that would fall off either end of the path are dropped.
+fn glyphs_along_path(
+    context: &cairo::Context,
+    path: &cairo::Path,
+    text: &str,
+    offset: f64,
+    alignment: Alignment,
+) -> Result<Vec<(cairo::Matrix, cairo::Glyph)>, cairo::Error> {
+    let measure = PathMeasure::new(path, context.tolerance());
+    let length = measure.length();
+
+    let scaled_font = context.scaled_font();
+    let (glyphs, _clusters) = scaled_font.text_to_glyphs(0.0, 0.0, text)?;
+    let text_width = scaled_font.text_extents(text).x_advance;
+
+    let start = offset
+        + match alignment {
+            Alignment::Left => 0.0,
+            Alignment::Center => (length - text_width) / 2.0,
+            Alignment::Right => length - text_width,
+        };
+
+    Ok(glyphs
+        .into_iter()
+        .filter_map(|glyph| {
+            let half_advance = scaled_font.glyph_extents(&[glyph]).x_advance / 2.0;
+            let ((x, y), angle) = measure.point_at(start + glyph.x + half_advance)?;
+
+            let mut matrix = cairo::Matrix::identity();
+            matrix.translate(x, y);
+            matrix.rotate(angle);
+
+            Some((
+                matrix,
+                cairo::Glyph {
+                    index: glyph.index,
+                    x: -half_advance,
+                    y: 0.0,
+                },
+            ))
+        })
+        .collect())
+}
+
+// Splits `text` into its glyphs in the context's current font, returning for
+// each one the text of the cluster it belongs to, its index in the font, its
+// origin and advance, and its outline.
+//
+// Glyphs are positioned starting at the current point, or the origin if there
+// is none, so appending every outline reproduces the path `text_path` would
+// create. The context's current path is left untouched.
+fn glyph_paths(context: &cairo::Context, text: &str) -> Result<Vec<GlyphPath>, cairo::Error> {
+    let (x, y) = if context.has_current_point()? {
+        context.current_point()?
+    } else {
+        (0.0, 0.0)
+    };
+
+    let scaled_font = context.scaled_font();
+    let (glyphs, clusters) = scaled_font.text_to_glyphs(x, y, text)?;
+
+    // toy fonts map each character to exactly one glyph, so fall back to that
+    // if the font does not provide cluster information
+    let clusters = if clusters.is_empty() {
+        text.chars()
+            .map(|c| cairo::TextCluster {
+                num_bytes: c.len_utf8() as i32,
+                num_glyphs: 1,
+            })
+            .collect()
+    } else {
+        clusters
+    };
+
+    let current_path = context.copy_path()?;
+    let glyph_paths = outline_glyphs(context, text, glyphs, &clusters);
+    // the current path is put back even if a glyph could not be outlined
+    context.new_path();
+    context.append_path(&current_path);
+
+    glyph_paths
+}
+
+// Outlines each glyph in turn, along with the text of its cluster, leaving the
+// last glyph's outline as the current path.
+fn outline_glyphs(
+    context: &cairo::Context,
+    text: &str,
+    glyphs: Vec<cairo::Glyph>,
+    clusters: &[cairo::TextCluster],
+) -> Result<Vec<GlyphPath>, cairo::Error> {
+    let scaled_font = context.scaled_font();
+    let mut glyph_paths = Vec::with_capacity(glyphs.len());
+    let mut glyphs = glyphs.into_iter();
+    let mut byte_offset = 0;
+
+    for cluster in clusters {
+        let byte_end = byte_offset + cluster.num_bytes as usize;
+        let cluster_text = text.get(byte_offset..byte_end).unwrap_or_default();
+        byte_offset = byte_end;
+
+        for glyph in glyphs.by_ref().take(cluster.num_glyphs as usize) {
+            let extents = scaled_font.glyph_extents(&[glyph]);
+
+            context.new_path();
+            context.glyph_path(&[glyph]);
+            let path = context.copy_path()?;
+
+            glyph_paths.push((
+                cluster_text.to_string(),
+                glyph.index,
+                Point {
+                    x: glyph.x,
+                    y: glyph.y,
+                },
+                Vector {
+                    x: extents.x_advance,
+                    y: extents.y_advance,
+                },
+                ResourceArc::new(PathRaw { path }),
+            ));
+        }
+    }
+
+    Ok(glyph_paths)
+}
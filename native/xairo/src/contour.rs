@@ -0,0 +1,379 @@
+// Contour lines, and the filled bands between them, of a grid of values,
+// by marching squares.
+//
+// Each square cell between four neighbouring values is crossed by the
+// contour at a level on each of its edges whose ends fall either side of
+// it, at the point found by interpolating between them. The crossings are
+// joined in pairs so that the values at or above the level are always on
+// the same side of the line, and in saddle cells, where only diagonally
+// opposite corners are above the level, the average of the four values
+// decides whether those corners are joined or kept apart. The lines are then
+// chained into polylines through neighbouring cells.
+//
+// A band between two levels is the area at or above the lower level less
+// the area at or above the upper one. Each area is outlined by the contours
+// of the grid surrounded by values below every level, which close the
+// outlines along the grid's edges, with the upper area's outlines reversed
+// so that the band fills under either fill rule.
+
+use crate::{
+    enums::{Channel, Error, PathSegment},
+    image_surface::{self, ImageSurface},
+    path::{self, Path},
+    point::Point,
+    simplify, subpath,
+};
+use std::collections::HashMap;
+
+type Vertex = (f64, f64);
+
+// An edge of the grid, as the grid point at its top or left end, and
+// whether it runs down from that point rather than across.
+type Edge = (i64, i64, bool);
+
+/// Whether contours are returned as lines or as the bands between them,
+/// and whether they are smoothed into curves.
+#[derive(rustler::NifMap)]
+pub struct ContourOptions {
+    pub bands: bool,
+    pub smooth: bool,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn contour_grid(
+    grid: Vec<Vec<f64>>,
+    levels: Vec<f64>,
+    options: ContourOptions,
+) -> Result<Vec<Path>, Error> {
+    let grid = Grid::from_rows(&grid)?;
+    paths(&grid, &levels, &options)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn contour_image_surface(
+    surface: ImageSurface,
+    channel: Channel,
+    levels: Vec<f64>,
+    options: ContourOptions,
+) -> Result<Vec<Path>, Error> {
+    let grid = Grid::from_surface(&surface.surface, channel)?;
+    paths(&grid, &levels, &options)
+}
+
+fn paths(grid: &Grid, levels: &[f64], options: &ContourOptions) -> Result<Vec<Path>, Error> {
+    let contours = if options.bands {
+        isobands(grid, levels)
+    } else {
+        isolines(grid, levels)
+    };
+    contours
+        .iter()
+        .map(|segments| {
+            let contour = path::from_segments(segments)?;
+            if options.smooth {
+                // a centripetal spline, which never loops between points
+                let subpaths = simplify::smooth(&contour.path, 0.5);
+                path::from_segments(&subpath::to_path_segments(&subpaths))
+            } else {
+                Ok(contour)
+            }
+        })
+        .collect()
+}
+
+/// A grid of values, with the value in column `x` and row `y` placed at
+/// `(x + offset, y + offset)`.
+pub struct Grid {
+    width: i64,
+    height: i64,
+    values: Vec<f64>,
+    offset: f64,
+}
+
+impl Grid {
+    /// Builds a grid from rows of values, placing each value at its column
+    /// and row. Returns `Error::InvalidSize` if the rows differ in length.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(Error::InvalidSize);
+        }
+        Ok(Self {
+            width: width as i64,
+            height: rows.len() as i64,
+            values: rows.concat(),
+            offset: 0.0,
+        })
+    }
+
+    /// Builds a grid from one channel of an `A8`, `Argb32` or `Rgb24`
+    /// surface, from `0` to `1`, placing each value at the centre of its
+    /// pixel. Colours are read unpremultiplied, and `A8` surfaces give their
+    /// alpha whatever the channel. Returns `Error::InvalidFormat` for
+    /// surfaces in other formats.
+    pub fn from_surface(surface: &cairo::ImageSurface, channel: Channel) -> Result<Self, Error> {
+        let width = surface.width() as usize;
+        let height = surface.height() as usize;
+        let stride = surface.stride() as usize;
+        let format = surface.format();
+        match format {
+            cairo::Format::A8 | cairo::Format::ARgb32 | cairo::Format::Rgb24 => {}
+            _ => return Err(Error::InvalidFormat),
+        }
+        // an empty surface has no data to borrow, and no values to contour
+        if width == 0 || height == 0 {
+            return Ok(Self {
+                width: 0,
+                height: 0,
+                values: Vec::new(),
+                offset: 0.5,
+            });
+        }
+
+        let value = move |row: &[u8], x: usize| {
+            let pixel = image_surface::pixel(row, x);
+            let alpha = if format == cairo::Format::Rgb24 {
+                255
+            } else {
+                pixel >> 24
+            };
+            let colour = |shift: u32| {
+                if alpha == 0 {
+                    0.0
+                } else {
+                    f64::from((pixel >> shift) & 0xff) / f64::from(alpha)
+                }
+            };
+            match channel {
+                Channel::Alpha => f64::from(alpha) / 255.0,
+                Channel::Red => colour(16),
+                Channel::Green => colour(8),
+                Channel::Blue => colour(0),
+                Channel::Luminance => 0.2126 * colour(16) + 0.7152 * colour(8) + 0.0722 * colour(0),
+            }
+        };
+
+        let mut values = Vec::with_capacity(width * height);
+        let read = surface.with_data(|data| {
+            for y in 0..height {
+                let row = &data[y * stride..];
+                for x in 0..width {
+                    values.push(match format {
+                        cairo::Format::A8 => f64::from(row[x]) / 255.0,
+                        _ => value(row, x),
+                    });
+                }
+            }
+        });
+        match read {
+            Ok(_) => Ok(Self {
+                width: width as i64,
+                height: height as i64,
+                values,
+                offset: 0.5,
+            }),
+            Err(cairo::BorrowError::Cairo(err)) => Err(err.into()),
+            Err(_) => Err(Error::Error),
+        }
+    }
+
+    // the value at column `x` and row `y`, with the grid surrounded by
+    // values below every level
+    fn at(&self, x: i64, y: i64) -> f64 {
+        if x < 0 || x >= self.width || y < 0 || y >= self.height {
+            f64::NEG_INFINITY
+        } else {
+            self.values[(y * self.width + x) as usize]
+        }
+    }
+}
+
+/// Returns the segments of the contour lines at each of `levels`, in the
+/// same order. Lines run clockwise around the values at or above their
+/// level, as seen with the y-axis pointing down, and are closed where they
+/// loop back on themselves rather than running off the grid's edge.
+pub fn isolines(grid: &Grid, levels: &[f64]) -> Vec<Vec<PathSegment>> {
+    levels
+        .iter()
+        .map(|&level| {
+            polylines(grid, level, false)
+                .into_iter()
+                .flat_map(|(points, closed)| segments(&points, closed))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the segments of the outlines of the bands between each level of
+/// `levels` and the next, where the values are at or above the lower of the
+/// two levels and below the higher one.
+pub fn isobands(grid: &Grid, levels: &[f64]) -> Vec<Vec<PathSegment>> {
+    levels
+        .windows(2)
+        .map(|pair| {
+            let (low, high) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+            if low == high {
+                return Vec::new();
+            }
+            let mut band: Vec<PathSegment> = polylines(grid, low, true)
+                .into_iter()
+                .flat_map(|(points, _)| segments(&points, true))
+                .collect();
+            for (mut points, _) in polylines(grid, high, true) {
+                points.reverse();
+                band.extend(segments(&points, true));
+            }
+            band
+        })
+        .collect()
+}
+
+// A line through a cell, from the crossing on one of its edges to the
+// crossing on another.
+struct Line {
+    from: Edge,
+    to: Edge,
+    start: Vertex,
+    end: Vertex,
+}
+
+// The contours at `level` chained into polylines, each with whether it is
+// closed. If `outline` is set, the cells around the grid's edge are
+// included, so that every polyline is closed.
+fn polylines(grid: &Grid, level: f64, outline: bool) -> Vec<(Vec<Vertex>, bool)> {
+    let lines = lines(grid, level, outline);
+    let starting: HashMap<Edge, usize> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (line.from, i))
+        .collect();
+    let ending: HashMap<Edge, usize> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (line.to, i))
+        .collect();
+
+    let mut used = vec![false; lines.len()];
+    let mut polylines = Vec::new();
+    let mut follow = |first: usize, used: &mut Vec<bool>| {
+        let mut points = vec![lines[first].start];
+        let mut next = Some(first);
+        let mut last = first;
+        while let Some(i) = next.filter(|&i| !used[i]) {
+            used[i] = true;
+            points.push(lines[i].end);
+            last = i;
+            next = starting.get(&lines[i].to).copied();
+        }
+        let closed = lines[last].to == lines[first].from;
+        if closed {
+            points.pop();
+        }
+        polylines.push((points, closed));
+    };
+    // open polylines start from lines nothing leads into, and the lines
+    // left over form loops
+    for i in 0..lines.len() {
+        if !used[i] && !ending.contains_key(&lines[i].from) {
+            follow(i, &mut used);
+        }
+    }
+    for i in 0..lines.len() {
+        if !used[i] {
+            follow(i, &mut used);
+        }
+    }
+    polylines
+}
+
+// The lines crossing the cells of the grid at `level`, with the values at
+// or above it on their right.
+fn lines(grid: &Grid, level: f64, outline: bool) -> Vec<Line> {
+    let (first, width, height) = if outline {
+        (-1, grid.width, grid.height)
+    } else {
+        (0, grid.width - 1, grid.height - 1)
+    };
+
+    let mut lines = Vec::new();
+    for y in first..height {
+        for x in first..width {
+            // the corners, and the edges running from each to the next,
+            // clockwise from the top left
+            let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+            let edges = [
+                (x, y, false),
+                (x + 1, y, true),
+                (x, y + 1, false),
+                (x, y, true),
+            ];
+            let values = corners.map(|(x, y)| grid.at(x, y));
+            let above = values.map(|value| value >= level);
+
+            let exits: Vec<usize> = (0..4)
+                .filter(|&k| above[k] && !above[(k + 1) % 4])
+                .collect();
+            let entries: Vec<usize> = (0..4)
+                .filter(|&k| !above[k] && above[(k + 1) % 4])
+                .collect();
+            let pairs: Vec<(usize, usize)> = match (exits.as_slice(), entries.as_slice()) {
+                (&[exit], &[entry]) => vec![(exit, entry)],
+                (&[a, b], _) => {
+                    let centre = values.iter().sum::<f64>() / 4.0;
+                    // the corners above the level are joined through the
+                    // middle of the cell if it is above the level too
+                    let turn = if centre >= level { 1 } else { 3 };
+                    vec![(a, (a + turn) % 4), (b, (b + turn) % 4)]
+                }
+                _ => Vec::new(),
+            };
+            for (exit, entry) in pairs {
+                lines.push(Line {
+                    from: edges[exit],
+                    to: edges[entry],
+                    start: crossing(grid, edges[exit], level),
+                    end: crossing(grid, edges[entry], level),
+                });
+            }
+        }
+    }
+    lines
+}
+
+// The point where the contour at `level` crosses `edge`. Each edge's
+// crossing is found from its ends in the same order, whichever cell it is
+// found for, so that neighbouring cells' lines meet exactly.
+fn crossing(grid: &Grid, (x, y, down): Edge, level: f64) -> Vertex {
+    let (x1, y1) = if down { (x, y + 1) } else { (x + 1, y) };
+    let (v0, v1) = (grid.at(x, y), grid.at(x1, y1));
+    let t = if v0 == f64::NEG_INFINITY {
+        1.0
+    } else if v1 == f64::NEG_INFINITY {
+        0.0
+    } else {
+        (level - v0) / (v1 - v0)
+    };
+    let (dx, dy) = if down { (0.0, t) } else { (t, 0.0) };
+    (x as f64 + dx + grid.offset, y as f64 + dy + grid.offset)
+}
+
+// The segments of a polyline, leaving out repeated points, and polylines
+// with nothing left to draw.
+fn segments(points: &[Vertex], closed: bool) -> Vec<PathSegment> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    if points.len() < if closed { 3 } else { 2 } {
+        return Vec::new();
+    }
+
+    let point = |(x, y): Vertex| Point { x, y };
+    let mut segments = vec![PathSegment::MoveTo(point(points[0]))];
+    segments.extend(points[1..].iter().map(|&p| PathSegment::LineTo(point(p))));
+    if closed {
+        segments.push(PathSegment::ClosePath);
+    }
+    segments
+}
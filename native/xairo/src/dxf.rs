@@ -0,0 +1,547 @@
+// DXF export of paths, for CAD programs and laser cutters.
+//
+// Each subpath becomes a polyline, with its curves flattened, or, when
+// splines are asked for in an R2000 file, each run of lines in it becomes a
+// polyline and each run of curves a spline through the same cubic Bézier
+// curves. R12 has no splines, so its curves are always flattened. DXF's
+// y-axis points up, so y coordinates are negated to keep the drawing the
+// right way up.
+//
+// An R12 file holds just the tables its entities refer to, which is all that
+// version needs. An R2000 file also holds the handles, owners, and the tables,
+// blocks and objects that AutoCAD expects of that version.
+
+use crate::{
+    bezier::Segment,
+    enums::{DxfVersion, Error, PathSegment, PlotUnit},
+    path::Path,
+    rgba::Rgba,
+    subpath::Subpath,
+    svg_path::format_number,
+};
+use std::fmt::Write;
+
+type Vertex = (f64, f64);
+
+// the AutoCAD Color Index colours layer colours are matched to, with 7
+// standing for both black and white, as it is drawn in whichever contrasts
+// with the background
+const PALETTE: [(u8, (f64, f64, f64)); 10] = [
+    (1, (1.0, 0.0, 0.0)),
+    (2, (1.0, 1.0, 0.0)),
+    (3, (0.0, 1.0, 0.0)),
+    (4, (0.0, 1.0, 1.0)),
+    (5, (0.0, 0.0, 1.0)),
+    (6, (1.0, 0.0, 1.0)),
+    (7, (0.0, 0.0, 0.0)),
+    (7, (1.0, 1.0, 1.0)),
+    (8, (0.5, 0.5, 0.5)),
+    (9, (0.75, 0.75, 0.75)),
+];
+
+// the characters DXF readers refuse in layer names, besides control
+// characters, which would end the name's line early
+const RESERVED: &str = "<>/\\\":;?*|=`";
+
+/// A layer of a DXF file, drawn in `color`, or in black if it has none.
+#[derive(rustler::NifMap)]
+pub struct DxfLayer {
+    pub name: String,
+    pub color: Option<Rgba>,
+    pub paths: Vec<Path>,
+}
+
+/// How a DXF file is written.
+///
+/// Coordinates are in `unit`, and are converted to millimetres if it is
+/// `PlotUnit::Pt` or `PlotUnit::Px`, which DXF has no unit for. Curves are
+/// flattened to within `tolerance` unless written as splines.
+#[derive(rustler::NifMap)]
+pub struct DxfOptions {
+    pub version: DxfVersion,
+    pub splines: bool,
+    pub unit: PlotUnit,
+    pub tolerance: f64,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn dxf_encode(layers: Vec<DxfLayer>, options: DxfOptions) -> Result<String, Error> {
+    let layers: Vec<(&str, Option<&Rgba>, Vec<PathSegment>)> = layers
+        .iter()
+        .map(|layer| {
+            let segments = layer
+                .paths
+                .iter()
+                .flat_map(|path| path.segments())
+                .collect();
+            (layer.name.as_str(), layer.color.as_ref(), segments)
+        })
+        .collect();
+    encode(&layers, &options)
+}
+
+// A polyline, and whether it is closed, or a spline, to write.
+enum Entity {
+    Polyline(Vec<Vertex>, bool),
+    // the start of the first curve, followed by the control points and end
+    // of each curve in turn, and whether the last curve ends at the start
+    Spline(Vec<Vertex>, bool),
+}
+
+/// Returns a DXF file drawing each layer's segments, given as a layer name,
+/// a colour, and the segments of the layer's paths.
+///
+/// Returns `Error::InvalidLayerName` if a layer's name is empty, or contains a
+/// control character, a backtick, or any of `<>/\":;?*|=`.
+pub fn encode(
+    layers: &[(&str, Option<&Rgba>, Vec<PathSegment>)],
+    options: &DxfOptions,
+) -> Result<String, Error> {
+    if !layers.iter().all(|(name, _, _)| is_layer_name(name)) {
+        return Err(Error::InvalidLayerName);
+    }
+    let (scale, insunits) = match options.unit {
+        PlotUnit::In => (1.0, 1),
+        PlotUnit::Cm => (1.0, 5),
+        PlotUnit::Mm => (1.0, 4),
+        unit => (unit.millimetres(), 4),
+    };
+    let splines = options.splines && matches!(options.version, DxfVersion::R2000);
+
+    let mut dxf = Dxf::new(options.version);
+    // layer 0 is always defined, as DXF readers expect it
+    let mut names = vec![("0", None)];
+    for (name, color, _) in layers {
+        match names.iter_mut().find(|entry| entry.0 == *name) {
+            Some(existing) => existing.1 = existing.1.or(*color),
+            None => names.push((name, *color)),
+        }
+    }
+    dxf.tables(&names);
+    dxf.blocks();
+
+    dxf.section("ENTITIES");
+    for (name, _, segments) in layers {
+        for subpath in Subpath::from_segments(segments) {
+            for entity in entities(&subpath, splines, options.tolerance) {
+                match entity {
+                    Entity::Polyline(points, closed) => {
+                        dxf.polyline(name, &transformed(&points, scale), closed)
+                    }
+                    Entity::Spline(points, closed) => {
+                        dxf.spline(name, &transformed(&points, scale), closed)
+                    }
+                }
+            }
+        }
+    }
+    dxf.end_section();
+    dxf.objects();
+    dxf.pair(0, "EOF");
+
+    Ok(dxf.header(insunits) + &dxf.out)
+}
+
+fn is_layer_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(|c| c.is_control() || RESERVED.contains(c))
+}
+
+fn transformed(points: &[Vertex], scale: f64) -> Vec<Vertex> {
+    points
+        .iter()
+        .map(|(x, y)| (x * scale, -y * scale))
+        .collect()
+}
+
+// The entities drawing a subpath, with curves written as splines if
+// `splines` is set, and otherwise flattened to within `tolerance`. A closed
+// subpath stays a single closed entity, so it is only written as a spline if
+// it is made of curves alone, and is flattened if it mixes curves and lines.
+fn entities(subpath: &Subpath, splines: bool, tolerance: f64) -> Vec<Entity> {
+    let segments = subpath.closed_segments();
+    let curves = segments
+        .iter()
+        .filter(|segment| matches!(segment, Segment::Curve(_)))
+        .count();
+    if !splines || curves == 0 || (subpath.closed && curves < segments.len()) {
+        let mut points = subpath.flatten(tolerance);
+        if subpath.closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+            points.pop();
+        }
+        return vec![Entity::Polyline(points, subpath.closed)];
+    }
+
+    let mut entities = Vec::new();
+    for segment in segments {
+        match (segment, entities.last_mut()) {
+            (Segment::Line(_, end), Some(Entity::Polyline(points, _))) => points.push(end),
+            (Segment::Line(start, end), _) => {
+                entities.push(Entity::Polyline(vec![start, end], false))
+            }
+            (Segment::Curve(cubic), Some(Entity::Spline(points, _))) => {
+                points.extend([cubic.p1, cubic.p2, cubic.p3])
+            }
+            (Segment::Curve(cubic), _) => entities.push(Entity::Spline(
+                vec![cubic.p0, cubic.p1, cubic.p2, cubic.p3],
+                subpath.closed,
+            )),
+        }
+    }
+    entities
+}
+
+// The ACI colour nearest to a colour.
+fn color_index(color: Option<&Rgba>) -> u8 {
+    let color = match color {
+        Some(color) => (color.red, color.green, color.blue),
+        None => return 7,
+    };
+    let distance = |(r, g, b): (f64, f64, f64)| {
+        (r - color.0).powi(2) + (g - color.1).powi(2) + (b - color.2).powi(2)
+    };
+    PALETTE
+        .iter()
+        .min_by(|(_, a), (_, b)| distance(*a).partial_cmp(&distance(*b)).unwrap())
+        .map_or(7, |(index, _)| *index)
+}
+
+// A colour as a 24-bit RGB value, as DXF gives true colours.
+fn true_color(color: &Rgba) -> u32 {
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u32;
+    channel(color.red) << 16 | channel(color.green) << 8 | channel(color.blue)
+}
+
+// A DXF file being written, as pairs of group codes and values. Only R2000
+// files get handles.
+struct Dxf {
+    out: String,
+    version: DxfVersion,
+    handle: u32,
+    // the handles of the block records of model space, which owns the
+    // entities, and of paper space
+    model_space: String,
+    paper_space: String,
+}
+
+impl Dxf {
+    // R2000 files get the handles of the block records first.
+    fn new(version: DxfVersion) -> Self {
+        let mut dxf = Self {
+            out: String::new(),
+            version,
+            handle: 0,
+            model_space: String::new(),
+            paper_space: String::new(),
+        };
+        if dxf.is_r2000() {
+            dxf.model_space = dxf.next_handle();
+            dxf.paper_space = dxf.next_handle();
+        }
+        dxf
+    }
+
+    fn is_r2000(&self) -> bool {
+        matches!(self.version, DxfVersion::R2000)
+    }
+
+    fn next_handle(&mut self) -> String {
+        self.handle += 1;
+        format!("{:X}", self.handle)
+    }
+
+    fn pair(&mut self, code: u16, value: impl std::fmt::Display) {
+        writeln!(self.out, "{:>3}\n{}", code, value).unwrap();
+    }
+
+    fn number(&mut self, code: u16, value: f64) {
+        self.pair(code, format_number(value, 6));
+    }
+
+    fn point(&mut self, code: u16, (x, y): Vertex) {
+        self.number(code, x);
+        self.number(code + 10, y);
+    }
+
+    fn section(&mut self, name: &str) {
+        self.pair(0, "SECTION");
+        self.pair(2, name);
+    }
+
+    fn end_section(&mut self) {
+        self.pair(0, "ENDSEC");
+    }
+
+    // The header, written last, when the next free handle is known.
+    fn header(&self, insunits: u8) -> String {
+        let mut header = Self::new(self.version);
+        header.section("HEADER");
+        header.pair(9, "$ACADVER");
+        header.pair(1, if self.is_r2000() { "AC1015" } else { "AC1009" });
+        if self.is_r2000() {
+            header.pair(9, "$HANDSEED");
+            header.pair(5, format!("{:X}", self.handle + 1));
+            header.pair(9, "$INSUNITS");
+            header.pair(70, insunits);
+        }
+        header.end_section();
+        if self.is_r2000() {
+            header.section("CLASSES");
+            header.end_section();
+        }
+        header.out
+    }
+
+    // Starts a table of `count` entries, returning its handle.
+    fn table(&mut self, name: &str, count: usize) -> String {
+        let handle = self.next_handle();
+        self.pair(0, "TABLE");
+        self.pair(2, name);
+        if self.is_r2000() {
+            self.pair(5, &handle);
+            self.pair(330, 0);
+            self.pair(100, "AcDbSymbolTable");
+        }
+        self.pair(70, count);
+        handle
+    }
+
+    fn table_entry(&mut self, kind: &str, table: &str, subclass: &str, name: &str) {
+        self.pair(0, kind);
+        if self.is_r2000() {
+            let handle = self.next_handle();
+            self.pair(5, handle);
+            self.pair(330, table);
+            self.pair(100, "AcDbSymbolTableRecord");
+            self.pair(100, subclass);
+        }
+        self.pair(2, name);
+        self.pair(70, 0);
+    }
+
+    fn end_table(&mut self) {
+        self.pair(0, "ENDTAB");
+    }
+
+    fn tables(&mut self, layers: &[(&str, Option<&Rgba>)]) {
+        self.section("TABLES");
+        if self.is_r2000() {
+            self.table("VPORT", 0);
+            self.end_table();
+        }
+
+        let line_types: &[&str] = if self.is_r2000() {
+            &["ByBlock", "ByLayer", "Continuous"]
+        } else {
+            &["CONTINUOUS"]
+        };
+        let table = self.table("LTYPE", line_types.len());
+        for name in line_types {
+            self.table_entry("LTYPE", &table, "AcDbLinetypeTableRecord", name);
+            self.pair(
+                3,
+                if *name == "ByBlock" || *name == "ByLayer" {
+                    ""
+                } else {
+                    "Solid line"
+                },
+            );
+            self.pair(72, 65);
+            self.pair(73, 0);
+            self.number(40, 0.0);
+        }
+        self.end_table();
+
+        let table = self.table("LAYER", layers.len());
+        for (name, color) in layers {
+            self.table_entry("LAYER", &table, "AcDbLayerTableRecord", name);
+            self.pair(62, color_index(*color));
+            self.pair(
+                6,
+                if self.is_r2000() {
+                    "Continuous"
+                } else {
+                    "CONTINUOUS"
+                },
+            );
+            if self.is_r2000() {
+                if let Some(color) = color {
+                    self.pair(420, true_color(color));
+                }
+                self.pair(370, -3);
+            }
+        }
+        self.end_table();
+
+        if self.is_r2000() {
+            let table = self.table("STYLE", 1);
+            self.table_entry("STYLE", &table, "AcDbTextStyleTableRecord", "Standard");
+            self.number(40, 0.0);
+            self.number(41, 1.0);
+            self.number(50, 0.0);
+            self.pair(71, 0);
+            self.number(42, 2.5);
+            self.pair(3, "txt");
+            self.pair(4, "");
+            self.end_table();
+
+            for name in ["VIEW", "UCS"] {
+                self.table(name, 0);
+                self.end_table();
+            }
+
+            let table = self.table("APPID", 1);
+            self.table_entry("APPID", &table, "AcDbRegAppTableRecord", "ACAD");
+            self.end_table();
+
+            self.table("DIMSTYLE", 0);
+            self.pair(100, "AcDbDimStyleTable");
+            self.end_table();
+
+            let table = self.table("BLOCK_RECORD", 2);
+            let spaces = [
+                (self.model_space.clone(), "*Model_Space"),
+                (self.paper_space.clone(), "*Paper_Space"),
+            ];
+            for (handle, name) in spaces {
+                self.pair(0, "BLOCK_RECORD");
+                self.pair(5, handle);
+                self.pair(330, &table);
+                self.pair(100, "AcDbSymbolTableRecord");
+                self.pair(100, "AcDbBlockTableRecord");
+                self.pair(2, name);
+            }
+            self.end_table();
+        }
+        self.end_section();
+    }
+
+    fn blocks(&mut self) {
+        if !self.is_r2000() {
+            return;
+        }
+        self.section("BLOCKS");
+        let spaces = [
+            (self.model_space.clone(), "*Model_Space", false),
+            (self.paper_space.clone(), "*Paper_Space", true),
+        ];
+        for (owner, name, paper) in spaces {
+            let handle = self.next_handle();
+            self.pair(0, "BLOCK");
+            self.pair(5, handle);
+            self.pair(330, &owner);
+            self.pair(100, "AcDbEntity");
+            if paper {
+                self.pair(67, 1);
+            }
+            self.pair(8, "0");
+            self.pair(100, "AcDbBlockBegin");
+            self.pair(2, name);
+            self.pair(70, 0);
+            self.point(10, (0.0, 0.0));
+            self.number(30, 0.0);
+            self.pair(3, name);
+            self.pair(1, "");
+
+            let handle = self.next_handle();
+            self.pair(0, "ENDBLK");
+            self.pair(5, handle);
+            self.pair(330, &owner);
+            self.pair(100, "AcDbEntity");
+            if paper {
+                self.pair(67, 1);
+            }
+            self.pair(8, "0");
+            self.pair(100, "AcDbBlockEnd");
+        }
+        self.end_section();
+    }
+
+    fn entity(&mut self, kind: &str, layer: &str, subclass: &str) {
+        self.pair(0, kind);
+        if self.is_r2000() {
+            let handle = self.next_handle();
+            let owner = self.model_space.clone();
+            self.pair(5, handle);
+            self.pair(330, owner);
+            self.pair(100, "AcDbEntity");
+        }
+        self.pair(8, layer);
+        if self.is_r2000() {
+            self.pair(100, subclass);
+        }
+    }
+
+    fn polyline(&mut self, layer: &str, points: &[Vertex], closed: bool) {
+        if self.is_r2000() {
+            self.entity("LWPOLYLINE", layer, "AcDbPolyline");
+            self.pair(90, points.len());
+            self.pair(70, if closed { 1 } else { 0 });
+            for point in points {
+                self.point(10, *point);
+            }
+        } else {
+            self.entity("POLYLINE", layer, "");
+            self.pair(66, 1);
+            self.point(10, (0.0, 0.0));
+            self.number(30, 0.0);
+            self.pair(70, if closed { 1 } else { 0 });
+            for point in points {
+                self.entity("VERTEX", layer, "");
+                self.point(10, *point);
+                self.number(30, 0.0);
+            }
+            self.entity("SEQEND", layer, "");
+        }
+    }
+
+    // A clamped cubic B-spline following a run of Bézier curves, which has
+    // their points as its control points, and each knot between curves
+    // repeated three times, so that it passes through the curves' ends.
+    fn spline(&mut self, layer: &str, points: &[Vertex], closed: bool) {
+        let curves = (points.len() - 1) / 3;
+        let mut knots = vec![0; 4];
+        for k in 1..curves {
+            knots.extend([k; 3]);
+        }
+        knots.extend([curves; 4]);
+
+        self.entity("SPLINE", layer, "AcDbSpline");
+        self.point(210, (0.0, 0.0));
+        self.number(230, 1.0);
+        // planar, and closed if it ends at its start
+        self.pair(70, if closed { 9 } else { 8 });
+        self.pair(71, 3);
+        self.pair(72, knots.len());
+        self.pair(73, points.len());
+        self.pair(74, 0);
+        for knot in knots {
+            self.pair(40, knot);
+        }
+        for point in points {
+            self.point(10, *point);
+            self.number(30, 0.0);
+        }
+    }
+
+    fn objects(&mut self) {
+        if !self.is_r2000() {
+            return;
+        }
+        self.section("OBJECTS");
+        let root = self.next_handle();
+        let groups = self.next_handle();
+        self.pair(0, "DICTIONARY");
+        self.pair(5, &root);
+        self.pair(330, 0);
+        self.pair(100, "AcDbDictionary");
+        self.pair(281, 1);
+        self.pair(3, "ACAD_GROUP");
+        self.pair(350, &groups);
+        self.pair(0, "DICTIONARY");
+        self.pair(5, &groups);
+        self.pair(330, &root);
+        self.pair(100, "AcDbDictionary");
+        self.pair(281, 1);
+        self.end_section();
+    }
+}
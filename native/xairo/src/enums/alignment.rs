@@ -0,0 +1,35 @@
+#[cfg(feature = "pango")]
+use pangocairo::pango;
+
+#[derive(rustler::NifUnitEnum)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+#[cfg(feature = "pango")]
+impl From<pango::Alignment> for Alignment {
+    fn from(alignment: pango::Alignment) -> Self {
+        match alignment {
+            pango::Alignment::Left => Self::Left,
+            pango::Alignment::Center => Self::Center,
+            pango::Alignment::Right => Self::Right,
+            _ => {
+                println!("unknown alignment: {:?}", alignment);
+                Self::Left
+            }
+        }
+    }
+}
+
+#[cfg(feature = "pango")]
+impl From<Alignment> for pango::Alignment {
+    fn from(alignment: Alignment) -> Self {
+        match alignment {
+            Alignment::Left => Self::Left,
+            Alignment::Center => Self::Center,
+            Alignment::Right => Self::Right,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum BooleanOperation {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+impl BooleanOperation {
+    /// Whether a point is inside the result of the operation, given whether
+    /// it is inside each of its operands.
+    pub const fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
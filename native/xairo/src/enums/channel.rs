@@ -0,0 +1,9 @@
+/// The channel of an image surface's pixels to read values from.
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum Channel {
+    Alpha,
+    Red,
+    Green,
+    Blue,
+    Luminance,
+}
@@ -0,0 +1,6 @@
+/// The versions of the DXF format files can be written in.
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum DxfVersion {
+    R12,
+    R2000,
+}
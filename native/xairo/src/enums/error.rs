@@ -2,10 +2,16 @@
 #[derive(rustler::NifUnitEnum)]
 pub enum Error {
     ClipNotRepresentable,
+    FontNotFound,
+    InvalidDash,
+    InvalidFormat,
     InvalidIndex,
+    InvalidLayerName,
+    InvalidMarkup,
     InvalidMatrix,
     InvalidMeshConstruction,
     InvalidSize,
+    InvalidSvgPath,
     SurfaceFinished,
     SurfaceTypeMismatch,
     WriteError,
@@ -16,6 +22,8 @@ impl From<cairo::Error> for Error {
     fn from(error: cairo::Error) -> Self {
         match error {
             cairo::Error::ClipNotRepresentable => Self::ClipNotRepresentable,
+            cairo::Error::InvalidDash => Self::InvalidDash,
+            cairo::Error::InvalidFormat => Self::InvalidFormat,
             cairo::Error::InvalidIndex => Self::InvalidIndex,
             cairo::Error::InvalidMatrix => Self::InvalidMatrix,
             cairo::Error::InvalidMeshConstruction => Self::InvalidMeshConstruction,
@@ -30,3 +38,15 @@ impl From<cairo::Error> for Error {
         }
     }
 }
+
+impl From<fontconfig::FontconfigError> for Error {
+    fn from(error: fontconfig::FontconfigError) -> Self {
+        match error {
+            fontconfig::FontconfigError::NoMatch => Self::FontNotFound,
+            _ => {
+                println!("Unmapped Error type: {:#?}", error);
+                Self::Error
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(rustler::NifUnitEnum)]
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
 pub enum FillRule {
     Winding,
     EvenOdd,
@@ -25,3 +25,14 @@ impl From<FillRule> for cairo::FillRule {
         }
     }
 }
+
+impl FillRule {
+    /// Whether a point with the given winding number is inside a shape
+    /// filled with this rule.
+    pub const fn is_inside(self, winding: i32) -> bool {
+        match self {
+            Self::Winding => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
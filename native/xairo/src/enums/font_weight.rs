@@ -1,4 +1,4 @@
-#[derive(rustler::NifUnitEnum)]
+#[derive(rustler::NifUnitEnum, Clone, Copy, PartialEq)]
 pub enum FontWeight {
     Normal,
     Bold,
@@ -0,0 +1,8 @@
+/// The kinds of vector hatching an area can be filled with.
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum HatchPattern {
+    Parallel,
+    Cross,
+    Stipple,
+    Concentric,
+}
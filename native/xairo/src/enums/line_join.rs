@@ -1,4 +1,4 @@
-#[derive(rustler::NifUnitEnum)]
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
 pub enum LineJoin {
     Miter,
     Round,
@@ -0,0 +1,11 @@
+use crate::path::Path;
+
+#[derive(rustler::NifTaggedEnum)]
+pub enum Marker {
+    Arrow,
+    OpenArrow,
+    Bar,
+    Circle,
+    Square,
+    Path(Path),
+}
@@ -1,25 +1,45 @@
+pub mod alignment;
 pub mod antialias;
+pub mod boolean_operation;
+pub mod channel;
+pub mod dxf_version;
 pub mod error;
 pub mod fill_rule;
 pub mod font_slant;
 pub mod font_weight;
 pub mod format;
+pub mod hatch_pattern;
 pub mod line_cap;
 pub mod line_join;
+pub mod marker;
 pub mod operator;
 pub mod path_segment;
+pub mod plot_unit;
 pub mod surface_type;
 pub mod svg_unit;
+pub mod width_profile;
+#[cfg(feature = "pango")]
+pub mod wrap_mode;
 
+pub use alignment::Alignment;
 pub use antialias::Antialias;
+pub use boolean_operation::BooleanOperation;
+pub use channel::Channel;
+pub use dxf_version::DxfVersion;
 pub use error::Error;
 pub use fill_rule::FillRule;
 pub use font_slant::FontSlant;
 pub use font_weight::FontWeight;
 pub use format::Format;
+pub use hatch_pattern::HatchPattern;
 pub use line_cap::LineCap;
 pub use line_join::LineJoin;
+pub use marker::Marker;
 pub use operator::Operator;
 pub use path_segment::PathSegment;
+pub use plot_unit::PlotUnit;
 pub use surface_type::SurfaceType;
 pub use svg_unit::SvgUnit;
+pub use width_profile::WidthProfile;
+#[cfg(feature = "pango")]
+pub use wrap_mode::WrapMode;
@@ -22,3 +22,17 @@ impl From<cairo::PathSegment> for PathSegment {
         }
     }
 }
+
+impl PathSegment {
+    /// Adds the segment to the current path of `context`.
+    pub fn append_to(&self, context: &cairo::Context) {
+        match self {
+            Self::MoveTo(point) => context.move_to(point.x, point.y),
+            Self::LineTo(point) => context.line_to(point.x, point.y),
+            Self::CurveTo(point1, point2, point3) => {
+                context.curve_to(point1.x, point1.y, point2.x, point2.y, point3.x, point3.y)
+            }
+            Self::ClosePath => context.close_path(),
+        }
+    }
+}
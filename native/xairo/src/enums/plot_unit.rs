@@ -0,0 +1,23 @@
+/// The units of length a plotter's coordinates can be given in.
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum PlotUnit {
+    Mm,
+    Cm,
+    In,
+    Pt,
+    Px,
+}
+
+impl PlotUnit {
+    /// The length of one of this unit in millimetres, with points at 72 and
+    /// pixels at 96 to the inch, as in CSS.
+    pub fn millimetres(self) -> f64 {
+        match self {
+            Self::Mm => 1.0,
+            Self::Cm => 10.0,
+            Self::In => 25.4,
+            Self::Pt => 25.4 / 72.0,
+            Self::Px => 25.4 / 96.0,
+        }
+    }
+}
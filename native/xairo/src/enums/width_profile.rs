@@ -0,0 +1,5 @@
+#[derive(rustler::NifTaggedEnum)]
+pub enum WidthProfile {
+    Stops(Vec<(f64, f64)>),
+    Points(Vec<f64>),
+}
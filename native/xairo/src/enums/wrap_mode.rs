@@ -0,0 +1,32 @@
+use pangocairo::pango;
+
+#[derive(rustler::NifUnitEnum)]
+pub enum WrapMode {
+    Word,
+    Char,
+    WordChar,
+}
+
+impl From<pango::WrapMode> for WrapMode {
+    fn from(wrap_mode: pango::WrapMode) -> Self {
+        match wrap_mode {
+            pango::WrapMode::Word => Self::Word,
+            pango::WrapMode::Char => Self::Char,
+            pango::WrapMode::WordChar => Self::WordChar,
+            _ => {
+                println!("unknown wrap mode: {:?}", wrap_mode);
+                Self::Word
+            }
+        }
+    }
+}
+
+impl From<WrapMode> for pango::WrapMode {
+    fn from(wrap_mode: WrapMode) -> Self {
+        match wrap_mode {
+            WrapMode::Word => Self::Word,
+            WrapMode::Char => Self::Char,
+            WrapMode::WordChar => Self::WordChar,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::enums::{Error, FontSlant, FontWeight};
+use fontconfig::{Fontconfig, ObjectSet, Pattern};
 use rustler::ResourceArc;
+use std::{collections::BTreeSet, ffi::CString};
 
 pub struct Raw {
     pub font_face: cairo::FontFace,
@@ -36,3 +38,42 @@ fn font_face_toy_get_slant(font_face: FontFace) -> FontSlant {
 fn font_face_toy_get_weight(font_face: FontFace) -> FontWeight {
     font_face.font_face.toy_get_weight().into()
 }
+
+// Returns the sorted, unique values of `object` across all installed fonts,
+// optionally restricted to a single font family.
+fn list_installed(
+    family: Option<&str>,
+    object: &std::ffi::CStr,
+) -> Result<Vec<String>, fontconfig::FontconfigError> {
+    let fc = Fontconfig::new().ok_or(fontconfig::FontconfigError::Failed)?;
+    let mut pattern = Pattern::new(&fc)?;
+    if let Some(family) = family {
+        pattern.add_string(fontconfig::FC_FAMILY, &CString::new(family)?)?;
+    }
+    let mut objects = ObjectSet::new(&fc)?;
+    objects.add(object)?;
+
+    let fonts = fontconfig::list_fonts(&pattern, Some(&objects))?;
+    let values = fonts
+        .iter()
+        .filter_map(|font| font.get_string(object).ok().map(String::from))
+        .collect::<BTreeSet<String>>();
+
+    Ok(values.into_iter().collect())
+}
+
+#[rustler::nif]
+fn font_face_families() -> Result<Vec<String>, Error> {
+    match list_installed(None, fontconfig::FC_FAMILY) {
+        Ok(families) => Ok(families),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn font_face_styles(family: String) -> Result<Vec<String>, Error> {
+    match list_installed(Some(&family), fontconfig::FC_STYLE) {
+        Ok(styles) => Ok(styles),
+        Err(err) => Err(err.into()),
+    }
+}
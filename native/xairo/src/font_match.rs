@@ -0,0 +1,95 @@
+use crate::enums::{Error, FontSlant, FontWeight};
+use fontconfig::{Fontconfig, Pattern};
+use std::ffi::CString;
+
+#[derive(rustler::NifStruct)]
+#[module = "Xairo.FontMatch"]
+pub struct FontMatch {
+    pub family: String,
+    pub style: String,
+    pub slant: FontSlant,
+    pub weight: FontWeight,
+    pub file: String,
+    pub index: i32,
+}
+
+// Mirrors the fontconfig values cairo requests when it resolves a toy font face.
+const fn slant_to_fc(slant: FontSlant) -> i32 {
+    match slant {
+        FontSlant::Normal => fontconfig::FC_SLANT_ROMAN,
+        FontSlant::Italic => fontconfig::FC_SLANT_ITALIC,
+        FontSlant::Oblique => fontconfig::FC_SLANT_OBLIQUE,
+    }
+}
+
+const fn weight_to_fc(weight: FontWeight) -> i32 {
+    match weight {
+        FontWeight::Normal => fontconfig::FC_WEIGHT_MEDIUM,
+        FontWeight::Bold => fontconfig::FC_WEIGHT_BOLD,
+    }
+}
+
+const fn slant_from_fc(slant: i32) -> FontSlant {
+    if slant >= fontconfig::FC_SLANT_OBLIQUE {
+        FontSlant::Oblique
+    } else if slant >= fontconfig::FC_SLANT_ITALIC {
+        FontSlant::Italic
+    } else {
+        FontSlant::Normal
+    }
+}
+
+const fn weight_from_fc(weight: i32) -> FontWeight {
+    if weight >= fontconfig::FC_WEIGHT_DEMIBOLD {
+        FontWeight::Bold
+    } else {
+        FontWeight::Normal
+    }
+}
+
+pub(crate) fn font_match(
+    family: &str,
+    slant: FontSlant,
+    weight: FontWeight,
+) -> Result<FontMatch, fontconfig::FontconfigError> {
+    let fc = Fontconfig::new().ok_or(fontconfig::FontconfigError::Failed)?;
+    let mut pattern = Pattern::new(&fc)?;
+    pattern.add_string(fontconfig::FC_FAMILY, &CString::new(family)?)?;
+    pattern.add_integer(fontconfig::FC_SLANT, slant_to_fc(slant))?;
+    pattern.add_integer(fontconfig::FC_WEIGHT, weight_to_fc(weight))?;
+
+    let matched = pattern.font_match()?;
+    Ok(FontMatch {
+        family: matched.get_string(fontconfig::FC_FAMILY)?.to_string(),
+        style: matched.get_string(fontconfig::FC_STYLE)?.to_string(),
+        slant: slant_from_fc(matched.slant()?),
+        weight: weight_from_fc(matched.weight()?),
+        file: matched.filename()?.to_string(),
+        index: matched.face_index().unwrap_or(0),
+    })
+}
+
+#[rustler::nif]
+fn font_match_font_match(
+    family: String,
+    slant: FontSlant,
+    weight: FontWeight,
+    strict: bool,
+) -> Result<FontMatch, Error> {
+    match font_match(&family, slant, weight) {
+        Ok(font_match) => {
+            // fonts commonly ship only one of an italic or oblique face, and
+            // either one satisfies a request for a slanted font
+            let exact = font_match.family.eq_ignore_ascii_case(&family)
+                && (font_match.slant == FontSlant::Normal) == (slant == FontSlant::Normal)
+                && font_match.weight == weight;
+
+            if strict && !exact {
+                Err(Error::FontNotFound)
+            } else {
+                Ok(font_match)
+            }
+        }
+        Err(err) => Err(err.into()),
+    }
+}
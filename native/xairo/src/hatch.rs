@@ -0,0 +1,234 @@
+// Hatching of the area a path fills with evenly spaced lines, dots or
+// contours, as vector geometry.
+//
+// For lines and dots, the area's polygons are turned so that the lines run
+// horizontally, and each line is cut where it crosses the polygons' edges,
+// keeping the spans between crossings whose winding number puts them inside
+// the area under the fill rule. Dots are placed along the spans, and the
+// spans or dots are then turned back. Contours are the outlines of the area
+// inset by whole multiples of the spacing.
+
+use crate::{
+    enums::{Error, FillRule, HatchPattern, LineJoin, PathSegment},
+    offset,
+    point::Point,
+    subpath::Subpath,
+};
+use std::f64::consts::FRAC_PI_2;
+
+type Vertex = (f64, f64);
+
+// the most lines, and the most dots, an area is hatched with, which keeps a
+// tiny gap from producing an unbounded number of them
+const MAX_LINES: f64 = 65536.0;
+const MAX_DOTS: f64 = 262_144.0;
+
+// how far, in gaps, a line can be from the edge of the area and still count
+// as lying along it, allowing for the error in turning the polygons
+const ON_EDGE: f64 = 1e-9;
+
+// the most contours drawn inside an area, since each is a separate offset of
+// the whole path
+const MAX_CONTOURS: usize = 1000;
+
+/// Returns the segments of `pattern` filling the area `path` fills under
+/// `fill_rule`, with its lines, dots or contours `spacing` apart, and curves
+/// flattened to within `tolerance`.
+///
+/// Lines, and the rows of dots, run at `angle` radians from the x-axis, and a
+/// cross-hatch adds lines at right angles to those. Each dot is a line of no
+/// length, which draws a dot when stroked with round caps. Contours follow
+/// the outline of the area at every multiple of `spacing` inside it, up to
+/// 1000 of them, rounded where the outline turns inwards, and ignore `angle`.
+///
+/// Returns `Error::InvalidSize` if `spacing` is not positive, or if it would
+/// take more than 65536 lines, or 262144 dots, to hatch the area.
+pub fn fill(
+    path: &cairo::Path,
+    pattern: HatchPattern,
+    angle: f64,
+    spacing: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    let polygons: Vec<Vec<Vertex>> = Subpath::from_path(path)
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect();
+
+    let lines = match pattern {
+        HatchPattern::Parallel => lines(&polygons, fill_rule, angle, spacing)?,
+        HatchPattern::Cross => {
+            let mut both = lines(&polygons, fill_rule, angle, spacing)?;
+            both.extend(lines(&polygons, fill_rule, angle + FRAC_PI_2, spacing)?);
+            both
+        }
+        HatchPattern::Stipple => dots(&polygons, fill_rule, angle, spacing)?
+            .into_iter()
+            .map(|dot| (dot, dot))
+            .collect(),
+        HatchPattern::Concentric => return contours(path, spacing, fill_rule, tolerance),
+    };
+    Ok(lines
+        .into_iter()
+        .flat_map(|(start, end)| {
+            [
+                PathSegment::MoveTo(Point::from(start)),
+                PathSegment::LineTo(Point::from(end)),
+            ]
+        })
+        .collect())
+}
+
+/// Returns the lines, as pairs of end points, hatching the area `polygons`
+/// fill under `fill_rule` at `angle` radians from the x-axis, `gap` apart.
+///
+/// The lines fall at whole multiples of `gap` from the origin, measured
+/// across them, so that neighbouring areas hatched alike line up. Returns
+/// `Error::InvalidSize` if `gap` is not positive, or if more than 65536 lines
+/// would cross the area.
+pub fn lines(
+    polygons: &[Vec<Vertex>],
+    fill_rule: FillRule,
+    angle: f64,
+    gap: f64,
+) -> Result<Vec<(Vertex, Vertex)>, Error> {
+    Ok(rows(polygons, fill_rule, angle, gap)?
+        .into_iter()
+        .map(|(k, start, end)| {
+            let y = k as f64 * gap;
+            (turn(angle, (start, y)), turn(angle, (end, y)))
+        })
+        .collect())
+}
+
+// Dots `gap` apart along the lines `lines` would hatch the area with, with
+// every other line's dots moved along by half the gap, so that they stagger.
+fn dots(
+    polygons: &[Vec<Vertex>],
+    fill_rule: FillRule,
+    angle: f64,
+    gap: f64,
+) -> Result<Vec<Vertex>, Error> {
+    let rows = rows(polygons, fill_rule, angle, gap)?;
+    let count: f64 = rows
+        .iter()
+        .map(|(_, start, end)| (end - start) / gap + 1.0)
+        .sum();
+    if count > MAX_DOTS {
+        return Err(Error::InvalidSize);
+    }
+
+    let mut dots = Vec::new();
+    for (k, start, end) in rows {
+        let shift = if k % 2 == 0 { 0.0 } else { gap / 2.0 };
+        let first = ((start - shift) / gap).floor() as i64 + 1;
+        let last = ((end - shift) / gap).ceil() as i64;
+        for j in first..last {
+            dots.push(turn(angle, (j as f64 * gap + shift, k as f64 * gap)));
+        }
+    }
+    Ok(dots)
+}
+
+// The outlines of the area inset by each multiple of `gap` in turn, until
+// nothing of it is left or `MAX_CONTOURS` have been drawn.
+fn contours(
+    path: &cairo::Path,
+    gap: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    if gap.is_nan() || gap <= 0.0 {
+        return Err(Error::InvalidSize);
+    }
+    let mut segments = Vec::new();
+    for k in 1..=MAX_CONTOURS {
+        let distance = -(k as f64) * gap;
+        let inset = offset::offset(path, distance, LineJoin::Round, 10.0, fill_rule, tolerance)?;
+        if inset.is_empty() {
+            break;
+        }
+        segments.extend(inset);
+    }
+    Ok(segments)
+}
+
+fn turn(angle: f64, (x, y): Vertex) -> Vertex {
+    let (sin, cos) = angle.sin_cos();
+    (x * cos - y * sin, x * sin + y * cos)
+}
+
+// The spans inside the area of the lines at `angle` and whole multiples of
+// `gap` from the origin, each given by the line's multiple and the ends of
+// the span measured along it, with the lines turned to run along the x-axis.
+fn rows(
+    polygons: &[Vec<Vertex>],
+    fill_rule: FillRule,
+    angle: f64,
+    gap: f64,
+) -> Result<Vec<(i64, f64, f64)>, Error> {
+    if gap.is_nan() || gap <= 0.0 {
+        return Err(Error::InvalidSize);
+    }
+    let turned: Vec<Vec<Vertex>> = polygons
+        .iter()
+        .map(|polygon| polygon.iter().map(|point| turn(-angle, *point)).collect())
+        .collect();
+
+    let (min, max) = turned
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, y)| {
+            (min.min(y), max.max(y))
+        });
+    if min > max {
+        return Ok(Vec::new());
+    }
+    if (max - min) / gap > MAX_LINES {
+        return Err(Error::InvalidSize);
+    }
+
+    let mut rows = Vec::new();
+    // a line touching only the furthest edge of the area across the lines
+    // would be drawn along its outline, so those are left out
+    let first = (min / gap + ON_EDGE).floor() as i64 + 1;
+    let last = (max / gap - ON_EDGE).ceil() as i64;
+    for k in first..last {
+        for (start, end) in spans(&turned, fill_rule, k as f64 * gap) {
+            rows.push((k, start, end));
+        }
+    }
+    Ok(rows)
+}
+
+// The spans of the horizontal line at `y` inside the area the polygons fill.
+// An edge crosses the line if one of its ends is on or above it and the other
+// below it, so that a line through a vertex is counted crossing only once.
+fn spans(polygons: &[Vec<Vertex>], fill_rule: FillRule, y: f64) -> Vec<(f64, f64)> {
+    let mut crossings = Vec::new();
+    for polygon in polygons {
+        let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+        for (&(x0, y0), &(x1, y1)) in edges {
+            if (y0 <= y) != (y1 <= y) {
+                let x = x0 + (y - y0) * (x1 - x0) / (y1 - y0);
+                crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+            }
+        }
+    }
+    crossings.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let mut spans = Vec::new();
+    let mut winding = 0;
+    let mut start = 0.0;
+    for (x, direction) in crossings {
+        let was_inside = fill_rule.is_inside(winding);
+        winding += direction;
+        match (was_inside, fill_rule.is_inside(winding)) {
+            (false, true) => start = x,
+            (true, false) if x > start => spans.push((start, x)),
+            _ => {}
+        }
+    }
+    spans
+}
@@ -0,0 +1,108 @@
+// Hit testing and bounds of paths, without drawing them on a context.
+//
+// A point is in a path's fill if the path winds around it, counted over the
+// polygons the path's subpaths are flattened to, and in its stroke if it lies
+// in one of the convex pieces the stroke is built from.
+
+use crate::{
+    bezier,
+    enums::{Error, FillRule},
+    stroke::{self, StrokeStyle},
+    subpath::Subpath,
+};
+
+type Vertex = (f64, f64);
+
+// how close to a path's outline a point counts as lying on it, which, as in
+// cairo, puts it inside
+const ON_EDGE: f64 = 1e-9;
+
+fn cross((x0, y0): Vertex, (x1, y1): Vertex, (x2, y2): Vertex) -> f64 {
+    (x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0)
+}
+
+/// Whether `point` lies within the area `cairo::Context::fill` would paint
+/// for `path` under `fill_rule`, with curves flattened to within `tolerance`.
+pub fn in_fill(path: &cairo::Path, point: Vertex, fill_rule: FillRule, tolerance: f64) -> bool {
+    let mut winding = 0;
+    for subpath in Subpath::from_path(path) {
+        let polygon = subpath.flatten(tolerance);
+        let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+        for (start, end) in edges {
+            if bezier::distance_to_segment(point, *start, *end) <= ON_EDGE {
+                return true;
+            }
+            if start.1 <= point.1 {
+                if end.1 > point.1 && cross(*start, *end, point) > 0.0 {
+                    winding += 1;
+                }
+            } else if end.1 <= point.1 && cross(*start, *end, point) < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    fill_rule.is_inside(winding)
+}
+
+/// Whether `point` lies within the area `cairo::Context::stroke` would paint
+/// for `path` in `style`, with curves, round joins and round caps flattened
+/// to within `tolerance`.
+///
+/// Returns `Error::InvalidDash` for an invalid dash pattern, as
+/// `stroke::outline` does.
+pub fn in_stroke(
+    path: &cairo::Path,
+    point: Vertex,
+    style: &StrokeStyle,
+    tolerance: f64,
+) -> Result<bool, Error> {
+    let pieces = stroke::pieces(&Subpath::from_path(path), style, tolerance)?;
+    Ok(pieces.iter().any(|piece| {
+        // the pieces are convex and counter-clockwise, so the point is inside
+        // one if it is on the left of all of its edges
+        piece
+            .iter()
+            .zip(piece.iter().cycle().skip(1))
+            .all(|(start, end)| cross(*start, *end, point) >= -ON_EDGE)
+    }))
+}
+
+/// The smallest rectangle containing every subpath of `path`, including the
+/// furthest points of its curves, as its top-left and bottom-right corners.
+///
+/// Returns `None` for a path with nothing to draw.
+pub fn extents(path: &cairo::Path) -> Option<(Vertex, Vertex)> {
+    Subpath::from_path(path)
+        .iter()
+        .flat_map(|subpath| {
+            let start = (subpath.start, subpath.start);
+            std::iter::once(start).chain(subpath.segments.iter().map(bezier::Segment::bounds))
+        })
+        .reduce(union)
+}
+
+/// The smallest rectangle containing the area `cairo::Context::stroke` would
+/// paint for `path` in `style`, to within `tolerance`, as its top-left and
+/// bottom-right corners.
+///
+/// Returns `None` if the stroke paints nothing, or `Error::InvalidDash` for an
+/// invalid dash pattern.
+pub fn stroke_extents(
+    path: &cairo::Path,
+    style: &StrokeStyle,
+    tolerance: f64,
+) -> Result<Option<(Vertex, Vertex)>, Error> {
+    let pieces = stroke::pieces(&Subpath::from_path(path), style, tolerance)?;
+    Ok(pieces
+        .iter()
+        .flatten()
+        .map(|point| (*point, *point))
+        .reduce(union))
+}
+
+fn union(
+    ((x0, y0), (x1, y1)): (Vertex, Vertex),
+    ((x2, y2), (x3, y3)): (Vertex, Vertex),
+) -> (Vertex, Vertex) {
+    ((x0.min(x2), y0.min(y2)), (x1.max(x3), y1.max(y3)))
+}
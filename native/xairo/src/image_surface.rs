@@ -1,4 +1,8 @@
-use crate::enums::{Error, Format};
+use crate::{
+    enums::{Error, Format},
+    path,
+    trace::{self, TraceOptions},
+};
 use rustler::ResourceArc;
 use std::fs::File;
 
@@ -11,6 +15,14 @@ unsafe impl Sync for Raw {}
 
 pub type ImageSurface = ResourceArc<Raw>;
 
+/// Returns the pixel at `x` in a row of `Argb32` or `Rgb24` image data, as
+/// a native-endian 32-bit value with alpha, or padding, in the top byte.
+pub fn pixel(row: &[u8], x: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&row[4 * x..4 * x + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
 #[rustler::nif]
 fn image_surface_create(format: Format, width: i32, height: i32) -> Result<ImageSurface, Error> {
     match cairo::ImageSurface::create(format.into(), width, height) {
@@ -49,3 +61,14 @@ fn image_surface_stride(surface: ImageSurface) -> i32 {
 fn image_surface_format(surface: ImageSurface) -> Format {
     surface.surface.format().into()
 }
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn image_surface_trace(
+    surface: ImageSurface,
+    options: TraceOptions,
+) -> Result<Vec<path::Path>, Error> {
+    trace::trace(&surface.surface, &options)?
+        .iter()
+        .map(|segments| path::from_segments(segments))
+        .collect()
+}
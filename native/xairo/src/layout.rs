@@ -0,0 +1,97 @@
+use crate::{
+    context::Context,
+    enums::{Alignment, Error, WrapMode},
+    point::Point,
+};
+use pangocairo::pango;
+use rustler::ResourceArc;
+
+pub struct Raw {
+    pub layout: pango::Layout,
+}
+
+unsafe impl Send for Raw {}
+unsafe impl Sync for Raw {}
+
+pub type Layout = ResourceArc<Raw>;
+
+type Rectangle = (Point, f64, f64);
+
+fn to_pango_units(value: f64) -> i32 {
+    (value * f64::from(pango::SCALE)).round() as i32
+}
+
+fn rectangle_from_pango(rect: pango::Rectangle) -> Rectangle {
+    let scale = f64::from(pango::SCALE);
+    (
+        Point {
+            x: f64::from(rect.x) / scale,
+            y: f64::from(rect.y) / scale,
+        },
+        f64::from(rect.width) / scale,
+        f64::from(rect.height) / scale,
+    )
+}
+
+#[rustler::nif]
+fn layout_new(context: Context) -> Result<Layout, Error> {
+    match pangocairo::create_layout(&context.context) {
+        Some(layout) => Ok(ResourceArc::new(Raw { layout })),
+        None => Err(Error::Error),
+    }
+}
+
+#[rustler::nif]
+fn layout_set_text(layout: Layout, text: String) {
+    layout.layout.set_text(&text);
+}
+
+#[rustler::nif]
+fn layout_set_markup(layout: Layout, markup: String) -> Result<(), Error> {
+    match pango::parse_markup(&markup, '\0') {
+        Ok(_) => {
+            layout.layout.set_markup(&markup);
+            Ok(())
+        }
+        Err(_) => Err(Error::InvalidMarkup),
+    }
+}
+
+#[rustler::nif]
+fn layout_set_width(layout: Layout, width: Option<f64>) {
+    layout.layout.set_width(width.map_or(-1, to_pango_units));
+}
+
+#[rustler::nif]
+fn layout_set_wrap(layout: Layout, wrap_mode: WrapMode) {
+    layout.layout.set_wrap(wrap_mode.into());
+}
+
+#[rustler::nif]
+fn layout_set_alignment(layout: Layout, alignment: Alignment) {
+    layout.layout.set_alignment(alignment.into());
+}
+
+#[rustler::nif]
+fn layout_set_font_description(layout: Layout, description: String) {
+    let description = pango::FontDescription::from_string(&description);
+    layout.layout.set_font_description(Some(&description));
+}
+
+#[rustler::nif]
+fn layout_extents(layout: Layout) -> (Rectangle, Rectangle) {
+    let (ink, logical) = layout.layout.extents();
+    (rectangle_from_pango(ink), rectangle_from_pango(logical))
+}
+
+#[rustler::nif]
+fn layout_show(layout: Layout, context: Context) {
+    pangocairo::update_layout(&context.context, &layout.layout);
+    pangocairo::show_layout(&context.context, &layout.layout);
+}
+
+#[rustler::nif]
+fn layout_path(layout: Layout, context: Context) {
+    pangocairo::update_layout(&context.context, &layout.layout);
+    pangocairo::layout_path(&context.context, &layout.layout);
+}
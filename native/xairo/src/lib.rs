@@ -1,198 +1,312 @@
 use rustler::{Env, Term};
 
+mod bezier;
+mod boolean;
+mod brush;
 mod context;
+mod contour;
+mod dxf;
 mod enums;
 mod font_extents;
 mod font_face;
+mod font_match;
+mod hatch;
+mod hit_test;
 mod image_surface;
+#[cfg(feature = "pango")]
+mod layout;
 mod linear_gradient;
+mod markers;
 mod matrix;
+mod measure;
 mod mesh;
+mod morph;
+mod offset;
 mod path;
 mod pdf_surface;
+mod plotter;
 mod point;
 mod ps_surface;
 mod radial_gradient;
 mod rgba;
+mod shapes;
+mod simplify;
+mod sketch;
 mod solid_pattern;
+mod stroke;
+mod subpath;
 mod surface_pattern;
+mod svg_path;
 mod svg_surface;
 mod text_extents;
+mod text_spacing;
+mod trace;
 mod vector;
 
-rustler::init!(
-    "Elixir.Xairo.Native",
-    [
-        // image surface
-        image_surface::image_surface_create,
-        image_surface::image_surface_write_to_png,
-        image_surface::image_surface_width,
-        image_surface::image_surface_height,
-        image_surface::image_surface_stride,
-        image_surface::image_surface_format,
-        // pdf surface
-        pdf_surface::pdf_surface_new,
-        pdf_surface::pdf_surface_finish,
-        // ps surface
-        ps_surface::ps_surface_new,
-        ps_surface::ps_surface_finish,
-        // svg surface
-        svg_surface::svg_surface_new,
-        svg_surface::svg_surface_finish,
-        svg_surface::svg_surface_document_unit,
-        svg_surface::svg_surface_set_document_unit,
-        // context
-        context::context_new,
-        context::context_new_from_pdf_surface,
-        context::context_new_from_ps_surface,
-        context::context_new_from_svg_surface,
-        context::context_set_source_rgba,
-        context::context_set_source_linear_gradient,
-        context::context_set_source_radial_gradient,
-        context::context_set_source_solid_pattern,
-        context::context_set_source_surface_pattern,
-        context::context_set_source_mesh,
-        context::context_set_source_surface,
-        context::context_arc,
-        context::context_arc_negative,
-        context::context_curve_to,
-        context::context_rel_curve_to,
-        context::context_line_to,
-        context::context_rel_line_to,
-        context::context_rectangle,
-        context::context_move_to,
-        context::context_rel_move_to,
-        context::context_close_path,
-        context::context_stroke,
-        context::context_stroke_preserve,
-        context::context_fill,
-        context::context_fill_preserve,
-        context::context_paint,
-        context::context_paint_with_alpha,
-        context::context_copy_path,
-        context::context_copy_path_flat,
-        context::context_append_path,
-        context::context_tolerance,
-        context::context_set_tolerance,
-        context::context_has_current_point,
-        context::context_current_point,
-        context::context_new_path,
-        context::context_new_sub_path,
-        context::context_show_text,
-        context::context_text_path,
-        context::context_set_font_size,
-        context::context_set_font_face,
-        context::context_select_font_face,
-        context::context_translate,
-        context::context_scale,
-        context::context_rotate,
-        context::context_transform,
-        context::context_set_matrix,
-        context::context_identity_matrix,
-        context::context_matrix,
-        context::context_set_font_matrix,
-        context::context_font_matrix,
-        context::context_mask_radial_gradient,
-        context::context_mask_linear_gradient,
-        context::context_mask_mesh,
-        context::context_mask_solid_pattern,
-        context::context_mask_surface_pattern,
-        context::context_mask_surface,
-        // context settings
-        context::context_set_line_width,
-        context::context_line_width,
-        context::context_set_antialias,
-        context::context_antialias,
-        context::context_set_fill_rule,
-        context::context_fill_rule,
-        context::context_set_line_cap,
-        context::context_line_cap,
-        context::context_set_line_join,
-        context::context_line_join,
-        context::context_set_miter_limit,
-        context::context_miter_limit,
-        context::context_set_dash,
-        context::context_dash_count,
-        context::context_dash,
-        context::context_dash_dashes,
-        context::context_dash_offset,
-        context::context_set_operator,
-        context::context_operator,
-        context::context_in_stroke,
-        context::context_in_fill,
-        context::context_user_to_device,
-        context::context_user_to_device_distance,
-        context::context_device_to_user,
-        context::context_device_to_user_distance,
-        // clip
-        context::context_clip,
-        context::context_clip_preserve,
-        context::context_reset_clip,
-        context::context_in_clip,
-        context::context_clip_extents,
-        context::context_clip_rectangle_list,
-        // extents
-        context::context_path_extents,
-        context::context_fill_extents,
-        context::context_stroke_extents,
-        // CONTEXT END
-        // path
-        path::path_iter,
-        // linear gradient
-        linear_gradient::linear_gradient_new,
-        linear_gradient::linear_gradient_linear_points,
-        linear_gradient::linear_gradient_color_stop_count,
-        linear_gradient::linear_gradient_add_color_stop,
-        linear_gradient::linear_gradient_color_stop_rgba,
-        // radial gradient
-        radial_gradient::radial_gradient_new,
-        radial_gradient::radial_gradient_radial_circles,
-        radial_gradient::radial_gradient_color_stop_count,
-        radial_gradient::radial_gradient_add_color_stop,
-        radial_gradient::radial_gradient_color_stop_rgba,
-        // solid pattern
-        solid_pattern::solid_pattern_from_rgba,
-        solid_pattern::solid_pattern_rgba,
-        // surface pattern
-        surface_pattern::surface_pattern_create_from_image_surface,
-        surface_pattern::surface_pattern_create_from_pdf_surface,
-        surface_pattern::surface_pattern_create_from_ps_surface,
-        surface_pattern::surface_pattern_create_from_svg_surface,
-        // mesh
-        mesh::mesh_new,
-        mesh::mesh_patch_count,
-        mesh::mesh_begin_patch,
-        mesh::mesh_end_patch,
-        mesh::mesh_move_to,
-        mesh::mesh_line_to,
-        mesh::mesh_curve_to,
-        mesh::mesh_set_control_point,
-        mesh::mesh_control_point,
-        mesh::mesh_set_corner_color,
-        mesh::mesh_corner_color_rgba,
-        mesh::mesh_path,
-        // font face
-        font_face::font_face_toy_create,
-        font_face::font_face_toy_get_family,
-        font_face::font_face_toy_get_slant,
-        font_face::font_face_toy_get_weight,
-        // matrix
-        matrix::matrix_new,
-        matrix::matrix_identity,
-        matrix::matrix_to_tuple,
-        matrix::matrix_transform_distance,
-        matrix::matrix_transform_point,
-        matrix::matrix_translate,
-        matrix::matrix_scale,
-        matrix::matrix_rotate,
-        matrix::matrix_invert,
-        matrix::matrix_multiply,
-        // font extents
-        font_extents::font_extents_font_extents,
-        // text extents
-        text_extents::text_extents_text_extents,
-    ],
-    load = on_load
+// NIFs that are only compiled in when an optional cargo feature is enabled
+// are passed in as arguments, since `rustler::init!` cannot handle `#[cfg]`
+// attributes on individual entries.
+macro_rules! init {
+    ($($feature_nifs:tt)*) => {
+        rustler::init!(
+            "Elixir.Xairo.Native",
+            [
+                // image surface
+                image_surface::image_surface_create,
+                image_surface::image_surface_write_to_png,
+                image_surface::image_surface_width,
+                image_surface::image_surface_height,
+                image_surface::image_surface_stride,
+                image_surface::image_surface_format,
+                image_surface::image_surface_trace,
+                // pdf surface
+                pdf_surface::pdf_surface_new,
+                pdf_surface::pdf_surface_finish,
+                // ps surface
+                ps_surface::ps_surface_new,
+                ps_surface::ps_surface_finish,
+                // svg surface
+                svg_surface::svg_surface_new,
+                svg_surface::svg_surface_finish,
+                svg_surface::svg_surface_document_unit,
+                svg_surface::svg_surface_set_document_unit,
+                // context
+                context::context_new,
+                context::context_new_from_pdf_surface,
+                context::context_new_from_ps_surface,
+                context::context_new_from_svg_surface,
+                context::context_set_source_rgba,
+                context::context_set_source_linear_gradient,
+                context::context_set_source_radial_gradient,
+                context::context_set_source_solid_pattern,
+                context::context_set_source_surface_pattern,
+                context::context_set_source_mesh,
+                context::context_set_source_surface,
+                context::context_arc,
+                context::context_arc_negative,
+                context::context_curve_to,
+                context::context_rel_curve_to,
+                context::context_quad_to,
+                context::context_rel_quad_to,
+                context::context_arc_to,
+                context::context_line_to,
+                context::context_rel_line_to,
+                context::context_rectangle,
+                context::context_ellipse,
+                context::context_rounded_rectangle,
+                context::context_regular_polygon,
+                context::context_star,
+                context::context_superellipse,
+                context::context_spiral,
+                context::context_rose,
+                context::context_move_to,
+                context::context_rel_move_to,
+                context::context_close_path,
+                context::context_stroke,
+                context::context_stroke_preserve,
+                context::context_fill,
+                context::context_fill_preserve,
+                context::context_fill_markers,
+                context::context_sketch_stroke,
+                context::context_sketch_fill,
+                context::context_paint,
+                context::context_paint_with_alpha,
+                context::context_copy_path,
+                context::context_copy_path_flat,
+                context::context_append_path,
+                context::context_tolerance,
+                context::context_set_tolerance,
+                context::context_has_current_point,
+                context::context_current_point,
+                context::context_new_path,
+                context::context_new_sub_path,
+                context::context_show_text,
+                context::context_text_path,
+                context::context_show_text_along_path,
+                context::context_text_path_along_path,
+                context::context_glyph_paths,
+                context::context_set_font_size,
+                context::context_set_font_face,
+                context::context_select_font_face,
+                context::context_translate,
+                context::context_scale,
+                context::context_rotate,
+                context::context_transform,
+                context::context_set_matrix,
+                context::context_identity_matrix,
+                context::context_matrix,
+                context::context_set_font_matrix,
+                context::context_font_matrix,
+                context::context_mask_radial_gradient,
+                context::context_mask_linear_gradient,
+                context::context_mask_mesh,
+                context::context_mask_solid_pattern,
+                context::context_mask_surface_pattern,
+                context::context_mask_surface,
+                // context settings
+                context::context_set_line_width,
+                context::context_line_width,
+                context::context_set_antialias,
+                context::context_antialias,
+                context::context_set_fill_rule,
+                context::context_fill_rule,
+                context::context_set_line_cap,
+                context::context_line_cap,
+                context::context_set_line_join,
+                context::context_line_join,
+                context::context_set_miter_limit,
+                context::context_miter_limit,
+                context::context_set_dash,
+                context::context_dash_count,
+                context::context_dash,
+                context::context_dash_dashes,
+                context::context_dash_offset,
+                context::context_set_operator,
+                context::context_operator,
+                context::context_in_stroke,
+                context::context_in_fill,
+                context::context_user_to_device,
+                context::context_user_to_device_distance,
+                context::context_device_to_user,
+                context::context_device_to_user_distance,
+                // clip
+                context::context_clip,
+                context::context_clip_preserve,
+                context::context_reset_clip,
+                context::context_in_clip,
+                context::context_clip_extents,
+                context::context_clip_rectangle_list,
+                // extents
+                context::context_path_extents,
+                context::context_fill_extents,
+                context::context_stroke_extents,
+                // CONTEXT END
+                // path
+                path::path_iter,
+                path::path_from_segments,
+                path::path_equal,
+                path::path_segment_count,
+                path::path_from_svg,
+                path::path_to_svg,
+                path::path_transform,
+                path::path_translate,
+                path::path_scale,
+                path::path_rotate,
+                path::path_reverse,
+                path::path_length,
+                path::path_subpath_lengths,
+                path::path_point_at_length,
+                path::path_slice,
+                path::path_boolean,
+                path::path_stroke_to_path,
+                path::path_offset,
+                path::path_hatch,
+                path::path_simplify,
+                path::path_smooth,
+                path::path_in_fill,
+                path::path_in_stroke,
+                path::path_extents,
+                path::path_stroke_extents,
+                path::path_interpolate,
+                path::path_regular_polygon,
+                path::path_star,
+                path::path_superellipse,
+                path::path_spiral,
+                path::path_rose,
+                path::path_markers,
+                path::path_brush_stroke,
+                // contour
+                contour::contour_grid,
+                contour::contour_image_surface,
+                // dxf
+                dxf::dxf_encode,
+                // plotter
+                plotter::plotter_hpgl,
+                plotter::plotter_gcode,
+                // linear gradient
+                linear_gradient::linear_gradient_new,
+                linear_gradient::linear_gradient_linear_points,
+                linear_gradient::linear_gradient_color_stop_count,
+                linear_gradient::linear_gradient_add_color_stop,
+                linear_gradient::linear_gradient_color_stop_rgba,
+                // radial gradient
+                radial_gradient::radial_gradient_new,
+                radial_gradient::radial_gradient_radial_circles,
+                radial_gradient::radial_gradient_color_stop_count,
+                radial_gradient::radial_gradient_add_color_stop,
+                radial_gradient::radial_gradient_color_stop_rgba,
+                // solid pattern
+                solid_pattern::solid_pattern_from_rgba,
+                solid_pattern::solid_pattern_rgba,
+                // surface pattern
+                surface_pattern::surface_pattern_create_from_image_surface,
+                surface_pattern::surface_pattern_create_from_pdf_surface,
+                surface_pattern::surface_pattern_create_from_ps_surface,
+                surface_pattern::surface_pattern_create_from_svg_surface,
+                // mesh
+                mesh::mesh_new,
+                mesh::mesh_patch_count,
+                mesh::mesh_begin_patch,
+                mesh::mesh_end_patch,
+                mesh::mesh_move_to,
+                mesh::mesh_line_to,
+                mesh::mesh_curve_to,
+                mesh::mesh_set_control_point,
+                mesh::mesh_control_point,
+                mesh::mesh_set_corner_color,
+                mesh::mesh_corner_color_rgba,
+                mesh::mesh_path,
+                // font face
+                font_face::font_face_toy_create,
+                font_face::font_face_toy_get_family,
+                font_face::font_face_toy_get_slant,
+                font_face::font_face_toy_get_weight,
+                font_face::font_face_families,
+                font_face::font_face_styles,
+                // font match
+                font_match::font_match_font_match,
+                // matrix
+                matrix::matrix_new,
+                matrix::matrix_identity,
+                matrix::matrix_to_tuple,
+                matrix::matrix_transform_distance,
+                matrix::matrix_transform_point,
+                matrix::matrix_translate,
+                matrix::matrix_scale,
+                matrix::matrix_rotate,
+                matrix::matrix_invert,
+                matrix::matrix_multiply,
+                // font extents
+                font_extents::font_extents_font_extents,
+                // text extents
+                text_extents::text_extents_text_extents,
+                // text spacing
+                text_spacing::text_spacing_show_text,
+                text_spacing::text_spacing_text_path,
+                text_spacing::text_spacing_text_extents,
+                $($feature_nifs)*
+            ],
+            load = on_load
+        );
+    };
+}
+
+#[cfg(not(feature = "pango"))]
+init!();
+
+#[cfg(feature = "pango")]
+init!(
+    // layout
+    layout::layout_new,
+    layout::layout_set_text,
+    layout::layout_set_markup,
+    layout::layout_set_width,
+    layout::layout_set_wrap,
+    layout::layout_set_alignment,
+    layout::layout_set_font_description,
+    layout::layout_extents,
+    layout::layout_show,
+    layout::layout_path,
 );
 
 fn on_load(env: Env, _info: Term) -> bool {
@@ -209,5 +323,7 @@ fn on_load(env: Env, _info: Term) -> bool {
     rustler::resource!(mesh::Raw, env);
     rustler::resource!(font_face::Raw, env);
     rustler::resource!(matrix::Raw, env);
+    #[cfg(feature = "pango")]
+    rustler::resource!(layout::Raw, env);
     true
 }
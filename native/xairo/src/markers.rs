@@ -0,0 +1,171 @@
+// Markers drawn at the vertices of a path, as SVG's `marker-start`,
+// `marker-mid` and `marker-end` properties draw them.
+//
+// Each marker is an outline to fill, drawn in a coordinate system with its
+// origin at the vertex, its x-axis along the path's direction there, and a
+// unit of `size` times the line width, so that markers grow with the
+// strokes they decorate.
+
+use crate::{
+    bezier::Segment,
+    enums::{Marker, PathSegment},
+    point::Point,
+    shapes,
+    subpath::Subpath,
+};
+use std::f64::consts::PI;
+
+type Vertex = (f64, f64);
+
+/// The markers to draw at the start, at the interior vertices, and at the
+/// end of each subpath of a path, and their size in line widths.
+#[derive(rustler::NifMap)]
+pub struct MarkerStyle {
+    pub start: Option<Marker>,
+    pub mid: Option<Marker>,
+    pub end: Option<Marker>,
+    pub size: f64,
+}
+
+/// Returns the segments of the outlines of the markers `style` places on
+/// `path` when it is stroked `line_width` wide.
+///
+/// Start markers point back, away from the subpath, so that an arrow at
+/// either end of a line points outwards, and mid markers follow the
+/// direction halfway between those of the segments meeting at the vertex.
+/// A closed subpath has no ends, so every vertex of it, including its start,
+/// gets a mid marker.
+pub fn place(path: &cairo::Path, style: &MarkerStyle, line_width: f64) -> Vec<PathSegment> {
+    let scale = style.size * line_width;
+    // the line width in the marker's units
+    let thickness = if scale > 0.0 { line_width / scale } else { 0.0 };
+
+    let mut segments = Vec::new();
+    let mut draw = |marker: &Option<Marker>, vertex: Vertex, angle: f64| {
+        if let Some(marker) = marker {
+            let (sin, cos) = angle.sin_cos();
+            let place = |point: &Point| {
+                let (x, y) = (point.x * scale, point.y * scale);
+                Point::from((vertex.0 + x * cos - y * sin, vertex.1 + x * sin + y * cos))
+            };
+            segments.extend(
+                outline(marker, thickness)
+                    .iter()
+                    .map(|segment| match segment {
+                        PathSegment::MoveTo(point) => PathSegment::MoveTo(place(point)),
+                        PathSegment::LineTo(point) => PathSegment::LineTo(place(point)),
+                        PathSegment::CurveTo(point1, point2, point3) => {
+                            PathSegment::CurveTo(place(point1), place(point2), place(point3))
+                        }
+                        PathSegment::ClosePath => PathSegment::ClosePath,
+                    }),
+            );
+        }
+    };
+
+    for subpath in Subpath::from_path(path) {
+        // segments of no length have no direction to orient a marker by
+        let segments: Vec<Segment> = subpath
+            .closed_segments()
+            .into_iter()
+            .filter(|segment| !is_point(segment))
+            .collect();
+        let n = segments.len();
+        if n == 0 {
+            continue;
+        }
+
+        if subpath.closed {
+            for i in 0..n {
+                let (before, after) = (&segments[(i + n - 1) % n], &segments[i]);
+                draw(&style.mid, after.start(), bisector(before, after));
+            }
+        } else {
+            draw(
+                &style.start,
+                segments[0].start(),
+                segments[0].angle_at(0.0) + PI,
+            );
+            for pair in segments.windows(2) {
+                draw(&style.mid, pair[1].start(), bisector(&pair[0], &pair[1]));
+            }
+            draw(
+                &style.end,
+                segments[n - 1].end(),
+                segments[n - 1].angle_at(1.0),
+            );
+        }
+    }
+    segments
+}
+
+fn is_point(segment: &Segment) -> bool {
+    match segment {
+        Segment::Line(start, end) => start == end,
+        Segment::Curve(cubic) => {
+            cubic.p0 == cubic.p1 && cubic.p0 == cubic.p2 && cubic.p0 == cubic.p3
+        }
+    }
+}
+
+// The direction halfway between the one `before` ends in and the one `after`
+// starts in, or the first of them where the path turns straight back.
+fn bisector(before: &Segment, after: &Segment) -> f64 {
+    let (incoming, outgoing) = (before.angle_at(1.0), after.angle_at(0.0));
+    let (x, y) = (
+        incoming.cos() + outgoing.cos(),
+        incoming.sin() + outgoing.sin(),
+    );
+    if x.hypot(y) > 1e-9 {
+        y.atan2(x)
+    } else {
+        incoming
+    }
+}
+
+// The outline of a marker, one unit long, in its own coordinate system.
+//
+// Arrowheads reach one line width past the vertex, where their sides are a
+// line width apart, so that they cover the end of a line with butt caps.
+// Open arrows and bars are drawn a line width thick.
+fn outline(marker: &Marker, thickness: f64) -> Vec<PathSegment> {
+    match marker {
+        Marker::Arrow => {
+            let tip = thickness;
+            shapes::polyline(&[(tip, 0.0), (tip - 1.0, -0.5), (tip - 1.0, 0.5)], true)
+        }
+        Marker::OpenArrow => {
+            let tip = thickness;
+            // the inside of the arrow is its outside moved back far enough
+            // for the arms to be a line width thick
+            let inset = thickness * 5.0_f64.sqrt();
+            if inset >= 1.0 {
+                return outline(&Marker::Arrow, thickness);
+            }
+            let back = tip - 1.0;
+            shapes::polyline(
+                &[
+                    (tip, 0.0),
+                    (back, -0.5),
+                    (back, -(1.0 - inset) / 2.0),
+                    (tip - inset, 0.0),
+                    (back, (1.0 - inset) / 2.0),
+                    (back, 0.5),
+                ],
+                true,
+            )
+        }
+        Marker::Bar => {
+            let half = thickness / 2.0;
+            shapes::polyline(
+                &[(-half, -0.5), (half, -0.5), (half, 0.5), (-half, 0.5)],
+                true,
+            )
+        }
+        Marker::Circle => shapes::ellipse((0.0, 0.0), (0.5, 0.5)),
+        Marker::Square => {
+            shapes::polyline(&[(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)], true)
+        }
+        Marker::Path(path) => path.segments(),
+    }
+}
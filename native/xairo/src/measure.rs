@@ -0,0 +1,173 @@
+use crate::{bezier::Segment, enums::PathSegment, point::Point, subpath::Subpath};
+
+// A segment along with a table mapping its parameter to the arc length from
+// its start, sampled at the ends of the lines it flattens to.
+struct MeasuredSegment {
+    segment: Segment,
+    // (t, length) pairs, starting at (0, 0)
+    lengths: Vec<(f64, f64)>,
+    // whether this is the implicit line closing its subpath
+    closing: bool,
+}
+
+impl MeasuredSegment {
+    fn new(segment: Segment, closing: bool, tolerance: f64) -> Self {
+        let mut lengths = vec![(0.0, 0.0)];
+        let mut previous = segment.start();
+        let mut length = 0.0;
+        segment.flatten(tolerance, &mut |t, point| {
+            length += crate::bezier::distance(previous, point);
+            previous = point;
+            lengths.push((t, length));
+        });
+        Self {
+            segment,
+            lengths,
+            closing,
+        }
+    }
+
+    fn length(&self) -> f64 {
+        self.lengths[self.lengths.len() - 1].1
+    }
+
+    // The parameter at which the segment has covered `distance`.
+    fn t_at(&self, distance: f64) -> f64 {
+        let index = match self
+            .lengths
+            .binary_search_by(|(_, length)| length.partial_cmp(&distance).unwrap())
+        {
+            Ok(index) => return self.lengths[index].0,
+            Err(index) => index.max(1).min(self.lengths.len() - 1),
+        };
+        let (t0, length0) = self.lengths[index - 1];
+        let (t1, length1) = self.lengths[index];
+        if length1 > length0 {
+            t0 + (t1 - t0) * (distance - length0) / (length1 - length0)
+        } else {
+            t0
+        }
+    }
+}
+
+struct MeasuredSubpath {
+    segments: Vec<MeasuredSegment>,
+    closed: bool,
+    length: f64,
+}
+
+/// Measures arc lengths along a path's lines and curves.
+///
+/// Curves are measured by adaptively flattening them, so that the lines
+/// standing in for them stray no further than the given tolerance from the
+/// curve. Points found along a curve are evaluated on the curve itself.
+///
+/// Subpaths are treated as a single path that jumps from the end of one to
+/// the start of the next, and the line closing a closed subpath counts
+/// towards its length.
+pub struct PathMeasure {
+    subpaths: Vec<MeasuredSubpath>,
+}
+
+impl PathMeasure {
+    pub fn new(path: &cairo::Path, tolerance: f64) -> Self {
+        let subpaths = Subpath::from_path(path)
+            .iter()
+            .map(|subpath| {
+                let open_segments = subpath.segments.len();
+                let segments: Vec<MeasuredSegment> = subpath
+                    .closed_segments()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, segment)| {
+                        MeasuredSegment::new(segment, i >= open_segments, tolerance)
+                    })
+                    .collect();
+                MeasuredSubpath {
+                    length: segments.iter().map(MeasuredSegment::length).sum(),
+                    segments,
+                    closed: subpath.closed,
+                }
+            })
+            .collect();
+        Self { subpaths }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.subpaths.iter().map(|subpath| subpath.length).sum()
+    }
+
+    pub fn subpath_lengths(&self) -> Vec<f64> {
+        self.subpaths.iter().map(|subpath| subpath.length).collect()
+    }
+
+    /// Returns the point at the given distance along the path, and the angle
+    /// of the path's tangent there, in radians, or `None` if the distance
+    /// falls outside of the path.
+    pub fn point_at(&self, distance: f64) -> Option<((f64, f64), f64)> {
+        if distance < 0.0 {
+            return None;
+        }
+
+        let mut remaining = distance;
+        for segment in self.subpaths.iter().flat_map(|subpath| &subpath.segments) {
+            if remaining <= segment.length() {
+                let t = segment.t_at(remaining);
+                return Some((segment.segment.point_at(t), segment.segment.angle_at(t)));
+            }
+            remaining -= segment.length();
+        }
+        None
+    }
+
+    /// Returns the segments of the part of the path between the distances
+    /// `start` and `end`, splitting lines and curves where they cross either.
+    ///
+    /// Each subpath the range touches starts a new subpath in the result. A
+    /// closed subpath is only closed in the result if the whole of it lies
+    /// within the range.
+    pub fn slice(&self, start: f64, end: f64) -> Vec<PathSegment> {
+        let mut segments = Vec::new();
+        let mut offset = 0.0;
+
+        for subpath in &self.subpaths {
+            let whole = subpath.closed && start <= offset && end >= offset + subpath.length;
+            let mut started = false;
+
+            for measured in &subpath.segments {
+                let (from, to) = (offset, offset + measured.length());
+                offset = to;
+                if to <= start || from >= end {
+                    continue;
+                }
+
+                let t0 = measured.t_at((start - from).max(0.0));
+                let t1 = measured.t_at(end.min(to) - from);
+                let part = measured.segment.subsegment(t0, t1);
+
+                if !started {
+                    segments.push(PathSegment::MoveTo(Point::from(part.start())));
+                    started = true;
+                }
+                // a closed subpath taken whole is closed by the `ClosePath` below
+                if measured.closing && whole {
+                    continue;
+                }
+                segments.push(match part {
+                    Segment::Line(_, end) => PathSegment::LineTo(Point::from(end)),
+                    Segment::Curve(cubic) => PathSegment::CurveTo(
+                        Point::from(cubic.p1),
+                        Point::from(cubic.p2),
+                        Point::from(cubic.p3),
+                    ),
+                });
+            }
+
+            if whole && started {
+                segments.push(PathSegment::ClosePath);
+            }
+        }
+
+        segments
+    }
+}
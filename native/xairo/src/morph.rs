@@ -0,0 +1,279 @@
+// Interpolation between two paths.
+//
+// The paths are brought to the same structure first: their subpaths are
+// paired up in order, with a subpath missing from either path standing in as
+// a single point, and the longest segments of each pair are split in half
+// until both sides have as many segments. Closed subpaths are also turned to
+// run in the same direction and to start at the points that line up best, of
+// those nearest the other subpath's start.
+// The interpolated path then follows the segments' points, with a line paired
+// with a curve treated as a curve.
+
+use crate::{
+    bezier::{self, Cubic, Segment},
+    subpath::Subpath,
+};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+type Vertex = (f64, f64);
+
+// closed subpaths are only weighed turned to start at this many of the points
+// nearest the other's start, rather than at every point
+const MAX_ROTATIONS: usize = 16;
+
+/// Returns the subpaths of the path `t` of the way from `a` to `b`, so that
+/// `t = 0` draws the same shape as `a` and `t = 1` as `b`.
+///
+/// A subpath of the result is closed only if both of the subpaths it comes
+/// from are.
+pub fn interpolate(a: &cairo::Path, b: &cairo::Path, t: f64) -> Vec<Subpath> {
+    let (mut a, mut b) = (Subpath::from_path(a), Subpath::from_path(b));
+    // a subpath missing from one path grows out of the point the other path's
+    // subpath starts from
+    while a.len() < b.len() {
+        a.push(collapsed(&b[a.len()]));
+    }
+    while b.len() < a.len() {
+        b.push(collapsed(&a[b.len()]));
+    }
+
+    a.iter()
+        .zip(b.iter())
+        .map(|(from, to)| interpolate_subpaths(from, to, t))
+        .collect()
+}
+
+fn collapsed(subpath: &Subpath) -> Subpath {
+    let mut point = Subpath::new(subpath.start);
+    point.closed = subpath.closed;
+    point
+}
+
+fn interpolate_subpaths(a: &Subpath, b: &Subpath, t: f64) -> Subpath {
+    let closed = a.closed && b.closed;
+    let mut from = segments(a);
+    let mut to = segments(b);
+    if closed && signed_area(&from) * signed_area(&to) < 0.0 {
+        to = to.iter().rev().map(Segment::reversed).collect();
+    }
+
+    let count = from.len().max(to.len());
+    split_to(&mut from, count);
+    split_to(&mut to, count);
+    if closed {
+        let offset = best_rotation(&from, &to);
+        to.rotate_left(offset);
+    }
+
+    let start = bezier::lerp(from[0].start(), to[0].start(), t);
+    let mut segments: Vec<Segment> = from
+        .iter()
+        .zip(to.iter())
+        .map(|(from, to)| interpolate_segments(from, to, t))
+        .collect();
+    // a line back to the start is left for closing the subpath to draw
+    if let Some(Segment::Line(_, end)) = segments.last() {
+        if closed && *end == start {
+            segments.pop();
+        }
+    }
+
+    Subpath {
+        start,
+        segments,
+        closed,
+    }
+}
+
+// The segments of a subpath, including the line closing it, with a subpath
+// of no segments standing in as a line of no length.
+fn segments(subpath: &Subpath) -> Vec<Segment> {
+    let segments = subpath.closed_segments();
+    if segments.is_empty() {
+        vec![Segment::Line(subpath.start, subpath.start)]
+    } else {
+        segments
+    }
+}
+
+// The area enclosed by the segments' end points, which is enough to tell
+// which way around a closed subpath runs.
+fn signed_area(segments: &[Segment]) -> f64 {
+    segments
+        .iter()
+        .map(|segment| {
+            let ((x0, y0), (x1, y1)) = (segment.start(), segment.end());
+            x0 * y1 - x1 * y0
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+// An estimate of a segment's length, between the lengths of its chord and of
+// its control polygon, used to pick the segments to split.
+fn rough_length(segment: &Segment) -> f64 {
+    match segment {
+        Segment::Line(start, end) => bezier::distance(*start, *end),
+        Segment::Curve(cubic) => {
+            let polygon = bezier::distance(cubic.p0, cubic.p1)
+                + bezier::distance(cubic.p1, cubic.p2)
+                + bezier::distance(cubic.p2, cubic.p3);
+            (bezier::distance(cubic.p0, cubic.p3) + polygon) / 2.0
+        }
+    }
+}
+
+// A piece of one of the segments being split, covering `width` of the
+// segment at `index` from `start` of the way along it, ordered so that the longest piece, or of
+// those the one nearest the start of the subpath, comes first.
+struct Piece {
+    length: f64,
+    index: usize,
+    start: f64,
+    width: f64,
+    segment: Segment,
+}
+
+impl Piece {
+    fn new(index: usize, start: f64, width: f64, segment: Segment) -> Self {
+        Self {
+            length: rough_length(&segment),
+            index,
+            start,
+            width,
+            segment,
+        }
+    }
+}
+
+impl PartialEq for Piece {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Piece {}
+
+impl PartialOrd for Piece {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Piece {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.length
+            .total_cmp(&other.length)
+            .then_with(|| other.index.cmp(&self.index))
+            .then_with(|| other.start.total_cmp(&self.start))
+    }
+}
+
+// Splits the longest segment in half until there are `count` of them. Halving
+// only ever gives starts that are exact fractions of the segments, so the
+// pieces can be put back in order by where they start.
+fn split_to(segments: &mut Vec<Segment>, count: usize) {
+    if segments.len() >= count {
+        return;
+    }
+    let mut pieces: BinaryHeap<Piece> = segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| Piece::new(index, 0.0, 1.0, *segment))
+        .collect();
+    while pieces.len() < count {
+        let longest = match pieces.pop() {
+            Some(longest) => longest,
+            None => return,
+        };
+        let (head, tail) = match longest.segment {
+            Segment::Line(start, end) => {
+                let middle = bezier::lerp(start, end, 0.5);
+                (Segment::Line(start, middle), Segment::Line(middle, end))
+            }
+            Segment::Curve(cubic) => {
+                let (head, tail) = cubic.split(0.5);
+                (Segment::Curve(head), Segment::Curve(tail))
+            }
+        };
+        let (index, width) = (longest.index, longest.width / 2.0);
+        pieces.push(Piece::new(index, longest.start, width, head));
+        pieces.push(Piece::new(index, longest.start + width, width, tail));
+    }
+
+    let mut pieces = pieces.into_vec();
+    pieces.sort_unstable_by(|a, b| a.index.cmp(&b.index).then(a.start.total_cmp(&b.start)));
+    *segments = pieces.into_iter().map(|piece| piece.segment).collect();
+}
+
+// The number of segments to turn the closed subpath `to` by so that its
+// segments start as close as possible to the starts of those of `from`. Only
+// the turns starting `to` at one of the points nearest the start of `from`
+// are weighed.
+fn best_rotation(from: &[Segment], to: &[Segment]) -> usize {
+    let cost = |offset: usize| -> f64 {
+        from.iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                let distance =
+                    bezier::distance(segment.start(), to[(i + offset) % to.len()].start());
+                distance * distance
+            })
+            .sum()
+    };
+
+    let first = from[0].start();
+    let mut offsets: Vec<usize> = (0..to.len()).collect();
+    let nearest = |a: &usize, b: &usize| {
+        bezier::distance(first, to[*a].start())
+            .total_cmp(&bezier::distance(first, to[*b].start()))
+            .then(a.cmp(b))
+    };
+    if offsets.len() > MAX_ROTATIONS {
+        offsets.select_nth_unstable_by(MAX_ROTATIONS, nearest);
+        offsets.truncate(MAX_ROTATIONS);
+    }
+    offsets.sort_unstable();
+
+    offsets
+        .into_iter()
+        .map(|offset| (offset, cost(offset)))
+        .fold((0, f64::INFINITY), |best, (offset, cost)| {
+            if cost < best.1 {
+                (offset, cost)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+fn as_cubic(segment: &Segment) -> Cubic {
+    match segment {
+        Segment::Line(start, end) => Cubic::new(
+            *start,
+            bezier::lerp(*start, *end, 1.0 / 3.0),
+            bezier::lerp(*start, *end, 2.0 / 3.0),
+            *end,
+        ),
+        Segment::Curve(cubic) => *cubic,
+    }
+}
+
+fn interpolate_segments(from: &Segment, to: &Segment, t: f64) -> Segment {
+    let lerp = |p: Vertex, q: Vertex| bezier::lerp(p, q, t);
+    match (from, to) {
+        (Segment::Line(p0, p1), Segment::Line(q0, q1)) => {
+            Segment::Line(lerp(*p0, *q0), lerp(*p1, *q1))
+        }
+        _ => {
+            let (p, q) = (as_cubic(from), as_cubic(to));
+            Segment::Curve(Cubic::new(
+                lerp(p.p0, q.p0),
+                lerp(p.p1, q.p1),
+                lerp(p.p2, q.p2),
+                lerp(p.p3, q.p3),
+            ))
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Offsets the area filled by a path by a signed distance.
+//
+// Every point within the distance of the area's boundary lies under a stroke
+// of the boundary twice as wide, so the boundary is stroked with the chosen
+// join, and the stroke is added to the area to grow it, or cut out of it to
+// shrink it. The boolean operation resolves any self-intersections, and parts
+// of the area narrower than the inset disappear.
+
+use crate::{
+    bezier::MIN_TOLERANCE,
+    boolean,
+    enums::{BooleanOperation, Error, FillRule, LineCap, LineJoin, PathSegment},
+    stroke::{self, StrokeStyle},
+    subpath::Subpath,
+};
+
+/// Returns the segments of a path filling the area `path` fills under
+/// `fill_rule`, grown outwards by `distance`, or shrunk inwards if `distance`
+/// is negative.
+///
+/// Corners on the outside of the offset are drawn with `line_join`, with
+/// miters limited by `miter_limit` as `cairo::Context::stroke` limits them.
+/// Open subpaths are closed first, as they would be filled. Curves and round
+/// joins are flattened to within `tolerance`, or a thousandth of `distance`
+/// if that is larger.
+pub fn offset(
+    path: &cairo::Path,
+    distance: f64,
+    line_join: LineJoin,
+    miter_limit: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    let tolerance = tolerance.max(distance.abs() * MIN_TOLERANCE);
+    let mut subpaths = Subpath::from_path(path);
+    for subpath in &mut subpaths {
+        subpath.closed = true;
+    }
+    let area: Vec<Vec<(f64, f64)>> = subpaths
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect();
+
+    let style = StrokeStyle {
+        line_width: 2.0 * distance.abs(),
+        line_cap: LineCap::Butt,
+        line_join,
+        miter_limit,
+        dashes: Vec::new(),
+        dash_offset: 0.0,
+    };
+    let border = stroke::pieces(&subpaths, &style, tolerance)?;
+
+    let operation = if distance < 0.0 {
+        BooleanOperation::Difference
+    } else {
+        BooleanOperation::Union
+    };
+    Ok(boolean::apply_polygons(
+        (&area, fill_rule),
+        (&border, FillRule::Winding),
+        operation,
+    ))
+}
@@ -1,4 +1,21 @@
-use crate::enums::PathSegment;
+use crate::{
+    bezier::Segment,
+    boolean, brush,
+    enums::{
+        BooleanOperation, Error, FillRule, HatchPattern, LineCap, LineJoin, PathSegment,
+        WidthProfile,
+    },
+    hatch, hit_test,
+    markers::{self, MarkerStyle},
+    matrix::Matrix,
+    measure::PathMeasure,
+    morph, offset,
+    point::Point,
+    shapes, simplify,
+    stroke::{self, StrokeStyle},
+    subpath::{self, Subpath},
+    svg_path,
+};
 use rustler::ResourceArc;
 
 pub struct Raw {
@@ -10,10 +27,386 @@ unsafe impl Sync for Raw {}
 
 pub type Path = ResourceArc<Raw>;
 
+impl Raw {
+    /// Builds a path by replaying the given segments on a scratch context.
+    ///
+    /// The scratch context has an identity transformation matrix, so the
+    /// segments' coordinates are stored unchanged. As with any path built by
+    /// cairo, a `ClosePath` segment is followed by a `MoveTo` back to the
+    /// start of its subpath.
+    pub fn from_segments(segments: &[PathSegment]) -> Result<Self, cairo::Error> {
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 0, 0)?;
+        let context = cairo::Context::new(&surface)?;
+
+        for segment in segments {
+            segment.append_to(&context);
+        }
+
+        Ok(Self {
+            path: context.copy_path()?,
+        })
+    }
+
+    pub fn segments(&self) -> Vec<PathSegment> {
+        self.path.iter().map(std::convert::Into::into).collect()
+    }
+
+    /// Returns a copy of the path with every point mapped through `matrix`.
+    pub fn transform(&self, matrix: &cairo::Matrix) -> Result<Self, cairo::Error> {
+        let transform_point = |point: &Point| {
+            let (x, y) = matrix.transform_point(point.x, point.y);
+            Point { x, y }
+        };
+
+        let segments = self
+            .segments()
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::MoveTo(point) => PathSegment::MoveTo(transform_point(point)),
+                PathSegment::LineTo(point) => PathSegment::LineTo(transform_point(point)),
+                PathSegment::CurveTo(point1, point2, point3) => PathSegment::CurveTo(
+                    transform_point(point1),
+                    transform_point(point2),
+                    transform_point(point3),
+                ),
+                PathSegment::ClosePath => PathSegment::ClosePath,
+            })
+            .collect::<Vec<PathSegment>>();
+
+        Self::from_segments(&segments)
+    }
+
+    /// Returns a copy of the path traced in the opposite direction, with its
+    /// subpaths in reverse order.
+    ///
+    /// A closed subpath stays closed, and starts from the same point it did
+    /// before. Subpaths containing only a `MoveTo`, such as the one cairo
+    /// adds after each `ClosePath`, are dropped.
+    pub fn reverse(&self) -> Result<Self, cairo::Error> {
+        let subpaths: Vec<Subpath> = Subpath::from_path(&self.path)
+            .iter()
+            .rev()
+            .map(|subpath| {
+                // walking a closed subpath's closing line backwards first
+                // keeps it starting where the original did
+                let segments = if subpath.closed {
+                    subpath.closed_segments()
+                } else {
+                    subpath.segments.clone()
+                };
+                Subpath {
+                    start: if subpath.closed {
+                        subpath.start
+                    } else {
+                        subpath.end()
+                    },
+                    segments: segments.iter().rev().map(Segment::reversed).collect(),
+                    closed: subpath.closed,
+                }
+            })
+            .collect();
+
+        Self::from_segments(&subpath::to_path_segments(&subpaths))
+    }
+}
+
+fn transformed(path: &Raw, matrix: &cairo::Matrix) -> Result<Path, Error> {
+    match path.transform(matrix) {
+        Ok(raw) => Ok(ResourceArc::new(raw)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Builds a path resource from the given segments, as `Raw::from_segments`
+/// does.
+pub fn from_segments(segments: &[PathSegment]) -> Result<Path, Error> {
+    match Raw::from_segments(segments) {
+        Ok(raw) => Ok(ResourceArc::new(raw)),
+        Err(err) => Err(err.into()),
+    }
+}
+
 #[rustler::nif]
 fn path_iter(path: Path) -> Vec<PathSegment> {
-    path.path
-        .iter()
-        .map(std::convert::Into::into)
-        .collect::<Vec<PathSegment>>()
+    path.segments()
+}
+
+#[rustler::nif]
+fn path_from_segments(segments: Vec<PathSegment>) -> Result<Path, Error> {
+    from_segments(&segments)
+}
+
+#[rustler::nif]
+fn path_equal(path1: Path, path2: Path) -> bool {
+    path1.path.iter().eq(path2.path.iter())
+}
+
+#[rustler::nif]
+fn path_segment_count(path: Path) -> usize {
+    path.path.iter().count()
+}
+
+#[rustler::nif]
+fn path_from_svg(d: String) -> Result<Path, Error> {
+    match svg_path::parse(&d) {
+        Some(segments) => from_segments(&segments),
+        None => Err(Error::InvalidSvgPath),
+    }
+}
+
+#[rustler::nif]
+fn path_to_svg(path: Path, precision: usize) -> String {
+    svg_path::serialize(&path.segments(), precision)
+}
+
+#[rustler::nif]
+fn path_transform(path: Path, matrix: Matrix) -> Result<Path, Error> {
+    transformed(&path, &matrix.matrix)
+}
+
+#[rustler::nif]
+fn path_translate(path: Path, dx: f64, dy: f64) -> Result<Path, Error> {
+    let mut matrix = cairo::Matrix::identity();
+    matrix.translate(dx, dy);
+    transformed(&path, &matrix)
+}
+
+#[rustler::nif]
+fn path_scale(path: Path, sx: f64, sy: f64) -> Result<Path, Error> {
+    let mut matrix = cairo::Matrix::identity();
+    matrix.scale(sx, sy);
+    transformed(&path, &matrix)
+}
+
+#[rustler::nif]
+fn path_rotate(path: Path, radians: f64) -> Result<Path, Error> {
+    let mut matrix = cairo::Matrix::identity();
+    matrix.rotate(radians);
+    transformed(&path, &matrix)
+}
+
+#[rustler::nif]
+fn path_reverse(path: Path) -> Result<Path, Error> {
+    match path.reverse() {
+        Ok(raw) => Ok(ResourceArc::new(raw)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_length(path: Path, tolerance: f64) -> f64 {
+    PathMeasure::new(&path.path, tolerance).length()
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_subpath_lengths(path: Path, tolerance: f64) -> Vec<f64> {
+    PathMeasure::new(&path.path, tolerance).subpath_lengths()
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_point_at_length(path: Path, distance: f64, tolerance: f64) -> Option<(Point, f64)> {
+    PathMeasure::new(&path.path, tolerance)
+        .point_at(distance)
+        .map(|(point, angle)| (Point::from(point), angle))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_slice(path: Path, start: f64, end: f64, tolerance: f64) -> Result<Path, Error> {
+    let segments = PathMeasure::new(&path.path, tolerance).slice(start, end);
+    from_segments(&segments)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_boolean(
+    path1: Path,
+    path2: Path,
+    operation: BooleanOperation,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    let segments = boolean::apply(&path1.path, &path2.path, operation, fill_rule, tolerance);
+    from_segments(&segments)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_stroke_to_path(path: Path, style: StrokeStyle, tolerance: f64) -> Result<Path, Error> {
+    let segments = stroke::outline(&path.path, &style, tolerance)?;
+    from_segments(&segments)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_offset(
+    path: Path,
+    distance: f64,
+    line_join: LineJoin,
+    miter_limit: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    let segments = offset::offset(
+        &path.path,
+        distance,
+        line_join,
+        miter_limit,
+        fill_rule,
+        tolerance,
+    )?;
+    from_segments(&segments)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_hatch(
+    path: Path,
+    pattern: HatchPattern,
+    angle: f64,
+    spacing: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    let segments = hatch::fill(&path.path, pattern, angle, spacing, fill_rule, tolerance)?;
+    from_segments(&segments)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_simplify(path: Path, tolerance: f64) -> Result<Path, Error> {
+    let subpaths = simplify::simplify(&path.path, tolerance);
+    from_segments(&subpath::to_path_segments(&subpaths))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_smooth(path: Path, alpha: f64) -> Result<Path, Error> {
+    let subpaths = simplify::smooth(&path.path, alpha);
+    from_segments(&subpath::to_path_segments(&subpaths))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_in_fill(path: Path, point: Point, fill_rule: FillRule, tolerance: f64) -> bool {
+    hit_test::in_fill(&path.path, point.to_tuple(), fill_rule, tolerance)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_in_stroke(
+    path: Path,
+    point: Point,
+    style: StrokeStyle,
+    tolerance: f64,
+) -> Result<bool, Error> {
+    hit_test::in_stroke(&path.path, point.to_tuple(), &style, tolerance)
+}
+
+#[rustler::nif]
+fn path_extents(path: Path) -> Option<(Point, Point)> {
+    hit_test::extents(&path.path).map(|(min, max)| (Point::from(min), Point::from(max)))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_stroke_extents(
+    path: Path,
+    style: StrokeStyle,
+    tolerance: f64,
+) -> Result<Option<(Point, Point)>, Error> {
+    let extents = hit_test::stroke_extents(&path.path, &style, tolerance)?;
+    Ok(extents.map(|(min, max)| (Point::from(min), Point::from(max))))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_interpolate(path1: Path, path2: Path, t: f64) -> Result<Path, Error> {
+    let subpaths = morph::interpolate(&path1.path, &path2.path, t);
+    from_segments(&subpath::to_path_segments(&subpaths))
+}
+
+#[rustler::nif]
+fn path_regular_polygon(
+    center: Point,
+    radius: f64,
+    sides: u32,
+    rotation: f64,
+) -> Result<Path, Error> {
+    from_segments(&shapes::regular_polygon(
+        center.to_tuple(),
+        radius,
+        sides,
+        rotation,
+    )?)
+}
+
+#[rustler::nif]
+fn path_star(
+    center: Point,
+    outer_radius: f64,
+    inner_radius: f64,
+    points: u32,
+    rotation: f64,
+) -> Result<Path, Error> {
+    let center = center.to_tuple();
+    from_segments(&shapes::star(
+        center,
+        outer_radius,
+        inner_radius,
+        points,
+        rotation,
+    )?)
+}
+
+#[rustler::nif]
+fn path_superellipse(
+    center: Point,
+    radii: (f64, f64),
+    exponent: f64,
+    rotation: f64,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    let center = center.to_tuple();
+    from_segments(&shapes::superellipse(
+        center, radii, exponent, rotation, tolerance,
+    ))
+}
+
+#[rustler::nif]
+fn path_spiral(
+    center: Point,
+    radii: (f64, f64),
+    turns: f64,
+    rotation: f64,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    let center = center.to_tuple();
+    from_segments(&shapes::spiral(
+        center, radii.0, radii.1, turns, rotation, tolerance,
+    )?)
+}
+
+#[rustler::nif]
+fn path_rose(
+    center: Point,
+    radius: f64,
+    k: (u32, u32),
+    rotation: f64,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    from_segments(&shapes::rose(
+        center.to_tuple(),
+        radius,
+        k,
+        rotation,
+        tolerance,
+    )?)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_markers(path: Path, style: MarkerStyle, line_width: f64) -> Result<Path, Error> {
+    from_segments(&markers::place(&path.path, &style, line_width))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn path_brush_stroke(
+    path: Path,
+    profile: WidthProfile,
+    line_cap: LineCap,
+    smooth: bool,
+    tolerance: f64,
+) -> Result<Path, Error> {
+    from_segments(&brush::outline(
+        &path.path, &profile, line_cap, smooth, tolerance,
+    ))
 }
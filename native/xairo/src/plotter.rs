@@ -0,0 +1,284 @@
+// Export of paths as instructions for pen plotters, in HPGL or G-code.
+//
+// The paths are flattened to polylines, each drawn with the pen down. Unless
+// told to keep the paths' order, the polylines are reordered to cut down the
+// distance travelled with the pen up: starting from the origin, the polyline
+// drawn next is always the one with an end nearest the pen, drawn from that
+// end, where up to 16 points spread evenly around a closed polyline count as
+// its ends. A polyline starting where the last one ended is drawn without
+// lifting the pen.
+
+use crate::{enums::PlotUnit, path::Path, subpath::Subpath, svg_path::format_number};
+
+type Vertex = (f64, f64);
+
+// HPGL's plotter units per millimetre
+const HPGL_UNITS: f64 = 40.0;
+
+// closed polylines can be started from this many of their points at most, so
+// that ordering doesn't weigh every point of long ones
+const MAX_CLOSED_STARTS: usize = 16;
+
+/// How paths are written as HPGL.
+///
+/// Coordinates are in `unit`, and, if `page_height` is given, measured up
+/// from the bottom of a page that high rather than down from the top.
+/// `velocity` is the pen speed in centimetres per second, left to the
+/// plotter if not given.
+#[derive(rustler::NifMap)]
+pub struct HpglOptions {
+    pub unit: PlotUnit,
+    pub page_height: Option<f64>,
+    pub pen: u32,
+    pub velocity: Option<f64>,
+    pub optimize: bool,
+    pub tolerance: f64,
+}
+
+/// How paths are written as G-code.
+///
+/// Coordinates are in `unit`, and, if `page_height` is given, measured up
+/// from the bottom of a page that high rather than down from the top. They
+/// are written in inches if `output_unit` is `PlotUnit::In`, and in
+/// millimetres otherwise, with feed rates in the same unit per minute. Moves
+/// with the pen up are rapid moves unless `travel_rate` is given.
+#[derive(rustler::NifMap)]
+pub struct GcodeOptions {
+    pub unit: PlotUnit,
+    pub output_unit: PlotUnit,
+    pub page_height: Option<f64>,
+    pub pen_up: String,
+    pub pen_down: String,
+    pub feed_rate: f64,
+    pub travel_rate: Option<f64>,
+    pub optimize: bool,
+    pub tolerance: f64,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn plotter_hpgl(paths: Vec<Path>, options: HpglOptions) -> String {
+    let paths: Vec<&cairo::Path> = paths.iter().map(|path| &path.path).collect();
+    hpgl(&paths, &options)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn plotter_gcode(paths: Vec<Path>, options: GcodeOptions) -> String {
+    let paths: Vec<&cairo::Path> = paths.iter().map(|path| &path.path).collect();
+    gcode(&paths, &options)
+}
+
+/// Returns an HPGL program drawing `paths` with pen number `options.pen`.
+pub fn hpgl(paths: &[&cairo::Path], options: &HpglOptions) -> String {
+    let scale = options.unit.millimetres() * HPGL_UNITS;
+    let strokes = strokes(
+        paths,
+        options.page_height,
+        options.optimize,
+        options.tolerance,
+    );
+
+    let mut program = format!("IN;SP{};", options.pen);
+    if let Some(velocity) = options.velocity {
+        program.push_str(&format!("VS{};", format_number(velocity, 3)));
+    }
+    program.push('\n');
+
+    let mut pen = None;
+    for stroke in strokes {
+        let mut points: Vec<(i64, i64)> = stroke
+            .iter()
+            .map(|(x, y)| ((x * scale).round() as i64, (y * scale).round() as i64))
+            .collect();
+        points.dedup();
+        if pen != Some(points[0]) {
+            program.push_str(&format!("PU{},{};", points[0].0, points[0].1));
+        }
+        let coordinates: Vec<String> = points[1..]
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+        program.push_str(&format!("PD{};\n", coordinates.join(",")));
+        pen = points.last().copied();
+    }
+
+    program.push_str("PU;SP0;\n");
+    program
+}
+
+/// Returns a G-code program drawing `paths`, lowering and raising the pen
+/// with the `options.pen_down` and `options.pen_up` commands.
+pub fn gcode(paths: &[&cairo::Path], options: &GcodeOptions) -> String {
+    let (units, precision, scale) = match options.output_unit {
+        PlotUnit::In => ("G20", 4, options.unit.millimetres() / 25.4),
+        _ => ("G21", 3, options.unit.millimetres()),
+    };
+    let strokes = strokes(
+        paths,
+        options.page_height,
+        options.optimize,
+        options.tolerance,
+    );
+    let coordinates = |(x, y): Vertex| {
+        format!(
+            "X{} Y{}",
+            format_number(x * scale, precision),
+            format_number(y * scale, precision)
+        )
+    };
+
+    let feed_rate = format_number(options.feed_rate, precision);
+    let travel_rate = options
+        .travel_rate
+        .map(|rate| format_number(rate, precision));
+
+    let mut lines = vec![units.to_string(), "G90".to_string(), options.pen_up.clone()];
+    let mut pen = None;
+    // the feed rate last set, as it carries over to later moves
+    let mut rate = None;
+    let mut set_rate = |new_rate: &String| {
+        if rate.as_ref() == Some(new_rate) {
+            String::new()
+        } else {
+            rate = Some(new_rate.clone());
+            format!(" F{}", new_rate)
+        }
+    };
+    for stroke in strokes {
+        let start = coordinates(stroke[0]);
+        if pen.as_ref() != Some(&start) {
+            if pen.is_some() {
+                lines.push(options.pen_up.clone());
+            }
+            match &travel_rate {
+                Some(travel_rate) => lines.push(format!("G1 {}{}", start, set_rate(travel_rate))),
+                None => lines.push(format!("G0 {}", start)),
+            }
+            lines.push(options.pen_down.clone());
+            pen = Some(start);
+        }
+
+        for point in &stroke[1..] {
+            let end = coordinates(*point);
+            if pen.as_ref() != Some(&end) {
+                lines.push(format!("G1 {}{}", end, set_rate(&feed_rate)));
+                pen = Some(end);
+            }
+        }
+    }
+    if pen.is_some() {
+        lines.push(options.pen_up.clone());
+    }
+
+    lines.retain(|line| !line.is_empty());
+    lines.join("\n") + "\n"
+}
+
+// A polyline to draw with the pen down. A closed polyline's last point is
+// left implicit.
+struct Polyline {
+    points: Vec<Vertex>,
+    closed: bool,
+}
+
+// The points of each polyline to draw, in the order to draw them.
+fn strokes(
+    paths: &[&cairo::Path],
+    page_height: Option<f64>,
+    optimize: bool,
+    tolerance: f64,
+) -> Vec<Vec<Vertex>> {
+    let polylines: Vec<Polyline> = paths
+        .iter()
+        .flat_map(|path| Subpath::from_path(path))
+        .map(|subpath| {
+            let mut points: Vec<Vertex> = subpath
+                .flatten(tolerance)
+                .into_iter()
+                .map(|(x, y)| match page_height {
+                    Some(height) => (x, height - y),
+                    None => (x, y),
+                })
+                .collect();
+            if subpath.closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+                points.pop();
+            }
+            Polyline {
+                points,
+                closed: subpath.closed,
+            }
+        })
+        .collect();
+
+    if optimize {
+        order(polylines)
+    } else {
+        polylines
+            .into_iter()
+            .map(|polyline| drawn(&polyline, 0))
+            .collect()
+    }
+}
+
+// The points drawn for a polyline started from its point at `start`, which,
+// for an open polyline, must be its first or last point.
+fn drawn(polyline: &Polyline, start: usize) -> Vec<Vertex> {
+    let points = &polyline.points;
+    if polyline.closed {
+        let mut drawn: Vec<Vertex> = points[start..]
+            .iter()
+            .chain(&points[..start])
+            .copied()
+            .collect();
+        drawn.push(points[start]);
+        drawn
+    } else if start == 0 {
+        points.clone()
+    } else {
+        points.iter().rev().copied().collect()
+    }
+}
+
+// The points a polyline can be started from: both ends of an open polyline,
+// or points spread evenly around a closed one.
+fn starts(polyline: &Polyline) -> Vec<usize> {
+    let len = polyline.points.len();
+    if polyline.closed {
+        let step = len.div_ceil(MAX_CLOSED_STARTS);
+        (0..len).step_by(step.max(1)).collect()
+    } else {
+        vec![0, len - 1]
+    }
+}
+
+// Orders the polylines greedily, always drawing next the one that can be
+// started nearest to where the pen is.
+fn order(polylines: Vec<Polyline>) -> Vec<Vec<Vertex>> {
+    let distance = |(x0, y0): Vertex, (x1, y1): Vertex| (x1 - x0).powi(2) + (y1 - y0).powi(2);
+
+    let mut polylines: Vec<(Polyline, Vec<usize>)> = polylines
+        .into_iter()
+        .map(|polyline| {
+            let starts = starts(&polyline);
+            (polyline, starts)
+        })
+        .collect();
+    let mut pen = (0.0, 0.0);
+    let mut strokes = Vec::with_capacity(polylines.len());
+    while !polylines.is_empty() {
+        let mut best = (0, 0, f64::INFINITY);
+        for (i, (polyline, starts)) in polylines.iter().enumerate() {
+            for &start in starts {
+                let d = distance(pen, polyline.points[start]);
+                if d < best.2 {
+                    best = (i, start, d);
+                }
+            }
+        }
+
+        let (polyline, _) = polylines.remove(best.0);
+        let stroke = drawn(&polyline, best.1);
+        pen = stroke[stroke.len() - 1];
+        strokes.push(stroke);
+    }
+    strokes
+}
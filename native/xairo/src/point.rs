@@ -10,3 +10,9 @@ impl Point {
         (self.x, self.y)
     }
 }
+
+impl From<(f64, f64)> for Point {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
@@ -0,0 +1,383 @@
+// Outlines of common shapes, as the segments of a path to append to a
+// context or to build a `Path` from.
+
+use crate::{
+    bezier,
+    enums::{Error, PathSegment},
+    point::Point,
+};
+use std::f64::consts::{PI, TAU};
+
+type Vertex = (f64, f64);
+
+// how far along the tangents at its ends the control points of a cubic
+// approximating a quarter of a circle lie, as a fraction of the radius
+const KAPPA: f64 = 0.552_284_749_830_793_4;
+
+fn curve_to(control1: Vertex, control2: Vertex, point: Vertex) -> PathSegment {
+    PathSegment::CurveTo(
+        Point::from(control1),
+        Point::from(control2),
+        Point::from(point),
+    )
+}
+
+/// A closed ellipse around `center`, with horizontal and vertical radii
+/// `radii`, starting from its rightmost point and running in the direction
+/// of increasing angles, as `cairo::Context::arc` does.
+pub fn ellipse(center: Vertex, radii: (f64, f64)) -> Vec<PathSegment> {
+    let (cx, cy) = center;
+    let (rx, ry) = radii;
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+
+    vec![
+        PathSegment::MoveTo(Point::from((cx + rx, cy))),
+        curve_to((cx + rx, cy + ky), (cx + kx, cy + ry), (cx, cy + ry)),
+        curve_to((cx - kx, cy + ry), (cx - rx, cy + ky), (cx - rx, cy)),
+        curve_to((cx - rx, cy - ky), (cx - kx, cy - ry), (cx, cy - ry)),
+        curve_to((cx + kx, cy - ry), (cx + rx, cy - ky), (cx + rx, cy)),
+        PathSegment::ClosePath,
+    ]
+}
+
+/// A closed rectangle with rounded corners, running from `origin` along its
+/// width first, as `cairo::Context::rectangle` does.
+///
+/// `radii` are given for the corners in the order they are reached, starting
+/// with the one at `origin`. As in CSS, negative radii are treated as zero,
+/// and if the radii at either end of a side add up to more than its length,
+/// all of them are scaled down until none do.
+pub fn rounded_rectangle(
+    origin: Vertex,
+    width: f64,
+    height: f64,
+    radii: [f64; 4],
+) -> Vec<PathSegment> {
+    let (x, y) = origin;
+    let (w, h) = (width.abs(), height.abs());
+    let radii = radii.map(|radius| radius.max(0.0));
+    let sides = [w, h, w, h];
+    let scale = (0..4)
+        .map(|i| {
+            let sum = radii[i] + radii[(i + 1) % 4];
+            if sum > sides[i] {
+                sides[i] / sum
+            } else {
+                1.0
+            }
+        })
+        .fold(1.0, f64::min);
+
+    // the corners, and the directions of the sides leaving them
+    let (sx, sy) = (width.signum(), height.signum());
+    let corners = [
+        ((x, y), (sx, 0.0)),
+        ((x + width, y), (0.0, sy)),
+        ((x + width, y + height), (-sx, 0.0)),
+        ((x, y + height), (0.0, -sy)),
+    ];
+    // where the rounding of each corner starts and ends
+    let rounding = |i: usize| {
+        let ((px, py), (dx, dy)) = corners[i % 4];
+        let (_, (ix, iy)) = corners[(i + 3) % 4];
+        let radius = radii[i % 4] * scale;
+        (
+            (px, py),
+            (px - ix * radius, py - iy * radius),
+            (px + dx * radius, py + dy * radius),
+        )
+    };
+
+    let (_, _, start) = rounding(0);
+    let mut segments = vec![PathSegment::MoveTo(Point::from(start))];
+    let mut current = start;
+    // the corner at the origin is rounded last, so that the path ends where
+    // it started
+    for i in 1..=4 {
+        let (corner, entry, exit) = rounding(i);
+        // no line is needed between corners whose roundings meet, or back to
+        // the start, which closing the path draws
+        let closing = i == 4 && entry == exit;
+        if entry != current && !closing {
+            segments.push(PathSegment::LineTo(Point::from(entry)));
+        }
+        current = exit;
+        if entry != exit {
+            segments.push(curve_to(
+                bezier::lerp(entry, corner, KAPPA),
+                bezier::lerp(exit, corner, KAPPA),
+                exit,
+            ));
+        }
+    }
+    segments.push(PathSegment::ClosePath);
+    segments
+}
+
+// how many times a stretch of a sampled curve can be halved, which bounds the
+// number of lines drawn for any tolerance
+const MAX_DEPTH: u32 = 10;
+
+// stretches of curves split into many of them are halved fewer times, so
+// that no curve is sampled with more lines than this
+const MAX_LINES: usize = 1 << 16;
+
+// the most turns a spiral can make, and the largest numerator and
+// denominator of a rose's `k`, which each keep them within `MAX_LINES`
+const MAX_TURNS: f64 = 4096.0;
+const MAX_ROSE_TERM: u32 = 64;
+
+// The point at `angle` and `radius` from `center`.
+fn polar(center: Vertex, radius: f64, angle: f64) -> Vertex {
+    (
+        center.0 + radius * angle.cos(),
+        center.1 + radius * angle.sin(),
+    )
+}
+
+/// A subpath of lines through `points`, closed if `closed` is true.
+pub fn polyline(points: &[Vertex], closed: bool) -> Vec<PathSegment> {
+    let mut segments: Vec<PathSegment> = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            if i == 0 {
+                PathSegment::MoveTo(Point::from(*point))
+            } else {
+                PathSegment::LineTo(Point::from(*point))
+            }
+        })
+        .collect();
+    if closed && !segments.is_empty() {
+        segments.push(PathSegment::ClosePath);
+    }
+    segments
+}
+
+// Points along the curve `f` as its parameter runs from `t0` to `t1`, close
+// enough together that lines through them stay within `tolerance` of it.
+//
+// The range is split into `pieces` equal stretches, so that no feature of the
+// curve is missed, and each stretch is halved for as long as the curve's
+// midpoint over it lies further than `tolerance` from the line across it.
+fn sample<F: Fn(f64) -> Vertex>(
+    f: F,
+    (t0, t1): (f64, f64),
+    pieces: usize,
+    tolerance: f64,
+) -> Vec<Vertex> {
+    let mut points = vec![f(t0)];
+    let step = (t1 - t0) / pieces as f64;
+    let max_depth = MAX_DEPTH.min((MAX_LINES / pieces.max(1)).max(1).ilog2());
+    for i in 0..pieces {
+        let (start, end) = (t0 + step * i as f64, t0 + step * (i + 1) as f64);
+        subdivide(
+            &f,
+            (start, f(start)),
+            (end, f(end)),
+            tolerance,
+            max_depth,
+            &mut points,
+        );
+    }
+    points
+}
+
+fn subdivide<F: Fn(f64) -> Vertex>(
+    f: &F,
+    (t0, p0): (f64, Vertex),
+    (t1, p1): (f64, Vertex),
+    tolerance: f64,
+    depth: u32,
+    points: &mut Vec<Vertex>,
+) {
+    let t = (t0 + t1) / 2.0;
+    let p = f(t);
+    if depth > 0 && bezier::distance_to_segment(p, p0, p1) > tolerance {
+        subdivide(f, (t0, p0), (t, p), tolerance, depth - 1, points);
+        subdivide(f, (t, p), (t1, p1), tolerance, depth - 1, points);
+    } else {
+        points.push(p1);
+    }
+}
+
+// The points sampled around a closed curve, without the last one, which
+// closing the subpath returns to.
+fn sample_closed<F: Fn(f64) -> Vertex>(
+    f: F,
+    range: (f64, f64),
+    pieces: usize,
+    tolerance: f64,
+) -> Vec<PathSegment> {
+    let mut points = sample(f, range, pieces, tolerance);
+    points.pop();
+    polyline(&points, true)
+}
+
+/// A closed regular polygon with `sides` corners at `radius` from `center`,
+/// the first at the angle `rotation` and the rest following in the direction
+/// of increasing angles.
+///
+/// Returns no segments for fewer than 3 sides, and `Error::InvalidSize` for
+/// more than 65536.
+pub fn regular_polygon(
+    center: Vertex,
+    radius: f64,
+    sides: u32,
+    rotation: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    if u64::from(sides) > MAX_LINES as u64 {
+        return Err(Error::InvalidSize);
+    }
+    if sides < 3 {
+        return Ok(Vec::new());
+    }
+    let corners: Vec<Vertex> = (0..sides)
+        .map(|i| {
+            polar(
+                center,
+                radius,
+                rotation + TAU * f64::from(i) / f64::from(sides),
+            )
+        })
+        .collect();
+    Ok(polyline(&corners, true))
+}
+
+/// A closed star with `points` tips at `outer_radius` from `center`, joined
+/// by corners at `inner_radius` halfway between them, the first tip at the
+/// angle `rotation` and the rest following in the direction of increasing
+/// angles.
+///
+/// Returns no segments for fewer than 2 points, and `Error::InvalidSize` for
+/// more than 32768, which would give more than 65536 corners.
+pub fn star(
+    center: Vertex,
+    outer_radius: f64,
+    inner_radius: f64,
+    points: u32,
+    rotation: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    let corners = 2 * u64::from(points);
+    if corners > MAX_LINES as u64 {
+        return Err(Error::InvalidSize);
+    }
+    if points < 2 {
+        return Ok(Vec::new());
+    }
+    let corners: Vec<Vertex> = (0..2 * points)
+        .map(|i| {
+            let radius = if i % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            };
+            polar(
+                center,
+                radius,
+                rotation + PI * f64::from(i) / f64::from(points),
+            )
+        })
+        .collect();
+    Ok(polyline(&corners, true))
+}
+
+/// A closed superellipse, the curve `|x / rx|^n + |y / ry|^n = 1` for the
+/// `exponent` `n`, around `center` with its axes turned by `rotation`,
+/// flattened to lines within `tolerance` of it.
+///
+/// An exponent of 2 gives an ellipse, larger ones squarer shapes, and ones
+/// between 0 and 1 shapes with sides curving inwards to sharp points, such as
+/// an astroid at 2/3. Returns no segments for an exponent that is not
+/// positive.
+pub fn superellipse(
+    center: Vertex,
+    radii: (f64, f64),
+    exponent: f64,
+    rotation: f64,
+    tolerance: f64,
+) -> Vec<PathSegment> {
+    if exponent <= 0.0 || exponent.is_nan() {
+        return Vec::new();
+    }
+    let power = |value: f64| value.signum() * value.abs().powf(2.0 / exponent);
+    let (sin, cos) = rotation.sin_cos();
+    let point = |t: f64| {
+        let (x, y) = (radii.0 * power(t.cos()), radii.1 * power(t.sin()));
+        (center.0 + x * cos - y * sin, center.1 + x * sin + y * cos)
+    };
+    sample_closed(point, (0.0, TAU), 16, tolerance)
+}
+
+/// An open Archimedean spiral around `center`, starting at `inner_radius`
+/// and the angle `rotation` and moving steadily out to `outer_radius` over
+/// `turns` turns, flattened to lines within `tolerance` of it.
+///
+/// A positive number of turns runs in the direction of increasing angles,
+/// and a negative number the other way. Returns `Error::InvalidSize` for
+/// more than 4096 turns either way.
+pub fn spiral(
+    center: Vertex,
+    inner_radius: f64,
+    outer_radius: f64,
+    turns: f64,
+    rotation: f64,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    if turns.is_nan() || turns.abs() > MAX_TURNS {
+        return Err(Error::InvalidSize);
+    }
+    let point = |t: f64| {
+        let radius = inner_radius + (outer_radius - inner_radius) * t;
+        polar(center, radius, rotation + TAU * turns * t)
+    };
+    let pieces = (turns.abs() * 16.0).ceil().max(1.0) as usize;
+    Ok(polyline(
+        &sample(point, (0.0, 1.0), pieces, tolerance),
+        false,
+    ))
+}
+
+/// A closed rose curve, `r = radius * cos(k * θ)` for `k = n / d`, around
+/// `center` with its first petal pointing at the angle `rotation`, flattened
+/// to lines within `tolerance` of it.
+///
+/// The curve is drawn over as many turns as it takes to return to its start:
+/// with `d = 1` it has `n` petals if `n` is odd and `2n` if it is even.
+/// Returns no segments if `d` is 0, and `Error::InvalidSize` if `n` or `d`,
+/// in lowest terms, is above 64.
+pub fn rose(
+    center: Vertex,
+    radius: f64,
+    (n, d): (u32, u32),
+    rotation: f64,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    if d == 0 {
+        return Ok(Vec::new());
+    }
+    let divisor = gcd(n, d);
+    let (n, d) = (n / divisor, d / divisor);
+    if n > MAX_ROSE_TERM || d > MAX_ROSE_TERM {
+        return Err(Error::InvalidSize);
+    }
+    let k = f64::from(n) / f64::from(d);
+    // the curve traces itself again after half as many turns when both n and
+    // d are odd
+    let period = if n % 2 == 1 && d % 2 == 1 {
+        PI * f64::from(d)
+    } else {
+        TAU * f64::from(d)
+    };
+    let point = |t: f64| polar(center, radius * (k * t).cos(), rotation + t);
+    let pieces = 8 * n.max(d) as usize * (period / PI).round() as usize;
+    Ok(sample_closed(point, (0.0, period), pieces, tolerance))
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,211 @@
+// Simplification and smoothing of the straight lines in a path.
+//
+// Both work on runs of consecutive lines, leaving any curves in the path as
+// they are. Simplification keeps the fewest points of each run needed to stay
+// within a tolerance of the original, using the Ramer-Douglas-Peucker
+// algorithm, and smoothing replaces each line with a Catmull-Rom spline
+// segment through the run's points, converted to a cubic curve.
+
+use crate::{bezier, bezier::Cubic, bezier::Segment, subpath::Subpath};
+
+type Vertex = (f64, f64);
+
+/// Returns the subpaths of a path with every run of lines reduced to the
+/// fewest of its points that keep it within `tolerance` of the original.
+pub fn simplify(path: &cairo::Path, tolerance: f64) -> Vec<Subpath> {
+    Subpath::from_path(path)
+        .iter()
+        .map(|subpath| {
+            let mut simplified = Subpath::new(subpath.start);
+            simplified.closed = subpath.closed;
+            for run in runs(subpath) {
+                match run {
+                    Run::Lines(points) => {
+                        let kept = ramer_douglas_peucker(&points, tolerance);
+                        simplified
+                            .segments
+                            .extend(kept.windows(2).map(|line| Segment::Line(line[0], line[1])));
+                    }
+                    Run::Curve(cubic) => simplified.segments.push(Segment::Curve(cubic)),
+                }
+            }
+            drop_closing_line(&mut simplified);
+            simplified
+        })
+        .collect()
+}
+
+/// Returns the subpaths of a path with every line replaced by a curve, so
+/// that each run of lines becomes a smooth curve through the same points.
+///
+/// `alpha` sets how the curves are fitted between unevenly spaced points:
+/// `0` gives a uniform Catmull-Rom spline, `0.5` a centripetal one, which
+/// never forms cusps or loops within a segment, and `1` a chordal one.
+pub fn smooth(path: &cairo::Path, alpha: f64) -> Vec<Subpath> {
+    Subpath::from_path(path)
+        .iter()
+        .map(|subpath| {
+            let mut smoothed = Subpath::new(subpath.start);
+            smoothed.closed = subpath.closed;
+            let runs = runs(subpath);
+            if let [Run::Lines(points)] = runs.as_slice() {
+                if subpath.closed {
+                    let mut ring = points.clone();
+                    ring.dedup();
+                    // the run ends back at the start of a closed subpath
+                    if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+                        ring.pop();
+                    }
+                    if ring.len() > 2 {
+                        let n = ring.len();
+                        smoothed.segments = (0..n)
+                            .map(|i| {
+                                Segment::Curve(catmull_rom(
+                                    ring[(i + n - 1) % n],
+                                    ring[i],
+                                    ring[(i + 1) % n],
+                                    ring[(i + 2) % n],
+                                    alpha,
+                                ))
+                            })
+                            .collect();
+                        return smoothed;
+                    }
+                }
+            }
+
+            for run in runs {
+                match run {
+                    Run::Lines(mut points) => {
+                        points.dedup();
+                        let n = points.len();
+                        for i in 0..n.saturating_sub(1) {
+                            // the ends of the run are extended by reflecting
+                            // their neighbours, so the curve leaves them
+                            // heading straight for the next point
+                            let before = if i == 0 {
+                                reflect(points[1], points[0])
+                            } else {
+                                points[i - 1]
+                            };
+                            let after = if i + 2 == n {
+                                reflect(points[n - 2], points[n - 1])
+                            } else {
+                                points[i + 2]
+                            };
+                            smoothed.segments.push(Segment::Curve(catmull_rom(
+                                before,
+                                points[i],
+                                points[i + 1],
+                                after,
+                                alpha,
+                            )));
+                        }
+                    }
+                    Run::Curve(cubic) => smoothed.segments.push(Segment::Curve(cubic)),
+                }
+            }
+            smoothed
+        })
+        .collect()
+}
+
+enum Run {
+    Lines(Vec<Vertex>),
+    Curve(Cubic),
+}
+
+// Splits a subpath, including the line closing it if it is closed, into its
+// curves and the runs of lines between them, each given by its points.
+fn runs(subpath: &Subpath) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut points = vec![subpath.start];
+    for segment in subpath.closed_segments() {
+        match segment {
+            Segment::Line(_, end) => points.push(end),
+            Segment::Curve(cubic) => {
+                if points.len() > 1 {
+                    runs.push(Run::Lines(points));
+                }
+                runs.push(Run::Curve(cubic));
+                points = vec![cubic.p3];
+            }
+        }
+    }
+    if points.len() > 1 {
+        runs.push(Run::Lines(points));
+    }
+    runs
+}
+
+// The line back to the start of a closed subpath is drawn by closing it, so
+// is left implicit.
+fn drop_closing_line(subpath: &mut Subpath) {
+    if let Some(Segment::Line(_, end)) = subpath.segments.last() {
+        if subpath.closed && *end == subpath.start {
+            subpath.segments.pop();
+        }
+    }
+}
+
+// Keeps the ends of the run, and recursively the point furthest from the line
+// between the points kept on either side of it, while that is further than
+// `tolerance` from the line.
+fn ramer_douglas_peucker(points: &[Vertex], tolerance: f64) -> Vec<Vertex> {
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let furthest = (first + 1..last)
+            .map(|i| {
+                let distance = bezier::distance_to_segment(points[i], points[first], points[last]);
+                (i, distance)
+            })
+            .max_by(|(_, d0), (_, d1)| d0.partial_cmp(d1).unwrap());
+        if let Some((i, distance)) = furthest {
+            if distance > tolerance {
+                keep[i] = true;
+                stack.push((first, i));
+                stack.push((i, last));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(point, _)| *point)
+        .collect()
+}
+
+fn reflect((x, y): Vertex, (cx, cy): Vertex) -> Vertex {
+    (2.0 * cx - x, 2.0 * cy - y)
+}
+
+// The cubic curve following the Catmull-Rom spline from `p1` to `p2`, with
+// knots spaced by the distances between the points raised to `alpha`.
+fn catmull_rom(p0: Vertex, p1: Vertex, p2: Vertex, p3: Vertex, alpha: f64) -> Cubic {
+    let d1 = bezier::distance(p0, p1).powf(alpha);
+    let d2 = bezier::distance(p1, p2).powf(alpha);
+    let d3 = bezier::distance(p2, p3).powf(alpha);
+
+    let control = |from: Vertex, to: Vertex, away: Vertex, d_near: f64| {
+        // where two of the points coincide the tangent is undefined, so the
+        // curve leaves straight along the line
+        if d_near <= f64::EPSILON || d2 <= f64::EPSILON {
+            return bezier::lerp(from, to, 1.0 / 3.0);
+        }
+        let a = d_near * d_near;
+        let b = 2.0 * a + 3.0 * d_near * d2 + d2 * d2;
+        let c = 3.0 * d_near * (d_near + d2);
+        (
+            (a * to.0 - d2 * d2 * away.0 + b * from.0) / c,
+            (a * to.1 - d2 * d2 * away.1 + b * from.1) / c,
+        )
+    };
+
+    Cubic::new(p1, control(p1, p2, p0, d1), control(p2, p1, p3, d3), p2)
+}
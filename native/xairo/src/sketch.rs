@@ -0,0 +1,188 @@
+// Hand-drawn, "sketchy" renderings of paths, after rough.js.
+//
+// Every line of a path is drawn twice as a slightly bowed curve, with its
+// ends and the points it bows through moved by random amounts, and every
+// curve is drawn twice with its points moved. Areas are filled with hachure
+// lines, each drawn as a rough line in the same way. The amounts grow with
+// the roughness, and come from a generator seeded by the caller, so that the
+// same seed always gives the same drawing.
+
+use crate::{
+    bezier::{self, Cubic, Segment},
+    enums::{Error, FillRule, PathSegment},
+    hatch,
+    point::Point,
+    subpath::Subpath,
+};
+
+type Vertex = (f64, f64);
+
+// the furthest, at a roughness of 1, that the points of lines and curves are
+// moved from where they would be drawn
+const MAX_OFFSET: f64 = 2.0;
+
+/// How rough a sketchy drawing looks, and how areas are hachured.
+///
+/// `hachure_gap` and `fill_weight` are left to the caller to default, as they
+/// depend on the width strokes are drawn with.
+#[derive(rustler::NifMap)]
+pub struct SketchStyle {
+    pub roughness: f64,
+    pub bowing: f64,
+    pub seed: u64,
+    pub hachure_angle: f64,
+    pub hachure_gap: Option<f64>,
+    pub fill_weight: Option<f64>,
+}
+
+/// Returns the segments of a sketchy outline of `path`, to be stroked, with
+/// each line and curve of it drawn twice, including the lines closing closed
+/// subpaths.
+pub fn stroke(path: &cairo::Path, style: &SketchStyle) -> Vec<PathSegment> {
+    let mut sketch = Sketch::new(style);
+    for subpath in Subpath::from_path(path) {
+        for segment in subpath.closed_segments() {
+            match segment {
+                Segment::Line(start, end) => sketch.double_line(start, end),
+                Segment::Curve(cubic) => sketch.curve(&cubic),
+            }
+        }
+    }
+    sketch.segments
+}
+
+/// Returns the segments of rough hachure lines `gap` apart, to be stroked,
+/// filling the area `path` fills under `fill_rule`, with curves flattened to
+/// within `tolerance`.
+///
+/// Returns `Error::InvalidSize` if `gap` is not positive, or if more than
+/// 65536 lines would cross the area.
+pub fn hachure(
+    path: &cairo::Path,
+    style: &SketchStyle,
+    gap: f64,
+    fill_rule: FillRule,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    let polygons: Vec<Vec<Vertex>> = Subpath::from_path(path)
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect();
+    let mut sketch = Sketch::new(style);
+    for (start, end) in hatch::lines(&polygons, fill_rule, style.hachure_angle, gap)? {
+        sketch.double_line(start, end);
+    }
+    Ok(sketch.segments)
+}
+
+// A SplitMix64 generator, which spreads its values well from any seed,
+// including 0.
+struct Random(u64);
+
+impl Random {
+    // the next value, evenly spread over [0, 1)
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+struct Sketch {
+    random: Random,
+    roughness: f64,
+    bowing: f64,
+    segments: Vec<PathSegment>,
+}
+
+impl Sketch {
+    fn new(style: &SketchStyle) -> Self {
+        Self {
+            random: Random(style.seed),
+            roughness: style.roughness,
+            bowing: style.bowing,
+            segments: Vec::new(),
+        }
+    }
+
+    // a random amount between `-range` and `range`, scaled by the roughness
+    // and `gain`
+    fn offset(&mut self, range: f64, gain: f64) -> f64 {
+        self.roughness * gain * range * (2.0 * self.random.next() - 1.0)
+    }
+
+    fn jitter(&mut self, (x, y): Vertex, range: f64, gain: f64) -> Vertex {
+        (x + self.offset(range, gain), y + self.offset(range, gain))
+    }
+
+    fn move_to(&mut self, (x, y): Vertex) {
+        self.segments.push(PathSegment::MoveTo(Point { x, y }));
+    }
+
+    fn curve_to(&mut self, (x1, y1): Vertex, (x2, y2): Vertex, (x3, y3): Vertex) {
+        self.segments.push(PathSegment::CurveTo(
+            Point { x: x1, y: y1 },
+            Point { x: x2, y: y2 },
+            Point { x: x3, y: y3 },
+        ));
+    }
+
+    // A line drawn twice, the second time with half the movement, so that
+    // the strokes stay close together.
+    fn double_line(&mut self, start: Vertex, end: Vertex) {
+        self.line(start, end, 1.0);
+        self.line(start, end, 0.5);
+    }
+
+    // A line drawn as a curve bowing out to one side, diverging from the
+    // straight line a random fraction of the way along it. Long lines are
+    // moved relatively less, and short ones by no more than a tenth of their
+    // length.
+    fn line(&mut self, start: Vertex, end: Vertex, scale: f64) {
+        let length = bezier::distance(start, end);
+        let gain = if length < 200.0 {
+            1.0
+        } else if length > 500.0 {
+            0.4
+        } else {
+            1.233_334 - 0.001_666_8 * length
+        };
+        let range = scale * MAX_OFFSET.min(length / 10.0);
+
+        let diverge = 0.2 + 0.2 * self.random.next();
+        let bow_x = self.bowing * MAX_OFFSET * (end.1 - start.1) / 200.0;
+        let bow_y = self.bowing * MAX_OFFSET * (start.0 - end.0) / 200.0;
+        let bow = (self.offset(bow_x, gain), self.offset(bow_y, gain));
+        let along = |t: f64| {
+            let (x, y) = bezier::lerp(start, end, t);
+            (x + bow.0, y + bow.1)
+        };
+
+        let from = self.jitter(start, range, gain);
+        let control1 = self.jitter(along(diverge), range, gain);
+        let control2 = self.jitter(along(2.0 * diverge), range, gain);
+        let to = self.jitter(end, range, gain);
+        self.move_to(from);
+        self.curve_to(control1, control2, to);
+    }
+
+    // A curve drawn twice, the first time from its own start, and the second
+    // time with all of its points moved, a little further than the first.
+    fn curve(&mut self, cubic: &Cubic) {
+        for range in [MAX_OFFSET, MAX_OFFSET + 0.3] {
+            let start = if range > MAX_OFFSET {
+                self.jitter(cubic.p0, MAX_OFFSET, 1.0)
+            } else {
+                cubic.p0
+            };
+            let control1 = self.jitter(cubic.p1, range, 1.0);
+            let control2 = self.jitter(cubic.p2, range, 1.0);
+            let end = self.jitter(cubic.p3, range, 1.0);
+            self.move_to(start);
+            self.curve_to(control1, control2, end);
+        }
+    }
+}
@@ -0,0 +1,487 @@
+// Converts a stroke into the outline of the area it covers.
+//
+// Each subpath is flattened and split into dashes, and each dash is covered
+// by convex pieces: a rectangle along each of its lines, a wedge at each
+// corner for the line join, and a cap at each open end. The pieces are then
+// merged into a single fill with the nonzero winding rule.
+
+use crate::{
+    bezier::MIN_TOLERANCE,
+    boolean,
+    enums::{Error, FillRule, LineCap, LineJoin, PathSegment},
+    subpath::Subpath,
+};
+use std::f64::consts::PI;
+
+type Vertex = (f64, f64);
+
+// arcs are never split into more lines than this, however small the
+// tolerance
+const MAX_ARC_STEPS: usize = 4096;
+
+// a path is never split into more dashes than this, however short the dashes
+const MAX_DASHES: f64 = 65536.0;
+
+/// The parameters `cairo::Context::stroke` draws a path with.
+#[derive(rustler::NifMap)]
+pub struct StrokeStyle {
+    pub line_width: f64,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub miter_limit: f64,
+    pub dashes: Vec<f64>,
+    pub dash_offset: f64,
+}
+
+// a run of connected lines to be stroked, along with the direction of the
+// line a dash was taken from, for placing the caps of a dash of zero length
+struct Run {
+    points: Vec<Vertex>,
+    // whether each point was flattened from inside a curve, where cairo
+    // always uses round joins
+    smooth: Vec<bool>,
+    closed: bool,
+    direction: Option<Vertex>,
+}
+
+fn add((x0, y0): Vertex, (x1, y1): Vertex) -> Vertex {
+    (x0 + x1, y0 + y1)
+}
+
+fn scale((x, y): Vertex, factor: f64) -> Vertex {
+    (x * factor, y * factor)
+}
+
+fn unit((x0, y0): Vertex, (x1, y1): Vertex) -> Option<Vertex> {
+    let length = (x1 - x0).hypot(y1 - y0);
+    if length > f64::EPSILON {
+        Some(((x1 - x0) / length, (y1 - y0) / length))
+    } else {
+        None
+    }
+}
+
+// the direction a quarter turn from `direction`, on its left in a y-up
+// coordinate system
+const fn normal((x, y): Vertex) -> Vertex {
+    (-y, x)
+}
+
+fn rotate((x, y): Vertex, angle: f64) -> Vertex {
+    let (sin, cos) = angle.sin_cos();
+    (x * cos - y * sin, x * sin + y * cos)
+}
+
+fn signed_area(polygon: &[Vertex]) -> f64 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|((x0, y0), (x1, y1))| x0 * y1 - x1 * y0)
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Returns the segments of a path filling the area `cairo::Context::stroke`
+/// would paint for `path` in `style`.
+///
+/// Curves, round joins and round caps are flattened to within `tolerance`, or
+/// a thousandth of the pen's radius if that is larger. Returns
+/// `Error::InvalidDash` if any dash length is negative, if all of them are
+/// zero, or if the path would be split into more than 65536 dashes.
+pub fn outline(
+    path: &cairo::Path,
+    style: &StrokeStyle,
+    tolerance: f64,
+) -> Result<Vec<PathSegment>, Error> {
+    let pieces = pieces(&Subpath::from_path(path), style, tolerance)?;
+    Ok(boolean::fill(&pieces, FillRule::Winding))
+}
+
+/// Returns convex polygons, each oriented counter-clockwise in a y-up
+/// coordinate system, whose union is the area stroking `subpaths` in `style`
+/// would paint.
+pub fn pieces(
+    subpaths: &[Subpath],
+    style: &StrokeStyle,
+    tolerance: f64,
+) -> Result<Vec<Vec<Vertex>>, Error> {
+    let dashes = dash_pattern(&style.dashes)?;
+    let half_width = style.line_width / 2.0;
+    if half_width <= 0.0 {
+        return Ok(Vec::new());
+    }
+
+    let tolerance = tolerance.max(half_width * MIN_TOLERANCE);
+    let mut stroker = Stroker {
+        style,
+        half_width,
+        tolerance,
+        pieces: Vec::new(),
+    };
+    let runs = subpaths.iter().map(|subpath| {
+        let (points, smooth) = flatten(subpath, tolerance);
+        Run {
+            points,
+            smooth,
+            closed: subpath.closed,
+            direction: None,
+        }
+    });
+    match &dashes {
+        Some(dashes) => {
+            let runs: Vec<Run> = runs.collect();
+            let period: f64 = dashes.iter().sum();
+            let length: f64 = runs.iter().map(run_length).sum();
+            if length / period * (dashes.len() / 2) as f64 > MAX_DASHES {
+                return Err(Error::InvalidDash);
+            }
+            for run in &runs {
+                for dash in dash_run(run, dashes, style.dash_offset) {
+                    stroker.stroke_run(dash);
+                }
+            }
+        }
+        None => runs.for_each(|run| stroker.stroke_run(run)),
+    }
+
+    Ok(stroker.pieces)
+}
+
+fn flatten(subpath: &Subpath, tolerance: f64) -> (Vec<Vertex>, Vec<bool>) {
+    let mut points = vec![subpath.start];
+    let mut smooth = vec![false];
+    for segment in &subpath.segments {
+        segment.flatten(tolerance, &mut |t, point| {
+            points.push(point);
+            smooth.push(t < 1.0);
+        });
+    }
+    (points, smooth)
+}
+
+fn run_length(run: &Run) -> f64 {
+    let closing = if run.closed { run.points.first() } else { None };
+    run.points
+        .iter()
+        .zip(run.points.iter().skip(1).chain(closing))
+        .map(|((x0, y0), (x1, y1))| (x1 - x0).hypot(y1 - y0))
+        .sum()
+}
+
+// Validates the dash lengths, and repeats an odd number of them, as cairo
+// does, so that the pattern alternates between dashes and gaps.
+fn dash_pattern(dashes: &[f64]) -> Result<Option<Vec<f64>>, Error> {
+    if dashes.is_empty() {
+        return Ok(None);
+    }
+    if dashes.iter().any(|dash| *dash < 0.0) || dashes.iter().all(|dash| *dash == 0.0) {
+        return Err(Error::InvalidDash);
+    }
+    let mut pattern = dashes.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dashes);
+    }
+    Ok(Some(pattern))
+}
+
+// Splits a run into the dashes of the pattern, which starts `offset` into it.
+// The first and last dashes of a closed run are joined together if the
+// pattern is on where they meet.
+fn dash_run(run: &Run, dashes: &[f64], offset: f64) -> Vec<Run> {
+    let total: f64 = dashes.iter().sum();
+    let mut index = 0;
+    let mut remaining = offset.rem_euclid(total);
+    // a dash of zero length right at the start of the run is still drawn
+    while remaining > dashes[index] || (remaining > 0.0 && remaining == dashes[index]) {
+        remaining -= dashes[index];
+        index = (index + 1) % dashes.len();
+    }
+    remaining = dashes[index] - remaining;
+    let starts_on = index % 2 == 0;
+
+    let mut points = run.points.clone();
+    let mut smooth = run.smooth.clone();
+    if run.closed {
+        points.push(run.points[0]);
+        smooth.push(run.smooth[0]);
+    }
+
+    let mut runs = Vec::new();
+    let mut current: Vec<Vertex> = Vec::new();
+    let mut current_smooth: Vec<bool> = Vec::new();
+    let mut direction = (1.0, 0.0);
+
+    for i in 1..points.len() {
+        let (start, end) = (points[i - 1], points[i]);
+        let length = (end.0 - start.0).hypot(end.1 - start.1);
+        direction = match unit(start, end) {
+            Some(direction) => direction,
+            None => continue,
+        };
+
+        let mut position = 0.0;
+        loop {
+            let on = index % 2 == 0;
+            if on && current.is_empty() {
+                current.push(add(start, scale(direction, position)));
+                current_smooth.push(false);
+            }
+            if remaining > length - position {
+                remaining -= length - position;
+                if on {
+                    current.push(end);
+                    current_smooth.push(smooth[i]);
+                }
+                break;
+            }
+
+            position += remaining;
+            if on {
+                current.push(add(start, scale(direction, position)));
+                current_smooth.push(false);
+                runs.push(Run {
+                    points: std::mem::take(&mut current),
+                    smooth: std::mem::take(&mut current_smooth),
+                    closed: false,
+                    direction: Some(direction),
+                });
+            }
+            index = (index + 1) % dashes.len();
+            remaining = dashes[index];
+        }
+    }
+
+    if run.closed && starts_on && !current.is_empty() {
+        if runs.is_empty() {
+            // the pattern never turns off
+            return vec![Run {
+                points: run.points.clone(),
+                smooth: run.smooth.clone(),
+                closed: true,
+                direction: run.direction,
+            }];
+        }
+        let first = runs.remove(0);
+        current.extend(first.points.into_iter().skip(1));
+        current_smooth.extend(first.smooth.into_iter().skip(1));
+    }
+    // a dash that only starts where the run ends has no length to draw
+    if current
+        .windows(2)
+        .any(|line| unit(line[0], line[1]).is_some())
+    {
+        runs.push(Run {
+            points: current,
+            smooth: current_smooth,
+            closed: false,
+            direction: Some(direction),
+        });
+    }
+    runs
+}
+
+struct Stroker<'a> {
+    style: &'a StrokeStyle,
+    half_width: f64,
+    tolerance: f64,
+    // convex polygons whose union is the stroke
+    pieces: Vec<Vec<Vertex>>,
+}
+
+impl Stroker<'_> {
+    fn add_piece(&mut self, mut piece: Vec<Vertex>) {
+        let area = signed_area(&piece);
+        if area.abs() <= f64::EPSILON {
+            return;
+        }
+        if area < 0.0 {
+            piece.reverse();
+        }
+        self.pieces.push(piece);
+    }
+
+    fn stroke_run(&mut self, run: Run) {
+        let mut points: Vec<Vertex> = Vec::with_capacity(run.points.len());
+        let mut smooth: Vec<bool> = Vec::with_capacity(run.points.len());
+        for (point, point_smooth) in run.points.into_iter().zip(run.smooth) {
+            match points.last() {
+                Some(last) if unit(*last, point).is_none() => {
+                    let last_smooth = smooth.last_mut().unwrap();
+                    *last_smooth = *last_smooth && point_smooth;
+                }
+                _ => {
+                    points.push(point);
+                    smooth.push(point_smooth);
+                }
+            }
+        }
+        if run.closed && points.len() > 1 && unit(points[points.len() - 1], points[0]).is_none() {
+            points.pop();
+            let last_smooth = smooth.pop().unwrap();
+            smooth[0] = smooth[0] && last_smooth;
+        }
+
+        if points.len() == 1 {
+            self.degenerate_cap(points[0], run.direction);
+            return;
+        }
+
+        let closed = run.closed && points.len() > 1;
+        let line_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let line = |i: usize| (points[i % points.len()], points[(i + 1) % points.len()]);
+
+        for i in 0..line_count {
+            let (start, end) = line(i);
+            let offset = scale(normal(unit(start, end).unwrap()), self.half_width);
+            self.add_piece(vec![
+                add(start, offset),
+                add(end, offset),
+                add(end, scale(offset, -1.0)),
+                add(start, scale(offset, -1.0)),
+            ]);
+        }
+
+        let join_count = if closed { line_count } else { line_count - 1 };
+        for i in 0..join_count {
+            let (start, corner) = line(i);
+            let (_, end) = line(i + 1);
+            let line_join = if smooth[(i + 1) % points.len()] {
+                LineJoin::Round
+            } else {
+                self.style.line_join
+            };
+            self.join(
+                line_join,
+                corner,
+                unit(start, corner).unwrap(),
+                unit(corner, end).unwrap(),
+            );
+        }
+
+        if !closed {
+            let last = points.len() - 1;
+            self.cap(points[0], unit(points[1], points[0]).unwrap());
+            self.cap(points[last], unit(points[last - 1], points[last]).unwrap());
+        }
+    }
+
+    // The number of lines needed to approximate an arc through `angle`
+    // radians of the pen's circle to within the tolerance.
+    fn arc_steps(&self, angle: f64) -> usize {
+        let step = if self.tolerance < self.half_width {
+            2.0 * (1.0 - self.tolerance / self.half_width).acos()
+        } else {
+            PI / 2.0
+        };
+        ((angle.abs() / step).ceil() as usize).clamp(1, MAX_ARC_STEPS)
+    }
+
+    // The points of an arc of the pen's circle around `center`, starting in
+    // `direction` and turning through `angle` radians.
+    fn arc(&self, center: Vertex, direction: Vertex, angle: f64) -> Vec<Vertex> {
+        let steps = self.arc_steps(angle);
+        (0..=steps)
+            .map(|step| {
+                let offset = rotate(direction, angle * step as f64 / steps as f64);
+                add(center, scale(offset, self.half_width))
+            })
+            .collect()
+    }
+
+    fn join(&mut self, line_join: LineJoin, corner: Vertex, incoming: Vertex, outgoing: Vertex) {
+        let cross = incoming.0 * outgoing.1 - incoming.1 * outgoing.0;
+        let dot = incoming.0 * outgoing.0 + incoming.1 * outgoing.1;
+        let reversal = cross.abs() <= 1e-12 && dot < 0.0;
+        if cross.abs() <= 1e-12 && !reversal {
+            return;
+        }
+
+        // the outer side of the corner, where the rectangles of the two lines
+        // leave a gap to be filled
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let before = scale(normal(incoming), side);
+        let after = scale(normal(outgoing), side);
+
+        match line_join {
+            LineJoin::Round => {
+                // the arc turns through the outside of the corner, which is
+                // ambiguous only where the path doubles back on itself
+                let mut angle = cross.atan2(dot);
+                let (x, y) = rotate(before, angle / 2.0);
+                if reversal && x * incoming.0 + y * incoming.1 < 0.0 {
+                    angle = -angle;
+                }
+                let mut piece = vec![corner];
+                piece.extend(self.arc(corner, before, angle));
+                self.add_piece(piece);
+            }
+            LineJoin::Miter if !reversal && miter_ratio(dot) <= self.style.miter_limit => {
+                let miter = scale(add(before, after), self.half_width / (1.0 + dot));
+                self.add_piece(vec![
+                    corner,
+                    add(corner, scale(before, self.half_width)),
+                    add(corner, miter),
+                    add(corner, scale(after, self.half_width)),
+                ]);
+            }
+            _ => self.add_piece(vec![
+                corner,
+                add(corner, scale(before, self.half_width)),
+                add(corner, scale(after, self.half_width)),
+            ]),
+        }
+    }
+
+    // Caps the end of a run at `point`, where `direction` points away from
+    // the run.
+    fn cap(&mut self, point: Vertex, direction: Vertex) {
+        let side = scale(normal(direction), self.half_width);
+        match self.style.line_cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                let extension = scale(direction, self.half_width);
+                self.add_piece(vec![
+                    add(point, side),
+                    add(add(point, side), extension),
+                    add(add(point, scale(side, -1.0)), extension),
+                    add(point, scale(side, -1.0)),
+                ]);
+            }
+            LineCap::Round => self.add_piece(self.arc(point, normal(direction), -PI)),
+        }
+    }
+
+    // Caps a run with no length, which cairo draws as a dot in the shape of
+    // the line cap. A square is only drawn for a dash, facing along the line
+    // it lies on, as a subpath with no length has no direction.
+    fn degenerate_cap(&mut self, point: Vertex, direction: Option<Vertex>) {
+        match (self.style.line_cap, direction) {
+            (LineCap::Butt, _) | (LineCap::Square, None) => {}
+            (LineCap::Square, Some(direction)) => {
+                let along = scale(direction, self.half_width);
+                let across = scale(normal(direction), self.half_width);
+                self.add_piece(vec![
+                    add(add(point, along), across),
+                    add(add(point, scale(along, -1.0)), across),
+                    add(add(point, scale(along, -1.0)), scale(across, -1.0)),
+                    add(add(point, along), scale(across, -1.0)),
+                ]);
+            }
+            (LineCap::Round, _) => {
+                let mut circle = self.arc(point, (1.0, 0.0), 2.0 * PI);
+                circle.pop();
+                self.add_piece(circle);
+            }
+        }
+    }
+}
+
+// The ratio of the length of a miter to the line width, for lines meeting
+// where the cosine of the angle between their directions is `dot`.
+fn miter_ratio(dot: f64) -> f64 {
+    1.0 / ((1.0 + dot) / 2.0).sqrt()
+}
@@ -0,0 +1,123 @@
+use crate::{bezier::Segment, enums::PathSegment, point::Point};
+
+/// A connected run of lines and curves from a path, starting at a `MoveTo`.
+///
+/// A closed subpath's closing line is not included in `segments`; see
+/// `Subpath::closed_segments`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subpath {
+    pub start: (f64, f64),
+    pub segments: Vec<Segment>,
+    pub closed: bool,
+}
+
+impl Subpath {
+    pub const fn new(start: (f64, f64)) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// Splits a path into its subpaths.
+    ///
+    /// Subpaths made of a single `MoveTo`, such as the one cairo adds after
+    /// each `ClosePath`, are dropped, as they contain nothing to draw.
+    pub fn from_path(path: &cairo::Path) -> Vec<Self> {
+        let segments: Vec<PathSegment> = path.iter().map(PathSegment::from).collect();
+        Self::from_segments(&segments)
+    }
+
+    /// Splits a path, given as the segments `path_iter` returns for it, into
+    /// its subpaths, as `Subpath::from_path` does.
+    pub fn from_segments(segments: &[PathSegment]) -> Vec<Self> {
+        let mut subpaths: Vec<Self> = Vec::new();
+        let mut current: Option<Self> = None;
+        let mut point = (0.0, 0.0);
+
+        for segment in segments {
+            match segment {
+                PathSegment::MoveTo(to) => {
+                    let to = (to.x, to.y);
+                    subpaths.extend(current.replace(Self::new(to)));
+                    point = to;
+                }
+                PathSegment::LineTo(to) => {
+                    let to = (to.x, to.y);
+                    let subpath = current.get_or_insert_with(|| Self::new(point));
+                    subpath.segments.push(Segment::Line(point, to));
+                    point = to;
+                }
+                PathSegment::CurveTo(p1, p2, p3) => {
+                    let (p1, p2, p3) = ((p1.x, p1.y), (p2.x, p2.y), (p3.x, p3.y));
+                    let subpath = current.get_or_insert_with(|| Self::new(point));
+                    subpath
+                        .segments
+                        .push(Segment::Curve(crate::bezier::Cubic::new(point, p1, p2, p3)));
+                    point = p3;
+                }
+                PathSegment::ClosePath => {
+                    if let Some(mut subpath) = current.take() {
+                        subpath.closed = true;
+                        point = subpath.start;
+                        subpaths.push(subpath);
+                    }
+                }
+            }
+        }
+        subpaths.extend(current);
+        subpaths.retain(|subpath| !subpath.segments.is_empty() || subpath.closed);
+        subpaths
+    }
+
+    pub fn end(&self) -> (f64, f64) {
+        self.segments.last().map_or(self.start, Segment::end)
+    }
+
+    /// The subpath's segments, followed, for a closed subpath, by the line
+    /// back to its start if it does not already end there.
+    pub fn closed_segments(&self) -> Vec<Segment> {
+        let mut segments = self.segments.clone();
+        if self.closed && self.end() != self.start {
+            segments.push(Segment::Line(self.end(), self.start));
+        }
+        segments
+    }
+
+    /// The points of the lines approximating the subpath, to within
+    /// `tolerance`, starting with its start point. The line closing a closed
+    /// subpath is left implicit.
+    pub fn flatten(&self, tolerance: f64) -> Vec<(f64, f64)> {
+        let mut points = vec![self.start];
+        for segment in &self.segments {
+            segment.flatten(tolerance, &mut |_, point| points.push(point));
+        }
+        points
+    }
+
+    fn to_path_segments(&self) -> Vec<PathSegment> {
+        let mut segments = Vec::with_capacity(self.segments.len() + 2);
+        segments.push(PathSegment::MoveTo(Point::from(self.start)));
+        segments.extend(self.segments.iter().map(|segment| match segment {
+            Segment::Line(_, end) => PathSegment::LineTo(Point::from(*end)),
+            Segment::Curve(cubic) => PathSegment::CurveTo(
+                Point::from(cubic.p1),
+                Point::from(cubic.p2),
+                Point::from(cubic.p3),
+            ),
+        }));
+        if self.closed {
+            segments.push(PathSegment::ClosePath);
+        }
+        segments
+    }
+}
+
+/// Joins subpaths back into a list of segments for a path.
+pub fn to_path_segments(subpaths: &[Subpath]) -> Vec<PathSegment> {
+    subpaths
+        .iter()
+        .flat_map(Subpath::to_path_segments)
+        .collect()
+}
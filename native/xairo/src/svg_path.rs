@@ -0,0 +1,352 @@
+use crate::{
+    bezier::{self, Cubic},
+    enums::PathSegment,
+    point::Point,
+};
+
+/// Parses SVG path data, as found in a `<path>` element's `d` attribute, into
+/// the segments cairo can represent.
+///
+/// Relative commands are resolved to absolute coordinates, horizontal and
+/// vertical lines become `LineTo`s, quadratic curves are raised to cubics,
+/// and elliptical arcs are approximated by one cubic per quarter turn.
+///
+/// Returns `None` if the path data is malformed.
+pub fn parse(d: &str) -> Option<Vec<PathSegment>> {
+    Parser::new(d).parse()
+}
+
+/// Serializes path segments into compact SVG path data, with coordinates
+/// rounded to `precision` decimal places.
+///
+/// The `MoveTo` cairo adds after each `ClosePath` is omitted, since SVG
+/// returns to the start of the subpath after a `Z` as well.
+pub fn serialize(segments: &[PathSegment], precision: usize) -> String {
+    let mut d = String::new();
+    let mut previous: Option<char> = None;
+    let mut start: Option<&Point> = None;
+
+    for segment in segments {
+        let (command, points): (char, Vec<&Point>) = match segment {
+            PathSegment::MoveTo(point) => {
+                let returns_to_start =
+                    matches!(start, Some(start) if start.x == point.x && start.y == point.y);
+                if previous == Some('Z') && returns_to_start {
+                    previous = Some('M');
+                    continue;
+                }
+                start = Some(point);
+                ('M', vec![point])
+            }
+            PathSegment::LineTo(point) => ('L', vec![point]),
+            PathSegment::CurveTo(point1, point2, point3) => ('C', vec![point1, point2, point3]),
+            PathSegment::ClosePath => ('Z', vec![]),
+        };
+
+        // repeated coordinates after an `M` are read as `L`s, so only line and
+        // curve commands can be elided
+        let repeated = previous == Some(command) && command != 'M' && command != 'Z';
+        if !repeated {
+            d.push(command);
+        }
+        for (i, point) in points.iter().enumerate() {
+            for (j, value) in [point.x, point.y].iter().enumerate() {
+                let value = format_number(*value, precision);
+                let first = i == 0 && j == 0 && !repeated;
+                if !first && !value.starts_with('-') {
+                    d.push(' ');
+                }
+                d.push_str(&value);
+            }
+        }
+        previous = Some(command);
+    }
+
+    d
+}
+
+/// Formats a number with at most `precision` decimal places, dropping any
+/// trailing zeros and the decimal point if they leave nothing after it.
+pub fn format_number(value: f64, precision: usize) -> String {
+    let mut formatted = format!("{:.*}", precision, value);
+    if formatted.contains('.') {
+        let trimmed = formatted.trim_end_matches('0').trim_end_matches('.').len();
+        formatted.truncate(trimmed);
+    }
+    if formatted == "-0" {
+        formatted = "0".to_string();
+    }
+    formatted
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+    segments: Vec<PathSegment>,
+    current: (f64, f64),
+    start: (f64, f64),
+    // the control point to reflect for a following `S` or `T` command
+    last_cubic_control: Option<(f64, f64)>,
+    last_quadratic_control: Option<(f64, f64)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(d: &'a str) -> Self {
+        Self {
+            data: d.as_bytes(),
+            position: 0,
+            segments: Vec::new(),
+            current: (0.0, 0.0),
+            start: (0.0, 0.0),
+            last_cubic_control: None,
+            last_quadratic_control: None,
+        }
+    }
+
+    fn parse(mut self) -> Option<Vec<PathSegment>> {
+        self.skip_separators();
+        let mut command = match self.peek() {
+            None => return Some(self.segments),
+            Some(b'M') | Some(b'm') => self.next_command()?,
+            Some(_) => return None,
+        };
+
+        loop {
+            self.parse_command(command)?;
+            self.skip_separators();
+
+            command = match self.peek() {
+                None => return Some(self.segments),
+                Some(c) if c.is_ascii_alphabetic() => self.next_command()?,
+                // further arguments repeat the previous command, except that
+                // those following a move are implicit lines
+                Some(_) => match command {
+                    b'M' => b'L',
+                    b'm' => b'l',
+                    b'Z' | b'z' => return None,
+                    command => command,
+                },
+            };
+        }
+    }
+
+    fn parse_command(&mut self, command: u8) -> Option<()> {
+        let relative = command.is_ascii_lowercase();
+        let (cx, cy) = self.current;
+        let offset = |(x, y): (f64, f64)| {
+            if relative {
+                (x + cx, y + cy)
+            } else {
+                (x, y)
+            }
+        };
+
+        let mut cubic_control = None;
+        let mut quadratic_control = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = offset(self.point()?);
+                self.move_to(point);
+            }
+            b'L' => {
+                let point = offset(self.point()?);
+                self.line_to(point);
+            }
+            b'H' => {
+                let x = self.number()?;
+                let x = if relative { x + cx } else { x };
+                self.line_to((x, cy));
+            }
+            b'V' => {
+                let y = self.number()?;
+                let y = if relative { y + cy } else { y };
+                self.line_to((cx, y));
+            }
+            b'C' => {
+                let control1 = offset(self.point()?);
+                let control2 = offset(self.point()?);
+                let point = offset(self.point()?);
+                self.curve_to(control1, control2, point);
+                cubic_control = Some(control2);
+            }
+            b'S' => {
+                let control1 = reflect(self.last_cubic_control, self.current);
+                let control2 = offset(self.point()?);
+                let point = offset(self.point()?);
+                self.curve_to(control1, control2, point);
+                cubic_control = Some(control2);
+            }
+            b'Q' => {
+                let control = offset(self.point()?);
+                let point = offset(self.point()?);
+                self.quadratic_to(control, point);
+                quadratic_control = Some(control);
+            }
+            b'T' => {
+                let control = reflect(self.last_quadratic_control, self.current);
+                let point = offset(self.point()?);
+                self.quadratic_to(control, point);
+                quadratic_control = Some(control);
+            }
+            b'A' => {
+                let rx = self.number()?;
+                let ry = self.number()?;
+                let rotation = self.number()?;
+                let large_arc = self.flag()?;
+                let sweep = self.flag()?;
+                let point = offset(self.point()?);
+                self.arc_to((rx, ry), rotation, large_arc, sweep, point);
+            }
+            b'Z' => {
+                self.segments.push(PathSegment::ClosePath);
+                self.current = self.start;
+            }
+            _ => return None,
+        }
+
+        self.last_cubic_control = cubic_control;
+        self.last_quadratic_control = quadratic_control;
+        Some(())
+    }
+
+    fn move_to(&mut self, point: (f64, f64)) {
+        self.segments.push(PathSegment::MoveTo(Point::from(point)));
+        self.current = point;
+        self.start = point;
+    }
+
+    fn line_to(&mut self, point: (f64, f64)) {
+        self.segments.push(PathSegment::LineTo(Point::from(point)));
+        self.current = point;
+    }
+
+    fn curve_to(&mut self, control1: (f64, f64), control2: (f64, f64), point: (f64, f64)) {
+        self.segments.push(PathSegment::CurveTo(
+            Point::from(control1),
+            Point::from(control2),
+            Point::from(point),
+        ));
+        self.current = point;
+    }
+
+    fn quadratic_to(&mut self, control: (f64, f64), point: (f64, f64)) {
+        let cubic = Cubic::from_quadratic(self.current, control, point);
+        self.curve_to(cubic.p1, cubic.p2, cubic.p3);
+    }
+
+    fn arc_to(
+        &mut self,
+        radii: (f64, f64),
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        point: (f64, f64),
+    ) {
+        let arc = bezier::endpoint_arc(
+            self.current,
+            radii,
+            rotation.to_radians(),
+            large_arc,
+            sweep,
+            point,
+        );
+        match arc {
+            Some(curves) => {
+                for curve in curves {
+                    self.curve_to(curve.p1, curve.p2, curve.p3);
+                }
+            }
+            None => self.line_to(point),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn next_command(&mut self) -> Option<u8> {
+        let command = self.peek()?;
+        self.position += 1;
+        Some(command)
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_ascii_whitespace() || c == b',' {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn point(&mut self) -> Option<(f64, f64)> {
+        Some((self.number()?, self.number()?))
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let start = self.position;
+
+        if let Some(b'+') | Some(b'-') = self.peek() {
+            self.position += 1;
+        }
+        let integer_digits = self.digits();
+        let mut fraction_digits = 0;
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            fraction_digits = self.digits();
+        }
+        if integer_digits == 0 && fraction_digits == 0 {
+            return None;
+        }
+
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.position += 1;
+            }
+            // an `e` without an exponent belongs to whatever follows the number
+            if self.digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+
+        // a number too large for an f64 parses as infinity, which no path
+        // can be drawn through
+        std::str::from_utf8(&self.data[start..self.position])
+            .ok()?
+            .parse()
+            .ok()
+            .filter(|number: &f64| number.is_finite())
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    // arc flags are a single digit, and may be written without separators
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.position += 1;
+        Some(flag)
+    }
+}
+
+fn reflect(control: Option<(f64, f64)>, (x, y): (f64, f64)) -> (f64, f64) {
+    match control {
+        Some((cx, cy)) => (2.0 * x - cx, 2.0 * y - cy),
+        None => (x, y),
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    context::Context,
+    enums::{Error, FontSlant, FontWeight},
+    font_match,
+    text_extents::TextExtents,
+};
+use std::{
+    convert::TryFrom,
+    sync::{Arc, Mutex, PoisonError},
+};
+use ttf_parser::{
+    gpos::{PairAdjustment, PositioningSubtable},
+    opentype_layout::LayoutTable,
+    Face, GlyphId, Tag,
+};
+
+#[derive(rustler::NifMap)]
+pub struct TextSpacing {
+    pub letter_spacing: f64,
+    pub word_spacing: f64,
+    pub kerning: bool,
+    pub vertical: bool,
+}
+
+// The raw data of the font file cairo resolves the context's font face to,
+// which is read for the kerning and vertical metrics the toy text API ignores.
+struct FontData {
+    data: Vec<u8>,
+    index: u32,
+}
+
+// A toy font face, by its family, slant and weight.
+type FontKey = (String, FontSlant, FontWeight);
+
+// the font files of this many toy font faces are kept in memory, so that
+// laying out text in the same few fonts doesn't look up and read them again
+const MAX_FONT_DATA: usize = 8;
+
+// The font data of the toy font faces most recently laid out, least recently
+// used first. Faces whose font file could not be found or read are not kept,
+// so that fonts installed later are still found.
+static FONT_DATA: Mutex<Vec<(FontKey, Arc<FontData>)>> = Mutex::new(Vec::new());
+
+impl FontData {
+    fn for_context(context: &cairo::Context) -> Option<Arc<Self>> {
+        let font_face = context.font_face();
+        let family = font_face.toy_get_family()?;
+        let slant: FontSlant = font_face.toy_get_slant().into();
+        let weight: FontWeight = font_face.toy_get_weight().into();
+        let key = (family, slant, weight);
+
+        if let Some(font_data) = Self::cached(&key) {
+            return Some(font_data);
+        }
+        // the font is looked up and read without holding the lock, so
+        // another thread may have cached it in the meantime
+        let font_data = Arc::new(Self::read(&key.0, slant, weight)?);
+        let mut cache = FONT_DATA.lock().unwrap_or_else(PoisonError::into_inner);
+        cache.retain(|(cached, _)| *cached != key);
+        if cache.len() >= MAX_FONT_DATA {
+            cache.remove(0);
+        }
+        cache.push((key, Arc::clone(&font_data)));
+        Some(font_data)
+    }
+
+    fn cached(key: &FontKey) -> Option<Arc<Self>> {
+        let mut cache = FONT_DATA.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = cache.iter().position(|(cached, _)| cached == key)?;
+        let entry = cache.remove(index);
+        let font_data = Arc::clone(&entry.1);
+        cache.push(entry);
+        Some(font_data)
+    }
+
+    fn read(family: &str, slant: FontSlant, weight: FontWeight) -> Option<Self> {
+        let font_match = font_match::font_match(family, slant, weight).ok()?;
+        Some(Self {
+            data: std::fs::read(font_match.file).ok()?,
+            index: font_match.index as u32,
+        })
+    }
+
+    fn face(&self) -> Option<Face<'_>> {
+        Face::from_slice(&self.data, self.index).ok()
+    }
+}
+
+// The kerning between a pair of glyphs, from the pair adjustments of the
+// `kern` feature of the font's `GPOS` table, or, if it has no such feature,
+// from its legacy `kern` table.
+fn kerning(face: &Face, left: u64, right: u64) -> Option<i16> {
+    let left = GlyphId(u16::try_from(left).ok()?);
+    let right = GlyphId(u16::try_from(right).ok()?);
+
+    let tables = face.tables();
+    if let Some(gpos) = tables.gpos.filter(has_kern_feature) {
+        return gpos_kerning(&gpos, left, right);
+    }
+    tables
+        .kern?
+        .subtables
+        .into_iter()
+        .filter(|subtable| subtable.horizontal && !subtable.variable && !subtable.has_cross_stream)
+        .find_map(|subtable| subtable.glyphs_kerning(left, right))
+}
+
+const KERN: Tag = Tag::from_bytes(b"kern");
+
+fn has_kern_feature(gpos: &LayoutTable) -> bool {
+    gpos.features.into_iter().any(|feature| feature.tag == KERN)
+}
+
+// Each of the `kern` feature's lookups adjusts the pair by the first of its
+// subtables that covers it, and the adjustments add up. Only the first
+// glyph's advance is read, which is where horizontal kerning is given.
+fn gpos_kerning(gpos: &LayoutTable, left: GlyphId, right: GlyphId) -> Option<i16> {
+    let mut lookups: Vec<u16> = gpos
+        .features
+        .into_iter()
+        .filter(|feature| feature.tag == KERN)
+        .flat_map(|feature| feature.lookup_indices)
+        .collect();
+    lookups.sort_unstable();
+    lookups.dedup();
+
+    lookups
+        .into_iter()
+        .filter_map(|index| gpos.lookups.get(index))
+        .filter_map(|lookup| {
+            lookup
+                .subtables
+                .into_iter::<PositioningSubtable>()
+                .find_map(|subtable| match subtable {
+                    PositioningSubtable::Pair(pair) => pair_kerning(&pair, left, right),
+                    _ => None,
+                })
+        })
+        .reduce(|total, kern| total.saturating_add(kern))
+}
+
+fn pair_kerning(pair: &PairAdjustment, left: GlyphId, right: GlyphId) -> Option<i16> {
+    let (first, _) = match pair {
+        PairAdjustment::Format1 { coverage, sets } => sets.get(coverage.get(left)?)?.get(right)?,
+        PairAdjustment::Format2 {
+            coverage,
+            classes,
+            matrix,
+        } => {
+            coverage.get(left)?;
+            matrix.get((classes.0.get(left), classes.1.get(right)))?
+        }
+    };
+    Some(first.x_advance)
+}
+
+fn vertical_advance(face: &Face, glyph: u64) -> Option<u16> {
+    face.glyph_ver_advance(GlyphId(u16::try_from(glyph).ok()?))
+}
+
+fn is_word_separator(cluster: &str) -> bool {
+    !cluster.is_empty() && cluster.chars().all(|c| c == ' ' || c == '\u{a0}')
+}
+
+// Lays out the glyphs for `text` in the context's current font, starting
+// from `origin`, and returns them along with the extents of the text.
+//
+// Letter spacing is added between each pair of clusters, word spacing after
+// each space, and, if enabled, the font's kerning between each pair of
+// glyphs. In vertical mode each glyph is centered horizontally on the
+// starting point and placed below the previous one, using the font's vertical
+// advances where it provides them and the font's height where it does not.
+fn layout(
+    context: &cairo::Context,
+    origin: (f64, f64),
+    text: &str,
+    spacing: &TextSpacing,
+) -> Result<(Vec<cairo::Glyph>, TextExtents), cairo::Error> {
+    let scaled_font = context.scaled_font();
+    let font_extents = scaled_font.extents();
+    let font_matrix = scaled_font.font_matrix();
+    let (glyphs, clusters) = scaled_font.text_to_glyphs(0.0, 0.0, text)?;
+
+    // toy fonts map each character to exactly one glyph
+    let clusters = if clusters.is_empty() {
+        text.chars()
+            .map(|c| cairo::TextCluster {
+                num_bytes: c.len_utf8() as i32,
+                num_glyphs: 1,
+            })
+            .collect()
+    } else {
+        clusters
+    };
+
+    let font_data = if spacing.kerning || spacing.vertical {
+        FontData::for_context(context)
+    } else {
+        None
+    };
+    let face = font_data.as_deref().and_then(FontData::face);
+    let units_per_em = face
+        .as_ref()
+        .map_or(1.0, |face| f64::from(face.units_per_em()));
+
+    let mut laid_out: Vec<cairo::Glyph> = Vec::with_capacity(glyphs.len());
+    let mut pen = (0.0, 0.0);
+    let mut glyphs = glyphs.into_iter();
+    let mut byte_offset = 0;
+
+    for (i, cluster) in clusters.iter().enumerate() {
+        let byte_end = byte_offset + cluster.num_bytes as usize;
+        let cluster_text = text.get(byte_offset..byte_end).unwrap_or_default();
+        byte_offset = byte_end;
+
+        if i > 0 {
+            if spacing.vertical {
+                pen.1 += spacing.letter_spacing;
+            } else {
+                pen.0 += spacing.letter_spacing;
+            }
+        }
+
+        for glyph in glyphs.by_ref().take(cluster.num_glyphs as usize) {
+            let extents = scaled_font.glyph_extents(&[glyph]);
+
+            if spacing.vertical {
+                let advance = face
+                    .as_ref()
+                    .and_then(|face| vertical_advance(face, glyph.index))
+                    .map_or(font_extents.height, |advance| {
+                        font_matrix
+                            .transform_distance(0.0, f64::from(advance) / units_per_em)
+                            .1
+                    });
+
+                laid_out.push(cairo::Glyph {
+                    index: glyph.index,
+                    x: origin.0 + pen.0 - extents.x_advance / 2.0,
+                    y: origin.1 + pen.1 + font_extents.ascent,
+                });
+                pen.1 += advance;
+            } else {
+                if spacing.kerning {
+                    let kern = match (face.as_ref(), laid_out.last()) {
+                        (Some(face), Some(previous)) => kerning(face, previous.index, glyph.index),
+                        _ => None,
+                    };
+                    if let Some(kern) = kern {
+                        let (dx, dy) =
+                            font_matrix.transform_distance(f64::from(kern) / units_per_em, 0.0);
+                        pen.0 += dx;
+                        pen.1 += dy;
+                    }
+                }
+
+                laid_out.push(cairo::Glyph {
+                    index: glyph.index,
+                    x: origin.0 + pen.0,
+                    y: origin.1 + pen.1,
+                });
+                pen.0 += extents.x_advance;
+                pen.1 += extents.y_advance;
+            }
+        }
+
+        if is_word_separator(cluster_text) {
+            if spacing.vertical {
+                pen.1 += spacing.word_spacing;
+            } else {
+                pen.0 += spacing.word_spacing;
+            }
+        }
+    }
+
+    let mut extents: TextExtents = scaled_font.glyph_extents(&laid_out).into();
+    // cairo measures bearings from the first glyph's origin, rather than the
+    // starting point
+    if let Some(first) = laid_out.first() {
+        extents.x_bearing += first.x - origin.0;
+        extents.y_bearing += first.y - origin.1;
+    }
+    extents.x_advance = pen.0;
+    extents.y_advance = pen.1;
+    extents.text = Some(text.to_string());
+
+    Ok((laid_out, extents))
+}
+
+fn current_point_or_origin(context: &cairo::Context) -> Result<(f64, f64), cairo::Error> {
+    if context.has_current_point()? {
+        context.current_point()
+    } else {
+        Ok((0.0, 0.0))
+    }
+}
+
+// Like `show_text`, this renders the text starting at the current point and
+// moves the current point to the end of the text.
+fn show_text(
+    context: &cairo::Context,
+    text: &str,
+    spacing: &TextSpacing,
+) -> Result<TextExtents, cairo::Error> {
+    let (x, y) = current_point_or_origin(context)?;
+    let (glyphs, extents) = layout(context, (x, y), text, spacing)?;
+    context.show_glyphs(&glyphs)?;
+    context.move_to(x + extents.x_advance, y + extents.y_advance);
+    Ok(extents)
+}
+
+fn text_path(
+    context: &cairo::Context,
+    text: &str,
+    spacing: &TextSpacing,
+) -> Result<TextExtents, cairo::Error> {
+    let (x, y) = current_point_or_origin(context)?;
+    let (glyphs, extents) = layout(context, (x, y), text, spacing)?;
+    context.glyph_path(&glyphs);
+    context.move_to(x + extents.x_advance, y + extents.y_advance);
+    Ok(extents)
+}
+
+#[rustler::nif]
+fn text_spacing_show_text(
+    context: Context,
+    text: String,
+    spacing: TextSpacing,
+) -> Result<TextExtents, Error> {
+    match show_text(&context.context, &text, &spacing) {
+        Ok(extents) => Ok(extents),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn text_spacing_text_path(
+    context: Context,
+    text: String,
+    spacing: TextSpacing,
+) -> Result<TextExtents, Error> {
+    match text_path(&context.context, &text, &spacing) {
+        Ok(extents) => Ok(extents),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[rustler::nif]
+fn text_spacing_text_extents(
+    context: Context,
+    text: String,
+    spacing: TextSpacing,
+) -> Result<TextExtents, Error> {
+    match layout(&context.context, (0.0, 0.0), &text, &spacing) {
+        Ok((_glyphs, extents)) => Ok(extents),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,1150 @@
+// Tracing of the pixels of an image surface into smooth outlines, after
+// Peter Selinger's potrace.
+//
+// The image is first thresholded into ink and background. The boundaries
+// between the two are traced along the pixel edges, each giving a closed
+// path of unit steps, and paths enclosing no more than a given number of
+// pixels are dropped as specks. Each path is then fitted with the polygon of
+// fewest sides, and least error, that stays within half a pixel of it, whose
+// vertices are moved to where the lines best fitting the path either side of
+// them meet. The polygon's corners are smoothed into curves unless they are
+// sharper than a threshold, and runs of curves that bend the same way are
+// optionally joined into fewer curves, as long as they stay within a
+// tolerance of the original ones.
+
+use crate::{
+    enums::{Error, PathSegment},
+    image_surface::pixel,
+    point::Point,
+};
+
+type Vertex = (f64, f64);
+
+// how far a straight stretch of the path reaches when nothing constrains it
+const UNBOUNDED: i64 = 10_000_000;
+
+// the cosine of 179 degrees, past which a run of curves turns too far to be
+// joined into one
+const COS_179: f64 = -0.999_847_695_156;
+
+/// How an image is traced.
+///
+/// A pixel counts as ink if its darkness, or its alpha for `A8` and `A1`
+/// images, is greater than `threshold`, from `0` to `1`. Outlines enclosing
+/// no more than `turd_size` pixels are dropped. Corners are kept sharp if
+/// they turn more sharply than `corner_threshold` allows, from `0`, which
+/// keeps every corner, to `4 / 3`, which smooths them all. If
+/// `optimize_curves` is set, neighbouring curves are joined when the result
+/// stays within `optimize_tolerance` pixels of them.
+#[derive(rustler::NifMap)]
+pub struct TraceOptions {
+    pub threshold: f64,
+    pub turd_size: u32,
+    pub corner_threshold: f64,
+    pub optimize_curves: bool,
+    pub optimize_tolerance: f64,
+}
+
+/// Returns the traced outlines of the ink in `surface`, in pixels, as one
+/// list of segments for each separate shape, holding its outer outline
+/// followed by the outlines of any holes in it. Holes wind the opposite way
+/// to outer outlines, so that shapes fill correctly under either fill rule.
+///
+/// Shapes are in the order their topmost row of pixels is found in, reading
+/// the image from the top, and islands inside holes are shapes of their own.
+pub fn trace(
+    surface: &cairo::ImageSurface,
+    options: &TraceOptions,
+) -> Result<Vec<Vec<PathSegment>>, Error> {
+    let bitmap = Bitmap::from_surface(surface, options.threshold)?;
+    let outlines: Vec<Outline> = decompose(&bitmap)
+        .into_iter()
+        .filter(|outline| outline.area > i64::from(options.turd_size))
+        .collect();
+
+    let mut shapes: Vec<Vec<PathSegment>> = Vec::new();
+    // the shape each outer outline starts
+    let mut shape_of: Vec<Option<usize>> = vec![None; outlines.len()];
+    for (i, outline) in outlines.iter().enumerate() {
+        let segments = Curve::fit(outline, options).segments();
+        let parent = if outline.ink {
+            None
+        } else {
+            parent(&outlines, i)
+        };
+        match parent.and_then(|parent| shape_of[parent]) {
+            Some(shape) => shapes[shape].extend(segments),
+            None => {
+                shape_of[i] = Some(shapes.len());
+                shapes.push(segments);
+            }
+        }
+    }
+    Ok(shapes)
+}
+
+struct Bitmap {
+    width: i64,
+    height: i64,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    fn from_surface(surface: &cairo::ImageSurface, threshold: f64) -> Result<Self, Error> {
+        let width = surface.width() as usize;
+        let height = surface.height() as usize;
+        let stride = surface.stride() as usize;
+
+        let ink: fn(&[u8], usize, f64) -> bool = match surface.format() {
+            cairo::Format::A1 => |row, x, _| {
+                let bit = if cfg!(target_endian = "little") {
+                    x % 8
+                } else {
+                    7 - x % 8
+                };
+                row[x / 8] & (1 << bit) != 0
+            },
+            cairo::Format::A8 => |row, x, threshold| f64::from(row[x]) / 255.0 > threshold,
+            cairo::Format::ARgb32 => |row, x, threshold| darkness(pixel(row, x)) > threshold,
+            // pixels without alpha are opaque
+            cairo::Format::Rgb24 => {
+                |row, x, threshold| darkness(pixel(row, x) | 0xff00_0000) > threshold
+            }
+            _ => return Err(Error::InvalidFormat),
+        };
+
+        // an empty surface has no data to borrow, and nothing to trace
+        if width == 0 || height == 0 {
+            return Ok(Self {
+                width: 0,
+                height: 0,
+                pixels: Vec::new(),
+            });
+        }
+
+        let mut pixels = Vec::with_capacity(width * height);
+        let read = surface.with_data(|data| {
+            for y in 0..height {
+                let row = &data[y * stride..];
+                for x in 0..width {
+                    pixels.push(ink(row, x, threshold));
+                }
+            }
+        });
+        match read {
+            Ok(_) => Ok(Self {
+                width: width as i64,
+                height: height as i64,
+                pixels,
+            }),
+            Err(cairo::BorrowError::Cairo(err)) => Err(err.into()),
+            Err(_) => Err(Error::Error),
+        }
+    }
+
+    fn at(&self, x: i64, y: i64) -> bool {
+        x >= 0
+            && x < self.width
+            && y >= 0
+            && y < self.height
+            && self.pixels[(y * self.width + x) as usize]
+    }
+
+    fn flip(&mut self, x: i64, y: i64) {
+        let i = (y * self.width + x) as usize;
+        self.pixels[i] = !self.pixels[i];
+    }
+}
+
+// How dark a premultiplied ARGB pixel is, from 0 to 1, once composited over
+// white, using the Rec. 709 luminance of its colour.
+fn darkness(pixel: u32) -> f64 {
+    let alpha = pixel >> 24;
+    let channel = |shift: u32| f64::from(((pixel >> shift) & 0xff) + 255 - alpha) / 255.0;
+    1.0 - (0.2126 * channel(16) + 0.7152 * channel(8) + 0.0722 * channel(0))
+}
+
+// A closed path along the pixel edges, as the pixel corners it steps
+// through, with whether it encloses ink or a hole, and how many pixels it
+// encloses.
+struct Outline {
+    points: Vec<(i64, i64)>,
+    ink: bool,
+    area: i64,
+}
+
+// Traces the boundaries of the bitmap. Each boundary is found from the first
+// pixel, reading from the top left, that differs from the pixel outside it,
+// and the pixels it encloses are then flipped in a working copy, so that the
+// next boundary found is of a hole in it, or of another shape.
+fn decompose(bitmap: &Bitmap) -> Vec<Outline> {
+    let mut working = Bitmap {
+        width: bitmap.width,
+        height: bitmap.height,
+        pixels: bitmap.pixels.clone(),
+    };
+    let mut outlines = Vec::new();
+    let mut next = 0;
+    while let Some(i) = working.pixels[next..].iter().position(|&pixel| pixel) {
+        next += i;
+        let (x, y) = (next as i64 % bitmap.width, next as i64 / bitmap.width);
+        let outline = follow(&working, x, y, bitmap.at(x, y));
+        flip_inside(&mut working, &outline);
+        outlines.push(outline);
+    }
+    outlines
+}
+
+// Follows the boundary around the pixel at `x`, `y`, starting down its left
+// edge. Where the boundary could turn either way, through diagonally
+// touching pixels, it turns so as to join the pixels that are in the
+// minority around that point.
+fn follow(bitmap: &Bitmap, x0: i64, y0: i64, ink: bool) -> Outline {
+    let (mut x, mut y) = (x0, y0);
+    let (mut dx, mut dy) = (0, 1);
+    let mut points = Vec::new();
+    let mut area = 0;
+    loop {
+        points.push((x, y));
+        x += dx;
+        y += dy;
+        area -= x * dy;
+        if (x, y) == (x0, y0) {
+            break;
+        }
+
+        let left = bitmap.at(x + (dx + dy - 1) / 2, y + (dy - dx - 1) / 2);
+        let right = bitmap.at(x + (dx - dy - 1) / 2, y + (dy + dx - 1) / 2);
+        if right && (left || !majority(bitmap, x, y)) {
+            let turned = (-dy, dx);
+            dx = turned.0;
+            dy = turned.1;
+        } else if !left {
+            let turned = (dy, -dx);
+            dx = turned.0;
+            dy = turned.1;
+        }
+    }
+    Outline { points, ink, area }
+}
+
+// Whether most of the pixels in the squares of growing size around the
+// corner at `x`, `y` are set, looking further out until one way wins.
+fn majority(bitmap: &Bitmap, x: i64, y: i64) -> bool {
+    for i in 2..5 {
+        let mut count = 0;
+        for a in (1 - i)..i {
+            for &(px, py) in &[
+                (x + a, y + i - 1),
+                (x + i - 1, y + a - 1),
+                (x + a - 1, y - i),
+                (x - i, y + a),
+            ] {
+                count += if bitmap.at(px, py) { 1 } else { -1 };
+            }
+        }
+        if count != 0 {
+            return count > 0;
+        }
+    }
+    false
+}
+
+fn flip_inside(bitmap: &mut Bitmap, outline: &Outline) {
+    let max_x = outline.points.iter().map(|&(x, _)| x).max().unwrap_or(0);
+    let mut y1 = outline.points[0].1;
+    for &(x, y) in &outline.points[1..] {
+        if y != y1 {
+            for j in x..max_x {
+                bitmap.flip(j, y.min(y1));
+            }
+            y1 = y;
+        }
+    }
+}
+
+// The ink outline directly around hole `i`, which is the smallest one
+// containing the centre of the first pixel found inside the hole.
+fn parent(outlines: &[Outline], i: usize) -> Option<usize> {
+    let (x, y) = outlines[i].points[0];
+    let centre = (x as f64 + 0.5, y as f64 + 0.5);
+    outlines
+        .iter()
+        .enumerate()
+        .filter(|(_, outline)| outline.ink && contains(&outline.points, centre))
+        .min_by_key(|(_, outline)| outline.area)
+        .map(|(j, _)| j)
+}
+
+fn contains(points: &[(i64, i64)], (x, y): Vertex) -> bool {
+    let mut inside = false;
+    for (i, &(x1, y1)) in points.iter().enumerate() {
+        let (x0, y0) = points[(i + points.len() - 1) % points.len()];
+        let (x0, y0, x1, y1) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64);
+        if (y0 > y) != (y1 > y) && x < x0 + (y - y0) * (x1 - x0) / (y1 - y0) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tag {
+    Corner,
+    Curve,
+}
+
+// A closed outline of segments, each ending at the midpoint of one side of
+// the fitted polygon and turning at, or curving towards, the polygon's
+// vertex before it. A corner is drawn as lines to `c[1]` and `c[2]`, and a
+// curve as a cubic curve through the controls in `c`.
+struct Curve {
+    tags: Vec<Tag>,
+    c: Vec<[Vertex; 3]>,
+    vertices: Vec<Vertex>,
+    alphas: Vec<f64>,
+}
+
+impl Curve {
+    fn fit(outline: &Outline, options: &TraceOptions) -> Self {
+        let polygon = Polygon::new(&outline.points);
+        let mut vertices = polygon.adjusted_vertices();
+        if !outline.ink {
+            vertices.reverse();
+        }
+        let curve = smooth(vertices, options.corner_threshold);
+        if options.optimize_curves {
+            optimize(&curve, options.optimize_tolerance)
+        } else {
+            curve
+        }
+    }
+
+    fn segments(&self) -> Vec<PathSegment> {
+        let point = |(x, y): Vertex| Point { x, y };
+        let m = self.tags.len();
+        let mut segments = vec![PathSegment::MoveTo(point(self.c[m - 1][2]))];
+        for (tag, c) in self.tags.iter().zip(&self.c) {
+            match tag {
+                Tag::Corner => {
+                    segments.push(PathSegment::LineTo(point(c[1])));
+                    segments.push(PathSegment::LineTo(point(c[2])));
+                }
+                Tag::Curve => {
+                    segments.push(PathSegment::CurveTo(point(c[0]), point(c[1]), point(c[2])))
+                }
+            }
+        }
+        segments.push(PathSegment::ClosePath);
+        segments
+    }
+}
+
+// Sums of the coordinates of the points of a path, and of their squares and
+// products, up to each point, measured from the first point, from which the
+// line best fitting any stretch of the path can be found at once.
+#[derive(Clone, Copy, Default)]
+struct Sums {
+    x: f64,
+    y: f64,
+    xy: f64,
+    x2: f64,
+    y2: f64,
+}
+
+struct Polygon<'a> {
+    points: &'a [(i64, i64)],
+    sums: Vec<Sums>,
+    // the indices of the points of the path the polygon passes through
+    indices: Vec<usize>,
+}
+
+impl<'a> Polygon<'a> {
+    fn new(points: &'a [(i64, i64)]) -> Self {
+        let (x0, y0) = points[0];
+        let mut sums = vec![Sums::default()];
+        for &(x, y) in points {
+            let (x, y) = ((x - x0) as f64, (y - y0) as f64);
+            let last = sums[sums.len() - 1];
+            sums.push(Sums {
+                x: last.x + x,
+                y: last.y + y,
+                xy: last.xy + x * y,
+                x2: last.x2 + x * x,
+                y2: last.y2 + y * y,
+            });
+        }
+        let mut polygon = Self {
+            points,
+            sums,
+            indices: Vec::new(),
+        };
+        let longest = polygon.longest_straight();
+        polygon.indices = polygon.best(&longest);
+        polygon
+    }
+
+    fn len(&self) -> i64 {
+        self.points.len() as i64
+    }
+
+    fn point(&self, i: i64) -> (i64, i64) {
+        self.points[modulo(i, self.len()) as usize]
+    }
+
+    // For each point, the furthest point after it that it can be joined to
+    // by a straight line staying within half a pixel of the path between,
+    // without the path turning in all four directions on the way.
+    fn longest_straight(&self) -> Vec<i64> {
+        let n = self.len();
+        let mut next_corner = vec![0; n as usize];
+        let mut pivots = vec![0; n as usize];
+        let mut longest = vec![0; n as usize];
+
+        let mut k = 0;
+        for i in (0..n).rev() {
+            let (x, y) = self.point(i);
+            let (kx, ky) = self.point(k);
+            if x != kx && y != ky {
+                k = i + 1;
+            }
+            next_corner[i as usize] = k;
+        }
+
+        for i in (0..n).rev() {
+            let mut directions = [0; 4];
+            let step = sub(self.point(i + 1), self.point(i));
+            directions[direction(step)] += 1;
+
+            let mut constraints = [(0, 0), (0, 0)];
+            let mut k = next_corner[i as usize];
+            let mut k1 = i;
+            let mut found = false;
+            loop {
+                let step = sub(self.point(k), self.point(k1));
+                directions[direction((step.0.signum(), step.1.signum()))] += 1;
+                if directions.iter().all(|&count| count > 0) {
+                    pivots[i as usize] = k1;
+                    found = true;
+                    break;
+                }
+
+                let current = sub(self.point(k), self.point(i));
+                if cross(constraints[0], current) < 0 || cross(constraints[1], current) > 0 {
+                    break;
+                }
+                if current.0.abs() > 1 || current.1.abs() > 1 {
+                    let off = (
+                        current.0
+                            + if current.1 >= 0 && (current.1 > 0 || current.0 < 0) {
+                                1
+                            } else {
+                                -1
+                            },
+                        current.1
+                            + if current.0 <= 0 && (current.0 < 0 || current.1 < 0) {
+                                1
+                            } else {
+                                -1
+                            },
+                    );
+                    if cross(constraints[0], off) >= 0 {
+                        constraints[0] = off;
+                    }
+                    let off = (
+                        current.0
+                            + if current.1 <= 0 && (current.1 < 0 || current.0 < 0) {
+                                1
+                            } else {
+                                -1
+                            },
+                        current.1
+                            + if current.0 >= 0 && (current.0 > 0 || current.1 < 0) {
+                                1
+                            } else {
+                                -1
+                            },
+                    );
+                    if cross(constraints[1], off) <= 0 {
+                        constraints[1] = off;
+                    }
+                }
+                k1 = k;
+                k = next_corner[k1 as usize];
+                if !cyclic(k, i, k1) {
+                    break;
+                }
+            }
+
+            if !found {
+                let step = sub(self.point(k), self.point(k1));
+                let step = (step.0.signum(), step.1.signum());
+                let current = sub(self.point(k1), self.point(i));
+                let a = cross(constraints[0], current);
+                let b = cross(constraints[0], step);
+                let c = cross(constraints[1], current);
+                let d = cross(constraints[1], step);
+                let mut j = UNBOUNDED;
+                if b < 0 {
+                    j = a.div_euclid(-b);
+                }
+                if d > 0 {
+                    j = j.min((-c).div_euclid(d));
+                }
+                pivots[i as usize] = modulo(k1 + j, n);
+            }
+        }
+
+        let mut j = pivots[n as usize - 1];
+        longest[n as usize - 1] = j;
+        for i in (0..n - 1).rev() {
+            if cyclic(i + 1, pivots[i as usize], j) {
+                j = pivots[i as usize];
+            }
+            longest[i as usize] = j;
+        }
+        let mut i = n - 1;
+        while i >= 0 && cyclic(modulo(i + 1, n), j, longest[i as usize]) {
+            longest[i as usize] = j;
+            i -= 1;
+        }
+        longest
+    }
+
+    // The error of the line from point `i` to point `j`, taken as the
+    // square root of the mean of the squared distances of the points between
+    // them from it, scaled by its length.
+    fn penalty(&self, i: i64, j: i64) -> f64 {
+        let n = self.len();
+        let (j, wrapped) = if j >= n { (j - n, true) } else { (j, false) };
+        let (first, last, total) = (
+            self.sums[i as usize],
+            self.sums[j as usize + 1],
+            self.sums[n as usize],
+        );
+        let (x, y, xy, x2, y2, k) = if wrapped {
+            (
+                last.x - first.x + total.x,
+                last.y - first.y + total.y,
+                last.xy - first.xy + total.xy,
+                last.x2 - first.x2 + total.x2,
+                last.y2 - first.y2 + total.y2,
+                (j + 1 - i + n) as f64,
+            )
+        } else {
+            (
+                last.x - first.x,
+                last.y - first.y,
+                last.xy - first.xy,
+                last.x2 - first.x2,
+                last.y2 - first.y2,
+                (j + 1 - i) as f64,
+            )
+        };
+
+        let (x0, y0) = self.point(0);
+        let (xi, yi) = self.point(i);
+        let (xj, yj) = self.point(j);
+        let px = (xi + xj) as f64 / 2.0 - x0 as f64;
+        let py = (yi + yj) as f64 / 2.0 - y0 as f64;
+        let ey = (xj - xi) as f64;
+        let ex = -(yj - yi) as f64;
+
+        let a = (x2 - 2.0 * x * px) / k + px * px;
+        let b = (xy - x * py - y * px) / k + px * py;
+        let c = (y2 - 2.0 * y * py) / k + py * py;
+        (ex * ex * a + 2.0 * ex * ey * b + ey * ey * c).sqrt()
+    }
+
+    // The indices of the points of the polygon with the fewest sides, each
+    // a straight stretch of the path, and of those the one with the least
+    // error, found by dynamic programming over the ranges of points each
+    // vertex can fall in.
+    fn best(&self, longest: &[i64]) -> Vec<usize> {
+        let n = self.len();
+        let nu = n as usize;
+        let mut penalties = vec![0.0; nu + 1];
+        let mut previous = vec![0; nu + 1];
+        let mut clip0 = vec![0; nu];
+        let mut clip1 = vec![0; nu + 1];
+        let mut seg0 = vec![0; nu + 1];
+        let mut seg1 = vec![0; nu + 1];
+
+        for i in 0..n {
+            let mut c = modulo(longest[modulo(i - 1, n) as usize] - 1, n);
+            if c == i {
+                c = modulo(i + 1, n);
+            }
+            clip0[i as usize] = if c < i { n } else { c };
+        }
+
+        let mut j = 1;
+        for i in 0..n {
+            while j <= clip0[i as usize] {
+                clip1[j as usize] = i;
+                j += 1;
+            }
+        }
+
+        let mut i = 0;
+        let mut m = 0;
+        while i < n {
+            seg0[m] = i;
+            i = clip0[i as usize];
+            m += 1;
+        }
+        seg0[m] = n;
+
+        let mut i = n;
+        for j in (1..=m).rev() {
+            seg1[j] = i;
+            i = clip1[i as usize];
+        }
+        seg1[0] = 0;
+
+        for j in 1..=m {
+            for i in seg1[j]..=seg0[j] {
+                let mut best = -1.0;
+                let mut k = seg0[j - 1];
+                while k >= clip1[i as usize] {
+                    let penalty = self.penalty(k, i) + penalties[k as usize];
+                    if best < 0.0 || penalty < best {
+                        previous[i as usize] = k;
+                        best = penalty;
+                    }
+                    k -= 1;
+                }
+                penalties[i as usize] = best;
+            }
+        }
+
+        let mut indices = vec![0; m];
+        let mut i = n;
+        for j in (0..m).rev() {
+            i = previous[i as usize];
+            indices[j] = i as usize;
+        }
+        indices
+    }
+
+    // The centroid of the points from `i` to `j`, and the direction of the
+    // line through it best fitting them.
+    fn fit_line(&self, i: i64, j: i64) -> (Vertex, Vertex) {
+        let n = self.len();
+        let rounds = j.div_euclid(n) - i.div_euclid(n);
+        let (i, j) = (modulo(i, n) as usize, modulo(j, n) as usize);
+        let (first, last, total) = (self.sums[i], self.sums[j + 1], self.sums[n as usize]);
+        let r = rounds as f64;
+        let x = last.x - first.x + r * total.x;
+        let y = last.y - first.y + r * total.y;
+        let x2 = last.x2 - first.x2 + r * total.x2;
+        let xy = last.xy - first.xy + r * total.xy;
+        let y2 = last.y2 - first.y2 + r * total.y2;
+        let k = (j as i64 + 1 - i as i64 + rounds * n) as f64;
+
+        let centroid = (x / k, y / k);
+        let mut a = (x2 - x * x / k) / k;
+        let b = (xy - x * y / k) / k;
+        let mut c = (y2 - y * y / k) / k;
+        // the larger eigenvalue of the covariance matrix
+        let lambda = (a + c + ((a - c) * (a - c) + 4.0 * b * b).sqrt()) / 2.0;
+        a -= lambda;
+        c -= lambda;
+        let direction = if a.abs() >= c.abs() {
+            let l = (a * a + b * b).sqrt();
+            if l == 0.0 {
+                (0.0, 0.0)
+            } else {
+                (-b / l, a / l)
+            }
+        } else {
+            let l = (c * c + b * b).sqrt();
+            if l == 0.0 {
+                (0.0, 0.0)
+            } else {
+                (-c / l, b / l)
+            }
+        };
+        (centroid, direction)
+    }
+
+    // The polygon's vertices, each moved to the point within half a pixel
+    // of it nearest to the lines best fitting the path along the polygon's
+    // sides either side of it, measured by the sum of squared distances.
+    fn adjusted_vertices(&self) -> Vec<Vertex> {
+        let n = self.len();
+        let m = self.indices.len();
+        let (x0, y0) = self.point(0);
+        let indices: Vec<i64> = self.indices.iter().map(|&i| i as i64).collect();
+
+        let quadratics: Vec<[[f64; 3]; 3]> = (0..m)
+            .map(|i| {
+                let j = modulo(indices[(i + 1) % m] - indices[i], n) + indices[i];
+                let (centroid, direction) = self.fit_line(indices[i], j);
+                let d = direction.0 * direction.0 + direction.1 * direction.1;
+                let mut q = [[0.0; 3]; 3];
+                if d != 0.0 {
+                    let v = [
+                        direction.1,
+                        -direction.0,
+                        direction.0 * centroid.1 - direction.1 * centroid.0,
+                    ];
+                    for (l, row) in q.iter_mut().enumerate() {
+                        for (k, entry) in row.iter_mut().enumerate() {
+                            *entry = v[l] * v[k] / d;
+                        }
+                    }
+                }
+                q
+            })
+            .collect();
+
+        (0..m)
+            .map(|i| {
+                let (px, py) = self.point(indices[i]);
+                let s = ((px - x0) as f64, (py - y0) as f64);
+                let before = &quadratics[(i + m - 1) % m];
+                let mut q = [[0.0; 3]; 3];
+                for l in 0..3 {
+                    for k in 0..3 {
+                        q[l][k] = before[l][k] + quadratics[i][l][k];
+                    }
+                }
+
+                let w = loop {
+                    let det = q[0][0] * q[1][1] - q[0][1] * q[1][0];
+                    if det != 0.0 {
+                        break (
+                            (-q[0][2] * q[1][1] + q[1][2] * q[0][1]) / det,
+                            (q[0][2] * q[1][0] - q[1][2] * q[0][0]) / det,
+                        );
+                    }
+                    // the lines are parallel, so a line through the vertex
+                    // at right angles to them is added to pin the point down
+                    let (v0, v1) = if q[0][0] > q[1][1] {
+                        (-q[0][1], q[0][0])
+                    } else if q[1][1] != 0.0 {
+                        (-q[1][1], q[1][0])
+                    } else {
+                        (1.0, 0.0)
+                    };
+                    let d = v0 * v0 + v1 * v1;
+                    let v = [v0, v1, -v1 * s.1 - v0 * s.0];
+                    for (l, row) in q.iter_mut().enumerate() {
+                        for (k, entry) in row.iter_mut().enumerate() {
+                            *entry += v[l] * v[k] / d;
+                        }
+                    }
+                };
+
+                if (w.0 - s.0).abs() <= 0.5 && (w.1 - s.1).abs() <= 0.5 {
+                    return (w.0 + x0 as f64, w.1 + y0 as f64);
+                }
+
+                // the best point is outside the square around the vertex, so
+                // the best point on its edges or corners is used instead
+                let mut best = (quadratic_form(&q, s), s);
+                let mut consider = |w: Vertex| {
+                    let value = quadratic_form(&q, w);
+                    if value < best.0 {
+                        best = (value, w);
+                    }
+                };
+                if q[0][0] != 0.0 {
+                    for z in 0..2 {
+                        let y = s.1 - 0.5 + z as f64;
+                        let x = -(q[0][1] * y + q[0][2]) / q[0][0];
+                        if (x - s.0).abs() <= 0.5 {
+                            consider((x, y));
+                        }
+                    }
+                }
+                if q[1][1] != 0.0 {
+                    for z in 0..2 {
+                        let x = s.0 - 0.5 + z as f64;
+                        let y = -(q[1][0] * x + q[1][2]) / q[1][1];
+                        if (y - s.1).abs() <= 0.5 {
+                            consider((x, y));
+                        }
+                    }
+                }
+                for l in 0..2 {
+                    for k in 0..2 {
+                        consider((s.0 - 0.5 + l as f64, s.1 - 0.5 + k as f64));
+                    }
+                }
+                let (x, y) = best.1;
+                (x + x0 as f64, y + y0 as f64)
+            })
+            .collect()
+    }
+}
+
+// Turns the polygon into a curve, making each vertex a corner if the
+// polygon turns too sharply there, and otherwise a curve between the
+// midpoints of the sides either side of it, bulging towards the vertex by
+// an amount depending on how sharp the turn is.
+fn smooth(vertices: Vec<Vertex>, corner_threshold: f64) -> Curve {
+    let m = vertices.len();
+    let mut tags = vec![Tag::Corner; m];
+    let mut c = vec![[(0.0, 0.0); 3]; m];
+    let mut alphas = vec![0.0; m];
+    for i in 0..m {
+        let j = (i + 1) % m;
+        let k = (i + 2) % m;
+        let midpoint = lerp(vertices[k], vertices[j], 0.5);
+
+        let denominator = diagonal_denominator(vertices[i], vertices[k]);
+        let mut alpha = if denominator != 0.0 {
+            let dd = (area(vertices[i], vertices[j], vertices[k]) / denominator).abs();
+            let alpha = if dd > 1.0 { 1.0 - 1.0 / dd } else { 0.0 };
+            alpha / 0.75
+        } else {
+            4.0 / 3.0
+        };
+
+        if alpha >= corner_threshold {
+            tags[j] = Tag::Corner;
+            c[j] = [(0.0, 0.0), vertices[j], midpoint];
+        } else {
+            alpha = alpha.clamp(0.55, 1.0);
+            tags[j] = Tag::Curve;
+            c[j] = [
+                lerp(vertices[i], vertices[j], 0.5 + 0.5 * alpha),
+                lerp(vertices[k], vertices[j], 0.5 + 0.5 * alpha),
+                midpoint,
+            ];
+        }
+        alphas[j] = alpha;
+    }
+    Curve {
+        tags,
+        c,
+        vertices,
+        alphas,
+    }
+}
+
+// A curve replacing a run of curves, with the fractions along the polygon's
+// sides its controls lie at.
+#[derive(Clone, Copy, Default)]
+struct Joined {
+    penalty: f64,
+    c: [Vertex; 2],
+    alpha: f64,
+    s: f64,
+}
+
+// Joins runs of curves into as few curves as possible, and of those the ones
+// with the least error, as long as each run is convex, turns through less
+// than a half turn, and the curve replacing it stays within `tolerance` of
+// the polygon's sides and of the curves it replaces.
+fn optimize(curve: &Curve, tolerance: f64) -> Curve {
+    let m = curve.tags.len();
+    let vertices = &curve.vertices;
+
+    let convexity: Vec<i32> = (0..m)
+        .map(|i| match curve.tags[i] {
+            Tag::Curve => sign(area(
+                vertices[(i + m - 1) % m],
+                vertices[i],
+                vertices[(i + 1) % m],
+            )),
+            Tag::Corner => 0,
+        })
+        .collect();
+
+    // the area enclosed by the curve up to each segment, from the first
+    // vertex
+    let mut areas = vec![0.0; m + 1];
+    let mut total = 0.0;
+    let p0 = vertices[0];
+    for i in 0..m {
+        let i1 = (i + 1) % m;
+        if curve.tags[i1] == Tag::Curve {
+            let alpha = curve.alphas[i1];
+            total +=
+                0.3 * alpha * (4.0 - alpha) * area(curve.c[i][2], vertices[i1], curve.c[i1][2])
+                    / 2.0;
+            total += area(p0, curve.c[i][2], curve.c[i1][2]) / 2.0;
+        }
+        areas[i + 1] = total;
+    }
+
+    let mut previous = vec![0; m + 1];
+    let mut penalties = vec![0.0; m + 1];
+    let mut lengths = vec![0; m + 1];
+    let mut joined = vec![Joined::default(); m + 1];
+    for j in 1..=m {
+        previous[j] = j - 1;
+        penalties[j] = penalties[j - 1];
+        lengths[j] = lengths[j - 1] + 1;
+        for i in (0..j.saturating_sub(1)).rev() {
+            let join = match join(curve, i, j % m, tolerance, &convexity, &areas) {
+                Some(join) => join,
+                None => break,
+            };
+            if lengths[j] > lengths[i] + 1
+                || (lengths[j] == lengths[i] + 1 && penalties[j] > penalties[i] + join.penalty)
+            {
+                previous[j] = i;
+                penalties[j] = penalties[i] + join.penalty;
+                lengths[j] = lengths[i] + 1;
+                joined[j] = join;
+            }
+        }
+    }
+
+    let count = lengths[m];
+    let mut optimized = Curve {
+        tags: vec![Tag::Corner; count],
+        c: vec![[(0.0, 0.0); 3]; count],
+        vertices: vec![(0.0, 0.0); count],
+        alphas: vec![0.0; count],
+    };
+    let mut j = m;
+    for i in (0..count).rev() {
+        let jm = j % m;
+        if previous[j] == j - 1 {
+            optimized.tags[i] = curve.tags[jm];
+            optimized.c[i] = curve.c[jm];
+            optimized.vertices[i] = curve.vertices[jm];
+            optimized.alphas[i] = curve.alphas[jm];
+        } else {
+            let join = joined[j];
+            optimized.tags[i] = Tag::Curve;
+            optimized.c[i] = [join.c[0], join.c[1], curve.c[jm][2]];
+            optimized.vertices[i] = lerp(curve.c[jm][2], vertices[jm], join.s);
+            optimized.alphas[i] = join.alpha;
+        }
+        j = previous[j];
+    }
+    optimized
+}
+
+// The single curve replacing the segments after `i` up to and including
+// `j`, if there is one close enough to them.
+fn join(
+    curve: &Curve,
+    i: usize,
+    j: usize,
+    tolerance: f64,
+    convexity: &[i32],
+    areas: &[f64],
+) -> Option<Joined> {
+    let m = curve.tags.len();
+    let vertices = &curve.vertices;
+    let c = &curve.c;
+    if i == j {
+        return None;
+    }
+
+    let i1 = (i + 1) % m;
+    let convex = convexity[i1];
+    if convex == 0 {
+        return None;
+    }
+    let d = distance(vertices[i], vertices[i1]);
+    let mut k = i1;
+    while k != j {
+        let k1 = (k + 1) % m;
+        let k2 = (k + 2) % m;
+        if convexity[k1] != convex
+            || sign(cross_product(
+                vertices[i],
+                vertices[i1],
+                vertices[k1],
+                vertices[k2],
+            )) != convex
+            || dot_product(vertices[i], vertices[i1], vertices[k1], vertices[k2])
+                < d * distance(vertices[k1], vertices[k2]) * COS_179
+        {
+            return None;
+        }
+        k = k1;
+    }
+
+    let p0 = c[i][2];
+    let p1 = vertices[i1];
+    let p2 = vertices[j];
+    let p3 = c[j][2];
+
+    let mut enclosed = areas[j] - areas[i] - area(vertices[0], c[i][2], c[j][2]) / 2.0;
+    if i >= j {
+        enclosed += areas[m];
+    }
+
+    let a1 = area(p0, p1, p2);
+    let a2 = area(p0, p1, p3);
+    let a3 = area(p0, p2, p3);
+    let a4 = a1 + a3 - a2;
+    if a2 == a1 {
+        return None;
+    }
+    let t = a3 / (a3 - a4);
+    let s = a2 / (a2 - a1);
+    let a = a2 * t / 2.0;
+    if a == 0.0 {
+        return None;
+    }
+    let alpha = 2.0 - (4.0 - enclosed / a / 0.3).sqrt();
+    let controls = [lerp(p0, p1, t * alpha), lerp(p3, p2, s * alpha)];
+    let cubic = [p0, controls[0], controls[1], p3];
+
+    let mut penalty = 0.0;
+    let mut k = i1;
+    while k != j {
+        let k1 = (k + 1) % m;
+        let point = crossing(&cubic, vertices[k], vertices[k1])?;
+        let d = distance(vertices[k], vertices[k1]);
+        if d == 0.0 {
+            return None;
+        }
+        let d1 = area(vertices[k], vertices[k1], point) / d;
+        if d1.abs() > tolerance
+            || inner_product(vertices[k], vertices[k1], point) < 0.0
+            || inner_product(vertices[k1], vertices[k], point) < 0.0
+        {
+            return None;
+        }
+        penalty += d1 * d1;
+        k = k1;
+    }
+
+    let mut k = i;
+    while k != j {
+        let k1 = (k + 1) % m;
+        let point = crossing(&cubic, c[k][2], c[k1][2])?;
+        let d = distance(c[k][2], c[k1][2]);
+        if d == 0.0 {
+            return None;
+        }
+        let mut d1 = area(c[k][2], c[k1][2], point) / d;
+        let mut d2 = area(c[k][2], c[k1][2], vertices[k1]) / d * 0.75 * curve.alphas[k1];
+        if d2 < 0.0 {
+            d1 = -d1;
+            d2 = -d2;
+        }
+        if d1 < d2 - tolerance {
+            return None;
+        }
+        if d1 < d2 {
+            penalty += (d1 - d2) * (d1 - d2);
+        }
+        k = k1;
+    }
+
+    Some(Joined {
+        penalty,
+        c: controls,
+        alpha,
+        s,
+    })
+}
+
+// The point of the cubic curve where it runs parallel to the line from `q0`
+// to `q1`, if there is one.
+fn crossing(cubic: &[Vertex; 4], q0: Vertex, q1: Vertex) -> Option<Vertex> {
+    let [p0, p1, p2, p3] = *cubic;
+    let a = cross_product(p0, p1, q0, q1);
+    let b = cross_product(p1, p2, q0, q1);
+    let c = cross_product(p2, p3, q0, q1);
+
+    let qa = a - 2.0 * b + c;
+    let qb = -2.0 * a + 2.0 * b;
+    let discriminant = qb * qb - 4.0 * qa * a;
+    if qa == 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let t = [(-qb + root) / (2.0 * qa), (-qb - root) / (2.0 * qa)]
+        .iter()
+        .copied()
+        .find(|t| (0.0..=1.0).contains(t))?;
+
+    let u = 1.0 - t;
+    Some((
+        u * u * u * p0.0 + 3.0 * u * u * t * p1.0 + 3.0 * u * t * t * p2.0 + t * t * t * p3.0,
+        u * u * u * p0.1 + 3.0 * u * u * t * p1.1 + 3.0 * u * t * t * p2.1 + t * t * t * p3.1,
+    ))
+}
+
+fn modulo(a: i64, n: i64) -> i64 {
+    a.rem_euclid(n)
+}
+
+// whether `b` falls in the cyclic range from `a` up to, but not including,
+// `c`
+fn cyclic(a: i64, b: i64, c: i64) -> bool {
+    if a <= c {
+        a <= b && b < c
+    } else {
+        a <= b || b < c
+    }
+}
+
+fn sub((x0, y0): (i64, i64), (x1, y1): (i64, i64)) -> (i64, i64) {
+    (x0 - x1, y0 - y1)
+}
+
+fn cross((x0, y0): (i64, i64), (x1, y1): (i64, i64)) -> i64 {
+    x0 * y1 - x1 * y0
+}
+
+// the index, from 0 to 3, of the direction of a unit step
+fn direction((dx, dy): (i64, i64)) -> usize {
+    ((3 + 3 * dx + dy) / 2) as usize
+}
+
+fn sign(value: f64) -> i32 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+fn lerp((x0, y0): Vertex, (x1, y1): Vertex, t: f64) -> Vertex {
+    (x0 + t * (x1 - x0), y0 + t * (y1 - y0))
+}
+
+fn distance((x0, y0): Vertex, (x1, y1): Vertex) -> f64 {
+    ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt()
+}
+
+// twice the signed area of the triangle `p0`, `p1`, `p2`
+fn area(p0: Vertex, p1: Vertex, p2: Vertex) -> f64 {
+    (p1.0 - p0.0) * (p2.1 - p0.1) - (p2.0 - p0.0) * (p1.1 - p0.1)
+}
+
+// the area of the parallelogram spanned by the diagonal from `p0` to `p2`
+// and the unit step at right angles to it, as far as the grid allows
+fn diagonal_denominator(p0: Vertex, p2: Vertex) -> f64 {
+    let r = (-sign(p2.1 - p0.1) as f64, sign(p2.0 - p0.0) as f64);
+    r.1 * (p2.0 - p0.0) - r.0 * (p2.1 - p0.1)
+}
+
+fn cross_product(p0: Vertex, p1: Vertex, p2: Vertex, p3: Vertex) -> f64 {
+    (p1.0 - p0.0) * (p3.1 - p2.1) - (p3.0 - p2.0) * (p1.1 - p0.1)
+}
+
+fn dot_product(p0: Vertex, p1: Vertex, p2: Vertex, p3: Vertex) -> f64 {
+    (p1.0 - p0.0) * (p3.0 - p2.0) + (p1.1 - p0.1) * (p3.1 - p2.1)
+}
+
+fn inner_product(p0: Vertex, p1: Vertex, p2: Vertex) -> f64 {
+    (p1.0 - p0.0) * (p2.0 - p0.0) + (p1.1 - p0.1) * (p2.1 - p0.1)
+}
+
+fn quadratic_form(q: &[[f64; 3]; 3], (x, y): Vertex) -> f64 {
+    let v = [x, y, 1.0];
+    let mut sum = 0.0;
+    for l in 0..3 {
+        for k in 0..3 {
+            sum += v[l] * q[l][k] * v[k];
+        }
+    }
+    sum
+}
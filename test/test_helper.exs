@@ -1,8 +1,15 @@
 config =
   case :os.type() do
     {_, :darwin} -> [exclude: [macos: false]]
-    _ -> []
+    _ -> [exclude: []]
   end
 
+features = Keyword.get(Application.get_env(:xairo, Xairo.Native, []), :features, [])
+
+config =
+  if "pango" in features,
+    do: config,
+    else: Keyword.update!(config, :exclude, &[{:pango, true} | &1])
+
 ExUnit.configure(config)
 ExUnit.start()
@@ -3,7 +3,7 @@ defmodule Xairo.Api.TextTest do
 
   import Xairo.Test.Support.ImageHelpers
 
-  alias Xairo.{FontFace, Image, Point, Rgba}
+  alias Xairo.{FontFace, Image, Path, Point, Rgba, Vector}
   import Xairo
 
   describe "show_text/2" do
@@ -31,6 +31,195 @@ defmodule Xairo.Api.TextTest do
     end
   end
 
+  describe "show_text/3" do
+    @tag macos: false
+    test "applies the font's kerning unless disabled" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> set_font_face(FontFace.toy_create("DejaVu Sans", :normal, :normal))
+        |> set_font_size(100)
+
+      unkerned = text_extents(image, "AV")
+
+      assert text_extents(image, "AV", kerning: false).x_advance == unkerned.x_advance
+      assert text_extents(image, "AV", word_spacing: 0).x_advance < unkerned.x_advance
+      assert text_extents(image, "AV", kerning: true).x_advance < unkerned.x_advance
+    end
+
+    test "adds letter and word spacing to the text's advance" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> set_font_size(20)
+
+      %{x_advance: advance} = text_extents(image, "hi there")
+
+      # spacing is added between the 8 characters, and after the 1 space
+      assert text_extents(image, "hi there", kerning: false, letter_spacing: 2).x_advance ==
+               advance + 7 * 2
+
+      assert text_extents(image, "hi there", kerning: false, word_spacing: 5).x_advance ==
+               advance + 5
+    end
+
+    test "lays vertical text out from top to bottom" do
+      extents =
+        Image.new("test.png", 100, 100)
+        |> set_font_size(20)
+        |> text_extents("hello", vertical: true)
+
+      assert extents.x_advance == 0
+      assert extents.y_advance > 0
+      assert extents.height > extents.width
+    end
+
+    test "moves the current point to the end of the text" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> set_source(Rgba.new(0, 0, 0))
+        |> set_font_size(20)
+        |> move_to({10, 10})
+
+      %{x_advance: dx, y_advance: dy} = text_extents(image, "hello", letter_spacing: 1)
+
+      image = show_text(image, "hello", letter_spacing: 1)
+
+      %Point{x: x, y: y} = current_point(image)
+
+      assert_in_delta x, 10 + dx, 0.01
+      assert_in_delta y, 10 + dy, 0.01
+    end
+  end
+
+  describe "text_path/3" do
+    test "adds the outline of the spaced text to the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to({10, 50})
+        |> set_font_size(20)
+
+      spaced =
+        image
+        |> text_path("hello", letter_spacing: 5)
+        |> copy_path()
+
+      unspaced =
+        image
+        |> new_path()
+        |> move_to({10, 50})
+        |> text_path("hello")
+        |> copy_path()
+
+      assert Enum.count(spaced) == Enum.count(unspaced)
+      assert spaced.segments != unspaced.segments
+    end
+  end
+
+  describe "glyph_paths/2" do
+    test "returns the outline of each glyph in the text" do
+      glyphs =
+        Image.new("test.png", 100, 100)
+        |> move_to({10, 50})
+        |> set_font_size(20)
+        |> glyph_paths("hé!")
+
+      assert [
+               {"h", _, %Point{x: 10.0, y: 50.0}, %Vector{} = advance, %Path{} = path},
+               {"é", _, %Point{} = origin, _, _},
+               {"!", _, _, _, _}
+             ] = glyphs
+
+      assert origin.x == 10.0 + advance.x
+      assert Enum.count(path) > 1
+    end
+
+    test "recreates the path from text_path/2 when the outlines are appended" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to({10, 50})
+
+      glyphs = glyph_paths(image, "hello")
+
+      expected =
+        image
+        |> text_path("hello")
+        |> copy_path()
+
+      actual =
+        Enum.reduce(glyphs, new_path(image), fn {_, _, _, _, path}, image ->
+          append_path(image, path)
+        end)
+        |> copy_path()
+
+      # text_path/2 also advances the current point past the end of the text
+      assert Enum.drop(actual.segments, -1) == Enum.drop(expected.segments, -1)
+    end
+  end
+
+  describe "show_text_along_path/5" do
+    test "renders the text without changing the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> arc({50, 50}, 30, :math.pi(), 2 * :math.pi())
+
+      path = copy_path(image)
+
+      image =
+        image
+        |> new_path()
+        |> move_to({5, 5})
+        |> set_source(Rgba.new(0, 0, 0))
+        |> show_text_along_path(path, "hello", 0, :center)
+
+      assert current_point(image) == Point.new(5, 5)
+      assert Enum.count(copy_path(image)) == 1
+    end
+  end
+
+  describe "text_path_along_path/5" do
+    test "adds the outlines of the glyphs along the path to the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to({10, 80})
+        |> line_to({90, 20})
+
+      path = copy_path(image)
+
+      {%Point{x: x1, y: y1}, %Point{x: x2, y: y2}} =
+        image
+        |> new_path()
+        |> set_font_size(20)
+        |> text_path_along_path(path, "hi")
+        |> path_extents()
+
+      assert x1 >= 0 and y1 >= 50
+      assert x2 <= 50 and y2 <= 90
+    end
+
+    test "drops glyphs that fall past the end of the path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to({10, 50})
+        |> line_to({20, 50})
+
+      path = copy_path(image)
+
+      short =
+        image
+        |> new_path()
+        |> text_path_along_path(path, "hello world")
+        |> copy_path()
+
+      long =
+        image
+        |> new_path()
+        |> move_to({10, 50})
+        |> text_path("hello world")
+        |> copy_path()
+
+      assert Enum.count(short) < Enum.count(long)
+    end
+  end
+
   describe "set_font_face/2" do
     test "sets the font face for the image" do
       ff = FontFace.toy_create("serif", :normal, :bold)
@@ -0,0 +1,82 @@
+defmodule Xairo.ContourTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Contour, Image, ImageSurface, Path, Point, Rgba}
+
+  doctest Contour
+
+  @peak [[0, 0, 0], [0, 1, 0], [0, 0, 0]]
+
+  describe "isolines/3" do
+    test "returns a path for each level" do
+      paths = Contour.isolines([[0, 1, 2], [0, 1, 2]], [0.5, 1.5, 3])
+
+      assert Enum.map(paths, &Path.to_svg/1) == ["M0.5 1L0.5 0", "M1.5 1L1.5 0", ""]
+    end
+
+    test "joins or separates saddles by the average of their corners" do
+      paths = Contour.isolines([[1, 0], [0, 1]], [0.4, 0.6])
+
+      assert Enum.map(paths, &Path.to_svg/1) == [
+               "M0.602 0L1 0.398M0.398 1L0 0.602",
+               "M0.398 0L0 0.398M0.602 1L1 0.602"
+             ]
+    end
+
+    test "smooths the lines into curves if asked" do
+      [path] = Contour.isolines(@peak, [0.5], smooth: true)
+
+      assert Path.to_svg(path) ==
+               "M0.5 1C0.5 0.832 0.832 0.5 1 0.5 1.168 0.5 1.5 0.832 1.5 1" <>
+                 " 1.5 1.168 1.168 1.5 1 1.5 0.832 1.5 0.5 1.168 0.5 1Z"
+    end
+
+    test "traces a channel of an image surface through the pixel centres" do
+      image =
+        Image.new("contour.png", 10, 10)
+        |> Xairo.set_source(Rgba.new(1, 0, 0))
+        |> Xairo.rectangle(Point.new(3, 3), 4, 4)
+        |> Xairo.fill()
+
+      [red] = Contour.isolines(image.surface, [0.5], channel: :red)
+      [green] = Contour.isolines(image.surface, [0.5], channel: :green)
+
+      assert Path.to_svg(red) == "M3 3.5L3.5 3 6.5 3 7 3.5 7 6.5 6.5 7 3.5 7 3 6.5Z"
+      assert Path.to_svg(green) == ""
+    end
+
+    test "returns an error for grids with rows of different lengths" do
+      assert Contour.isolines([[0, 1], [0]], [0.5]) == {:error, :invalid_size}
+    end
+
+    test "returns empty paths for an empty surface" do
+      surface = ImageSurface.create(:a8, 0, 0)
+
+      assert [path] = Contour.isolines(surface, [0.5])
+      assert Path.to_svg(path) == ""
+      assert [band] = Contour.isobands(surface, [0.25, 0.75])
+      assert Path.to_svg(band) == ""
+    end
+
+    test "returns an error for surfaces it cannot read" do
+      surface = ImageSurface.create(:rgb16_565, 10, 10)
+
+      assert Contour.isolines(surface, [0.5]) == {:error, :invalid_format}
+    end
+  end
+
+  describe "isobands/3" do
+    test "returns the band between each pair of levels, with holes reversed" do
+      [path] = Contour.isobands(@peak, [0.25, 0.75])
+
+      assert Path.to_svg(path) ==
+               "M0.25 1L1 0.25 1.75 1 1 1.75ZM1 1.25L1.25 1 1 0.75 0.75 1Z"
+    end
+
+    test "closes bands along the edges of the grid" do
+      [path] = Contour.isobands([[1, 1], [1, 1]], [0.5, 2])
+
+      assert Path.to_svg(path) == "M0 0L1 0 1 1 0 1Z"
+    end
+  end
+end
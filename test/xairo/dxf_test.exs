@@ -0,0 +1,85 @@
+defmodule Xairo.DxfTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Dxf, Path, Rgba}
+
+  # the group code and value pairs of a DXF file
+  defp pairs(dxf) do
+    dxf
+    |> String.split("\n")
+    |> Enum.chunk_every(2, 2, :discard)
+    |> Enum.map(fn [code, value] -> {String.trim(code), value} end)
+  end
+
+  describe "encode/2" do
+    test "writes an R12 file with a polyline for each subpath" do
+      square = Path.from_svg("M0 0H10V10H0Z")
+      dxf = Dxf.encode([{"cut", square}], version: :r12)
+      pairs = pairs(dxf)
+
+      assert {"1", "AC1009"} in pairs
+      assert Enum.count(pairs, &(&1 == {"0", "POLYLINE"})) == 1
+      assert Enum.count(pairs, &(&1 == {"0", "VERTEX"})) == 4
+      assert String.contains?(dxf, "  0\nPOLYLINE\n  8\ncut\n 66\n1\n")
+      assert String.contains?(dxf, "  0\nVERTEX\n  8\ncut\n 10\n10\n 20\n-10\n 30\n0\n")
+      assert String.ends_with?(dxf, "  0\nEOF\n")
+    end
+
+    test "defines each layer with its nearest color index" do
+      path = Path.from_svg("M0 0H10")
+      layers = [{"cut", path, Rgba.new(0.9, 0.1, 0)}, {"engrave", path, Rgba.new(0, 0, 0.8)}]
+
+      dxf = Dxf.encode(layers, version: :r12)
+      assert String.contains?(dxf, "  2\n0\n 70\n0\n 62\n7\n")
+      assert String.contains?(dxf, "  2\ncut\n 70\n0\n 62\n1\n")
+      assert String.contains?(dxf, "  2\nengrave\n 70\n0\n 62\n5\n")
+
+      assert {"420", "15079936"} in pairs(Dxf.encode(layers))
+    end
+
+    test "returns an error for an invalid layer name" do
+      path = Path.from_svg("M0 0H10")
+
+      assert Dxf.encode([{"", path}]) == {:error, :invalid_layer_name}
+      assert Dxf.encode([{"cut\n  0\nLINE", path}]) == {:error, :invalid_layer_name}
+      assert Dxf.encode([{"cut/engrave", path}]) == {:error, :invalid_layer_name}
+    end
+
+    test "writes runs of curves as splines in R2000 files" do
+      path = Path.from_svg("M0 0C0 10 10 10 10 0C10-10 20-10 20 0H30")
+      pairs = path |> Dxf.encode() |> pairs()
+
+      assert {"1", "AC1015"} in pairs
+      assert {"73", "7"} in pairs
+      spline = Enum.drop_while(pairs, &(&1 != {"0", "SPLINE"}))
+      assert for({"40", knot} <- spline, do: knot) == ~w(0 0 0 0 1 1 1 2 2 2 2)
+      assert {"0", "LWPOLYLINE"} in pairs
+      assert {"90", "2"} in pairs
+    end
+
+    test "closes a closed subpath of curves, and flattens one that mixes in lines" do
+      circle = Path.from_svg("M0 5A5 5 0 0 1 10 5A5 5 0 0 1 0 5Z")
+      pairs = circle |> Dxf.encode() |> pairs()
+
+      assert Enum.count(pairs, &(&1 == {"0", "SPLINE"})) == 1
+      assert {"70", "9"} in pairs
+      refute {"0", "LWPOLYLINE"} in pairs
+
+      half = Path.from_svg("M0 5A5 5 0 0 1 10 5Z")
+      pairs = half |> Dxf.encode() |> pairs()
+
+      refute {"0", "SPLINE"} in pairs
+      assert Enum.count(pairs, &(&1 == {"0", "LWPOLYLINE"})) == 1
+      polyline = Enum.drop_while(pairs, &(&1 != {"0", "LWPOLYLINE"}))
+      assert {"70", "1"} in polyline
+    end
+
+    test "flattens curves if asked" do
+      path = Path.from_svg("M0 0C0 10 10 10 10 0")
+      pairs = path |> Dxf.encode(splines: false) |> pairs()
+
+      refute {"0", "SPLINE"} in pairs
+      assert {"0", "LWPOLYLINE"} in pairs
+    end
+  end
+end
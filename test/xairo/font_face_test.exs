@@ -25,6 +25,52 @@ defmodule Xairo.FontFaceTest do
     end
   end
 
+  describe "toy_create/4" do
+    @describetag macos: false
+
+    test "returns a FontFace struct for an installed font in strict mode" do
+      face = FontFace.toy_create("DejaVu Sans", :normal, :bold, strict: true)
+
+      assert is_struct(face, FontFace)
+    end
+
+    test "returns an error for a font that is not installed in strict mode" do
+      assert FontFace.toy_create("DejaVu Snas", :normal, :normal, strict: true) ==
+               {:error, :font_not_found}
+    end
+
+    test "falls back to a default font outside of strict mode" do
+      face = FontFace.toy_create("DejaVu Snas", :normal, :normal)
+
+      assert FontFace.toy_get_family(face) == "DejaVu Snas"
+    end
+  end
+
+  describe "families/0" do
+    @describetag macos: false
+
+    test "returns the sorted names of the installed font families" do
+      families = FontFace.families()
+
+      assert "DejaVu Sans" in families
+      assert families == Enum.sort(families)
+    end
+  end
+
+  describe "styles/1" do
+    @describetag macos: false
+
+    test "returns the styles installed for a font family" do
+      styles = FontFace.styles("DejaVu Sans")
+
+      assert "Bold" in styles
+    end
+
+    test "returns an empty list for a family that is not installed" do
+      assert FontFace.styles("DejaVu Snas") == []
+    end
+  end
+
   describe "toy_get_family" do
     test "returns the font's family" do
       face = FontFace.toy_create("serif", :italic, :normal)
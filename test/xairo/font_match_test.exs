@@ -0,0 +1,45 @@
+defmodule Xairo.FontMatchTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.FontMatch
+
+  describe "for/4" do
+    @describetag macos: false
+
+    test "returns the installed font matching the given values" do
+      font_match = FontMatch.for("DejaVu Sans", :normal, :bold)
+
+      assert is_struct(font_match, FontMatch)
+      assert font_match.family == "DejaVu Sans"
+      assert font_match.style == "Bold"
+      assert font_match.slant == :normal
+      assert font_match.weight == :bold
+      assert Path.basename(font_match.file) == "DejaVuSans-Bold.ttf"
+      assert font_match.index == 0
+    end
+
+    test "returns the fallback font for a family that is not installed" do
+      font_match = FontMatch.for("DejaVu Snas", :normal, :normal)
+
+      assert is_struct(font_match, FontMatch)
+      refute font_match.family == "DejaVu Snas"
+    end
+
+    test "returns an error for a family that is not installed in strict mode" do
+      assert FontMatch.for("DejaVu Snas", :normal, :normal, strict: true) ==
+               {:error, :font_not_found}
+    end
+
+    test "treats italic and oblique faces as equivalent in strict mode" do
+      font_match = FontMatch.for("DejaVu Sans", :italic, :normal, strict: true)
+
+      assert font_match.slant in [:italic, :oblique]
+    end
+
+    test "ignores case when comparing family names in strict mode" do
+      font_match = FontMatch.for("dejavu sans", :normal, :normal, strict: true)
+
+      assert font_match.family == "DejaVu Sans"
+    end
+  end
+end
@@ -2,7 +2,7 @@ defmodule Xairo.ImageSurfaceTest do
   use ExUnit.Case, async: true
   import Xairo.Test.Support.ImageHelpers
 
-  alias Xairo.ImageSurface
+  alias Xairo.{Image, ImageSurface, Path, Point, Rgba}
 
   doctest ImageSurface
 
@@ -75,4 +75,64 @@ defmodule Xairo.ImageSurfaceTest do
       assert ImageSurface.format(surface) == :argb32
     end
   end
+
+  describe "trace/2" do
+    setup do
+      image =
+        Image.new("trace.png", 60, 40)
+        |> Xairo.set_source(Rgba.new(1, 1, 1))
+        |> Xairo.paint()
+        |> Xairo.set_source(Rgba.new(0, 0, 0))
+        |> Xairo.set_fill_rule(:even_odd)
+        |> Xairo.rectangle(Point.new(4, 4), 20, 20)
+        |> Xairo.rectangle(Point.new(10, 10), 8, 8)
+        |> Xairo.fill()
+        |> Xairo.rectangle(Point.new(2, 36), 1, 1)
+        |> Xairo.fill()
+        |> Xairo.arc(Point.new(42, 20), 12, 0, 2 * :math.pi())
+        |> Xairo.fill()
+
+      {:ok, surface: image.surface}
+    end
+
+    test "returns each shape with its holes as a path", %{surface: surface} do
+      [square, circle] = ImageSurface.trace(surface)
+
+      assert Path.to_svg(square) == "M4 14L4 24 24 24 24 4 4 4ZM18 14L18 18 10 18 10 10 18 10Z"
+      assert Enum.any?(circle.segments, &match?({:curve_to, _, _, _}, &1))
+    end
+
+    test "keeps small specks if asked", %{surface: surface} do
+      assert surface |> ImageSurface.trace(turd_size: 0) |> length() == 3
+    end
+
+    test "keeps every corner with a corner threshold of 0", %{surface: surface} do
+      [_square, circle] = ImageSurface.trace(surface, corner_threshold: 0)
+
+      refute Enum.any?(circle.segments, &match?({:curve_to, _, _, _}, &1))
+    end
+
+    test "traces alpha-only surfaces" do
+      image =
+        Image.new("trace.png", 20, 20, format: :a8)
+        |> Xairo.set_source(Rgba.new(0, 0, 0))
+        |> Xairo.rectangle(Point.new(5, 5), 10, 10)
+        |> Xairo.fill()
+
+      [square] = ImageSurface.trace(image.surface)
+
+      assert Path.to_svg(square) == "M5 10L5 15 15 15 15 5 5 5Z"
+    end
+
+    test "returns no paths for an empty surface" do
+      assert ImageSurface.create(:argb32, 0, 0) |> ImageSurface.trace() == []
+      assert ImageSurface.create(:a8, 10, 0) |> ImageSurface.trace() == []
+    end
+
+    test "returns an error for surfaces it cannot threshold" do
+      surface = ImageSurface.create(:rgb16_565, 10, 10)
+
+      assert ImageSurface.trace(surface) == {:error, :invalid_format}
+    end
+  end
 end
@@ -0,0 +1,122 @@
+defmodule Xairo.LayoutTest do
+  use ExUnit.Case, async: true
+
+  @moduletag pango: true
+
+  alias Xairo.{Image, Layout, Point, Rgba}
+
+  import Xairo
+
+  describe "new/1" do
+    test "returns a layout struct" do
+      layout =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+
+      assert is_struct(layout, Layout)
+    end
+  end
+
+  describe "set_markup/2" do
+    test "returns the layout for valid markup" do
+      layout =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+
+      assert Layout.set_markup(layout, "<b>bold</b> and <i>italic</i>") == layout
+    end
+
+    test "returns an error for invalid markup" do
+      layout =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+
+      assert Layout.set_markup(layout, "<b>unclosed") == {:error, :invalid_markup}
+    end
+  end
+
+  describe "extents/1" do
+    test "returns the ink and logical extents of the layout" do
+      {{ink_origin, ink_width, ink_height}, {logical_origin, logical_width, logical_height}} =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+        |> Layout.set_font_description("Sans 12")
+        |> Layout.set_text("hello")
+        |> Layout.extents()
+
+      assert %Point{} = ink_origin
+      assert %Point{x: 0.0, y: 0.0} = logical_origin
+
+      assert ink_width > 0
+      assert ink_height > 0
+      assert logical_width >= ink_width
+      assert logical_height >= ink_height
+    end
+
+    test "wraps lines to the layout's width" do
+      layout =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+        |> Layout.set_font_description("Sans 12")
+        |> Layout.set_text("the quick brown fox jumps over the lazy dog")
+
+      {_, {_, unwrapped_width, unwrapped_height}} = Layout.extents(layout)
+
+      {_, {_, wrapped_width, wrapped_height}} =
+        layout
+        |> Layout.set_width(50)
+        |> Layout.set_wrap(:word_char)
+        |> Layout.extents()
+
+      assert wrapped_width <= 50
+      assert wrapped_width < unwrapped_width
+      assert wrapped_height > unwrapped_height
+    end
+
+    test "shapes right-to-left text" do
+      {_, {_, width, _}} =
+        Image.new("test.png", 100, 100)
+        |> Layout.new()
+        |> Layout.set_text("مرحبا بالعالم")
+        |> Layout.extents()
+
+      assert width > 0
+    end
+  end
+
+  describe "show_layout/2" do
+    test "renders the layout to the image" do
+      image = Image.new("test.png", 100, 100)
+
+      layout =
+        image
+        |> Layout.new()
+        |> Layout.set_text("hello")
+
+      assert image
+             |> set_source(Rgba.new(0, 0, 0))
+             |> move_to(Point.new(10, 10))
+             |> show_layout(layout) == image
+    end
+  end
+
+  describe "layout_path/2" do
+    test "adds the outlines of the layout's text to the current path" do
+      image = Image.new("test.png", 100, 100)
+
+      layout =
+        image
+        |> Layout.new()
+        |> Layout.set_alignment(:center)
+        |> Layout.set_text("hello")
+
+      path =
+        image
+        |> move_to(Point.new(10, 10))
+        |> layout_path(layout)
+        |> copy_path()
+
+      assert Enum.count(path) > 1
+    end
+  end
+end
@@ -1,10 +1,12 @@
 defmodule Xairo.PathTest do
   use ExUnit.Case, async: true
 
-  alias Xairo.{Image, Point, Vector}
+  alias Xairo.{Image, Matrix, Path, Point, Vector}
 
   import Xairo
 
+  doctest Path
+
   describe "Enum.empty?/1" do
     test "returns true for an empty path" do
       image = Image.new("test.png", 100, 100)
@@ -94,4 +96,762 @@ defmodule Xairo.PathTest do
              )
     end
   end
+
+  describe "new/1" do
+    test "builds a path from a list of segments" do
+      path =
+        Path.new([
+          {:move_to, {10, 10}},
+          {:line_to, Point.new(50, 50)},
+          {:curve_to, {70, 70}, {80, 90}, {65, 95}},
+          :close_path
+        ])
+
+      assert path.segments == [
+               {:move_to, Point.new(10, 10)},
+               {:line_to, Point.new(50, 50)},
+               {:curve_to, Point.new(70, 70), Point.new(80, 90), Point.new(65, 95)},
+               :close_path,
+               {:move_to, Point.new(10, 10)}
+             ]
+    end
+
+    test "builds the same path as drawing the segments on an image" do
+      drawn =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(10, 10))
+        |> line_to(Point.new(50, 50))
+        |> rel_curve_to(Vector.new(20, 20), Vector.new(30, 40), Vector.new(15, 45))
+        |> close_path()
+        |> copy_path()
+
+      assert Path.equal?(Path.new(drawn.segments), drawn)
+    end
+
+    test "can be appended to an image" do
+      path = Path.new([{:move_to, {10, 10}}, {:line_to, {50, 50}}])
+
+      copied =
+        Image.new("test.png", 100, 100)
+        |> append_path(path)
+        |> copy_path()
+
+      assert copied.segments == path.segments
+    end
+
+    test "builds an empty path from an empty list" do
+      assert Enum.empty?(Path.new([]))
+    end
+  end
+
+  describe "equal?/2" do
+    test "returns false for paths with different segments" do
+      path1 = Path.new([{:move_to, {10, 10}}, {:line_to, {50, 50}}])
+      path2 = Path.new([{:move_to, {10, 10}}, {:line_to, {50, 60}}])
+
+      refute Path.equal?(path1, path2)
+    end
+  end
+
+  describe "segment_count/1" do
+    test "returns the number of segments in the path" do
+      path = Path.new([{:move_to, {10, 10}}, {:line_to, {50, 50}}, :close_path])
+
+      assert Path.segment_count(path) == 4
+    end
+  end
+
+  describe "from_svg/1" do
+    test "resolves relative commands to absolute coordinates" do
+      assert Path.from_svg("m10 10 l40 0 0 40 h-40 V20").segments ==
+               Path.new([
+                 {:move_to, {10, 10}},
+                 {:line_to, {50, 10}},
+                 {:line_to, {50, 50}},
+                 {:line_to, {10, 50}},
+                 {:line_to, {10, 20}}
+               ]).segments
+    end
+
+    test "reflects the previous control point for smooth curves" do
+      path = Path.from_svg("M0 0C0 10 10 20 20 20S40 30 40 40")
+
+      assert Enum.at(path, 2) ==
+               {:curve_to, Point.new(30, 20), Point.new(40, 30), Point.new(40, 40)}
+    end
+
+    test "converts quadratic curves to cubic curves" do
+      path = Path.from_svg("M0 0Q30 30 60 0T120 0")
+
+      assert [
+               {:move_to, _},
+               {:curve_to, %Point{x: 20.0, y: 20.0}, %Point{x: 40.0, y: 20.0}, %Point{x: 60.0}},
+               {:curve_to, %Point{x: 80.0, y: -20.0}, %Point{x: 100.0, y: -20.0},
+                %Point{x: 120.0}}
+             ] = path.segments
+    end
+
+    test "approximates arcs with a cubic curve per quarter turn" do
+      path = Path.from_svg("M0 50A50 50 0 0 1 100 50")
+
+      assert [{:move_to, _}, {:curve_to, _, _, top}, {:curve_to, _, _, end_point}] =
+               path.segments
+
+      assert_in_delta top.x, 50, 0.01
+      assert_in_delta top.y, 0, 0.01
+      assert end_point == Point.new(100, 50)
+    end
+
+    test "accepts numbers without separators" do
+      assert Path.from_svg("M.5.5l10-5").segments ==
+               Path.new([{:move_to, {0.5, 0.5}}, {:line_to, {10.5, -4.5}}]).segments
+    end
+
+    test "returns an error for invalid path data" do
+      assert Path.from_svg("L10 10") == {:error, :invalid_svg_path}
+      assert Path.from_svg("M10 10 L20") == {:error, :invalid_svg_path}
+      assert Path.from_svg("M1e999 0") == {:error, :invalid_svg_path}
+    end
+  end
+
+  describe "to_svg/2" do
+    test "round trips a path through SVG path data" do
+      path =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(10, 10))
+        |> line_to(Point.new(50, 50))
+        |> rel_curve_to(Vector.new(20, 20), Vector.new(30, 40), Vector.new(15, 45))
+        |> close_path()
+        |> copy_path()
+
+      svg = Path.to_svg(path)
+
+      assert svg == "M10 10L50 50C70 70 80 90 65 95Z"
+      assert Path.equal?(Path.from_svg(svg), path)
+    end
+  end
+
+  describe "transform/2" do
+    test "maps every point of the path through the matrix" do
+      path = Path.from_svg("M10 10L20 10C30 10 30 20 20 20Z")
+
+      matrix =
+        Matrix.identity()
+        |> Matrix.translate(100, 0)
+        |> Matrix.scale(2, 2)
+
+      assert Path.transform(path, matrix) |> Path.to_svg() ==
+               "M120 20L140 20C160 20 160 40 140 40Z"
+    end
+
+    test "does not need an image to draw on" do
+      path = Path.new([{:move_to, {10, 0}}])
+
+      assert [{:move_to, point}] = path |> Path.rotate(:math.pi() / 2) |> Enum.to_list()
+
+      assert_in_delta point.x, 0, 0.01
+      assert_in_delta point.y, 10, 0.01
+    end
+  end
+
+  describe "scale/3" do
+    test "scales the path relative to the origin" do
+      path = Path.from_svg("M10 10L20 -10")
+
+      assert path |> Path.scale(2, 0.5) |> Path.to_svg() == "M20 5L40-5"
+    end
+  end
+
+  describe "reverse/1" do
+    test "reverses curves and the order of subpaths" do
+      path = Path.from_svg("M0 0L10 0C20 0 20 10 10 10M50 50L60 60")
+
+      assert path |> Path.reverse() |> Path.to_svg() ==
+               "M60 60L50 50M10 10C20 10 20 0 10 0L0 0"
+    end
+
+    test "keeps closed subpaths closed and starting from the same point" do
+      path = Path.from_svg("M0 0L10 0L10 10Z")
+
+      assert path |> Path.reverse() |> Path.to_svg() == "M0 0L10 10 10 0Z"
+    end
+
+    test "reversing twice returns the original path" do
+      path = Path.from_svg("M0 0L10 0C20 0 20 10 10 10Z")
+
+      assert Path.equal?(path |> Path.reverse() |> Path.reverse(), path)
+    end
+  end
+
+  describe "total_length/2" do
+    test "measures curves to within the tolerance" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+
+      assert_in_delta Path.total_length(circle, 0.001), 2 * :math.pi() * 50, 0.1
+    end
+
+    test "does not count the jumps between subpaths" do
+      path = Path.from_svg("M0 0h10M100 100h20")
+
+      assert Path.total_length(path) == 30
+      assert Path.subpath_lengths(path) == [10, 20]
+    end
+  end
+
+  describe "point_at_length/3" do
+    test "returns the point and tangent angle along a curve" do
+      path = Path.from_svg("M0 50A50 50 0 0 1 100 50")
+
+      {point, angle} = Path.point_at_length(path, Path.total_length(path) / 2)
+
+      assert_in_delta point.x, 50, 0.01
+      assert_in_delta point.y, 0, 0.01
+      assert_in_delta angle, 0, 0.01
+    end
+
+    test "returns nil outside of the path" do
+      path = Path.from_svg("M0 0h10")
+
+      refute Path.point_at_length(path, -1)
+      refute Path.point_at_length(path, 11)
+    end
+  end
+
+  describe "slice/4" do
+    test "splits curves where the range starts and ends" do
+      path = Path.from_svg("M0 50A50 50 0 0 1 100 50")
+      half = Path.total_length(path) / 2
+
+      first = Path.slice(path, 0, half)
+      second = Path.slice(path, half, 2 * half)
+
+      assert_in_delta Path.total_length(first), half, 0.01
+      assert_in_delta Path.total_length(second), half, 0.01
+
+      assert [{:move_to, _}, {:curve_to, _, _, top}] = first.segments
+      assert [{:move_to, ^top} | _] = second.segments
+    end
+
+    test "keeps a closed subpath closed only when all of it is included" do
+      path = Path.from_svg("M0 0h10v10z")
+
+      assert path |> Path.slice(0, 100) |> Path.to_svg() == "M0 0L10 0 10 10Z"
+      assert path |> Path.slice(0, 20) |> Path.to_svg() == "M0 0L10 0 10 10"
+    end
+  end
+
+  describe "boolean operations" do
+    setup do
+      [
+        a: Path.from_svg("M0 0H10V10H0Z"),
+        b: Path.from_svg("M5 5H15V15H5Z")
+      ]
+    end
+
+    test "union/3 returns the outline of both paths", %{a: a, b: b} do
+      assert Path.union(a, b) |> Path.to_svg() ==
+               "M0 0L10 0 10 5 15 5 15 15 5 15 5 10 0 10Z"
+    end
+
+    test "intersection/3 returns the overlap of the paths", %{a: a, b: b} do
+      assert Path.intersection(a, b) |> Path.to_svg() == "M5 5L10 5 10 10 5 10Z"
+    end
+
+    test "difference/3 cuts the second path out of the first", %{a: a, b: b} do
+      assert Path.difference(a, b) |> Path.to_svg() == "M0 0L10 0 10 5 5 5 5 10 0 10Z"
+
+      hole = Path.from_svg("M3 3H7V7H3Z")
+
+      assert Path.difference(a, hole) |> Path.to_svg() ==
+               "M0 0L10 0 10 10 0 10ZM3 3L3 7 7 7 7 3Z"
+    end
+
+    test "xor/3 returns the areas covered by only one path", %{a: a, b: b} do
+      assert Path.xor(a, b) |> Path.to_svg() ==
+               "M0 0L10 0 10 5 5 5 5 10 0 10ZM10 5L15 5 15 15 5 15 5 10 10 10Z"
+    end
+
+    test "merges paths sharing an edge" do
+      a = Path.from_svg("M0 0H10V10H0Z")
+      b = Path.from_svg("M10 0H20V10H10Z")
+
+      assert Path.union(a, b) |> Path.to_svg() == "M0 0L20 0 20 10 0 10Z"
+    end
+
+    test "respects the fill rule for self-intersecting paths" do
+      star = Path.from_svg("M50 0L79 90L2 35H98L21 90Z")
+      empty = Path.new([])
+
+      # the pentagon in the middle of the star is filled under the winding
+      # rule, but not under the even-odd rule
+      winding = Path.union(star, empty, fill_rule: :winding)
+      even_odd = Path.union(star, empty, fill_rule: :even_odd)
+
+      assert length(Path.subpath_lengths(winding)) == 1
+      assert length(Path.subpath_lengths(even_odd)) == 5
+    end
+
+    test "flattens curves to within the tolerance" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+      square = Path.from_svg("M50 0H100V100H50Z")
+
+      half = Path.intersection(circle, square, tolerance: 0.01)
+
+      assert Enum.all?(half, &(&1 == :close_path or elem(&1, 0) in [:move_to, :line_to]))
+      assert_in_delta Path.total_length(half), :math.pi() * 50 + 100, 0.5
+    end
+  end
+
+  describe "stroke_to_path/2" do
+    setup do
+      [line: Path.from_svg("M0 0H10"), corner: Path.from_svg("M0 0H10V10")]
+    end
+
+    test "outlines a line with the given caps", %{line: line} do
+      assert line |> Path.stroke_to_path(line_width: 4) |> Path.to_svg() ==
+               "M0-2L10-2 10 2 0 2Z"
+
+      assert line |> Path.stroke_to_path(line_width: 4, line_cap: :square) |> Path.to_svg() ==
+               "M-2-2L12-2 12 2-2 2Z"
+    end
+
+    test "joins corners with the given line join", %{corner: corner} do
+      assert corner |> Path.stroke_to_path() |> Path.to_svg() ==
+               "M0-1L11-1 11 10 9 10 9 1 0 1Z"
+
+      assert corner |> Path.stroke_to_path(line_join: :bevel) |> Path.to_svg() ==
+               "M0-1L10-1 11 0 11 10 9 10 9 1 0 1Z"
+    end
+
+    test "splits the outline into dashes", %{line: line} do
+      assert line |> Path.stroke_to_path(dashes: [2, 2]) |> Path.to_svg() ==
+               "M0-1L2-1 2 1 0 1ZM4-1L6-1 6 1 4 1ZM8-1L10-1 10 1 8 1Z"
+
+      assert line |> Path.stroke_to_path(dashes: [2, 2], dash_offset: 1) |> Path.to_svg() ==
+               "M0-1L1-1 1 1 0 1ZM3-1L5-1 5 1 3 1ZM7-1L9-1 9 1 7 1Z"
+    end
+
+    test "outlines both sides of a closed path" do
+      square = Path.from_svg("M0 0H10V10H0Z")
+
+      assert square |> Path.stroke_to_path() |> Path.to_svg() ==
+               "M-1-1L11-1 11 11-1 11ZM1 1L1 9 9 9 9 1Z"
+    end
+
+    test "flattens curves and round joins to within the tolerance" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+      ring = Path.stroke_to_path(circle, line_width: 4, tolerance: 0.01)
+
+      assert_in_delta Path.total_length(ring), 2 * :math.pi() * (52 + 48), 0.5
+    end
+
+    test "returns an error for an invalid dash pattern", %{line: line} do
+      assert Path.stroke_to_path(line, dashes: [-1]) == {:error, :invalid_dash}
+      assert Path.stroke_to_path(line, dashes: [0, 0]) == {:error, :invalid_dash}
+      assert Path.stroke_to_path(line, dashes: [1.0e-9]) == {:error, :invalid_dash}
+    end
+
+    test "bounds the number of lines curves, joins and caps are split into with a tolerance of 0",
+         %{corner: corner} do
+      outline = Path.stroke_to_path(corner, line_join: :round, line_cap: :round, tolerance: 0)
+
+      assert is_struct(outline, Path)
+      assert Path.segment_count(outline) > 100
+
+      curve = Path.from_svg("M0 0C50-50 100 50 150 0")
+      assert curve |> Path.stroke_to_path(tolerance: 0) |> is_struct(Path)
+    end
+  end
+
+  describe "offset/3" do
+    setup do
+      [square: Path.from_svg("M0 0H10V10H0Z")]
+    end
+
+    test "grows the path with the given line join", %{square: square} do
+      assert square |> Path.offset(1) |> Path.to_svg() == "M-1-1L11-1 11 11-1 11Z"
+
+      assert square |> Path.offset(1, line_join: :bevel) |> Path.to_svg() ==
+               "M0-1L10-1 11 0 11 10 10 11 0 11-1 10-1 0Z"
+    end
+
+    test "shrinks the path for a negative distance", %{square: square} do
+      assert square |> Path.offset(-1) |> Path.to_svg() == "M1 1L9 1 9 9 1 9Z"
+
+      l_shape = Path.from_svg("M0 0H20V10H10V20H0Z")
+
+      assert l_shape |> Path.offset(-2) |> Path.to_svg() ==
+               "M2 2L18 2 18 8 8 8 8 18 2 18Z"
+    end
+
+    test "removes parts of the path that collapse", %{square: square} do
+      assert square |> Path.offset(-5) |> Path.to_svg() == ""
+
+      dumbbell = Path.from_svg("M0 0H10V4H20V0H30V10H20V6H10V10H0Z")
+
+      assert dumbbell |> Path.offset(-1.5) |> Path.to_svg() ==
+               "M1.5 1.5L8.5 1.5 8.5 8.5 1.5 8.5ZM21.5 1.5L28.5 1.5 28.5 8.5 21.5 8.5Z"
+    end
+
+    test "closes open subpaths" do
+      assert Path.from_svg("M0 0H10V10") |> Path.offset(1) |> Path.to_svg() ==
+               "M-2.414-1L11-1 11 12.414Z"
+    end
+
+    test "respects the fill rule for self-intersecting paths" do
+      star = Path.from_svg("M50 0L79 90L2 35H98L21 90Z")
+
+      assert length(Path.subpath_lengths(Path.offset(star, -2))) == 1
+      assert length(Path.subpath_lengths(Path.offset(star, -2, fill_rule: :even_odd))) == 5
+    end
+
+    test "offsets curves to within the tolerance" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+
+      assert_in_delta Path.total_length(Path.offset(circle, 5, tolerance: 0.01)),
+                      2 * :math.pi() * 55,
+                      0.5
+
+      assert_in_delta Path.total_length(Path.offset(circle, -5, tolerance: 0.01)),
+                      2 * :math.pi() * 45,
+                      0.5
+    end
+
+    test "flattens curves to a thousandth of the distance with a tolerance of 0" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+
+      assert_in_delta Path.total_length(Path.offset(circle, 5, tolerance: 0)),
+                      2 * :math.pi() * 55,
+                      0.05
+    end
+  end
+
+  describe "hatch/4" do
+    setup do
+      [square: Path.from_svg("M0 0H10V10H0Z")]
+    end
+
+    test "fills the path with parallel lines", %{square: square} do
+      assert square |> Path.hatch(:parallel, 4, angle: 0) |> Path.to_svg() ==
+               "M0 4L10 4M0 8L10 8"
+
+      assert square |> Path.hatch(:parallel, 5) |> Path.to_svg() ==
+               "M7.07 0L10 2.93M0 0L10 10M0 7.07L2.93 10"
+    end
+
+    test "adds lines at right angles for a cross-hatch", %{square: square} do
+      assert square |> Path.hatch(:cross, 4, angle: 0) |> Path.to_svg() ==
+               "M0 4L10 4M0 8L10 8M8 0L8 10M4 0L4 10"
+    end
+
+    test "staggers rows of dots for a stipple", %{square: square} do
+      assert square |> Path.hatch(:stipple, 4, angle: 0) |> Path.to_svg() ==
+               "M2 4L2 4M6 4L6 4M4 8L4 8M8 8L8 8"
+    end
+
+    test "draws insets of the path for concentric contours", %{square: square} do
+      assert square |> Path.hatch(:concentric, 2) |> Path.to_svg() ==
+               "M2 2L8 2 8 8 2 8ZM4 4L6 4 6 6 4 6Z"
+    end
+
+    test "clips the hatching to the area the fill rule fills" do
+      ring = Path.from_svg("M0 0H10V10H0ZM3 3H7V7H3Z")
+
+      assert ring |> Path.hatch(:parallel, 2, angle: 0, fill_rule: :even_odd) |> Path.to_svg() ==
+               "M0 2L10 2M0 4L3 4M7 4L10 4M0 6L3 6M7 6L10 6M0 8L10 8"
+
+      assert ring |> Path.hatch(:parallel, 2, angle: 0) |> Path.to_svg() ==
+               "M0 2L10 2M0 4L10 4M0 6L10 6M0 8L10 8"
+    end
+
+    test "returns an error for a spacing too small to hatch with", %{square: square} do
+      assert Path.hatch(square, :parallel, 0) == {:error, :invalid_size}
+      assert Path.hatch(square, :cross, 1.0e-4) == {:error, :invalid_size}
+      assert Path.hatch(square, :stipple, 0.01) == {:error, :invalid_size}
+      assert Path.hatch(square, :concentric, -1) == {:error, :invalid_size}
+    end
+  end
+
+  describe "simplify/2" do
+    test "removes points within the tolerance of the simplified lines" do
+      path = Path.from_svg("M0 0L5 0.1 10 0 10 10 5 10.1 0 10Z")
+
+      assert path |> Path.simplify(0.5) |> Path.to_svg() == "M0 0L10 0 10 10 0 10Z"
+      assert path |> Path.simplify(0.01) |> Path.equal?(path)
+    end
+
+    test "leaves curves as they are" do
+      path = Path.from_svg("M0 0L5 0.1 10 0C10 5 5 10 0 10L0 5Z")
+
+      assert path |> Path.simplify(0.5) |> Path.to_svg() == "M0 0L10 0C10 5 5 10 0 10Z"
+    end
+  end
+
+  describe "smooth/2" do
+    test "replaces lines with curves through the same points" do
+      path = Path.from_svg("M0 0L10 0 10 10 0 10Z")
+
+      assert path |> Path.smooth() |> Path.to_svg() ==
+               "M0 0C1.668-1.668 8.332-1.668 10 0 11.668 1.668 11.668 8.332 10 10 " <>
+                 "8.332 11.668 1.668 11.668 0 10-1.668 8.332-1.668 1.668 0 0Z"
+    end
+
+    test "leaves a single line straight" do
+      assert Path.from_svg("M0 0L10 0") |> Path.smooth() |> Path.to_svg() ==
+               "M0 0C3.332 0 6.668 0 10 0"
+    end
+  end
+
+  describe "in_fill/3" do
+    test "respects the fill rule" do
+      # two squares drawn in the same direction, one inside the other
+      path = Path.from_svg("M0 0H30V30H0ZM10 10H20V20H10Z")
+
+      assert Path.in_fill(path, {15, 15})
+      refute Path.in_fill(path, {15, 15}, fill_rule: :even_odd)
+      assert Path.in_fill(path, {5, 5}, fill_rule: :even_odd)
+    end
+
+    test "includes points on the outline" do
+      square = Path.from_svg("M0 0H10V10H0Z")
+
+      assert Path.in_fill(square, {10, 5})
+      refute Path.in_fill(square, {10.01, 5})
+    end
+
+    test "closes open subpaths" do
+      assert Path.in_fill(Path.from_svg("M0 0H10V10"), {8, 2})
+    end
+  end
+
+  describe "in_stroke/3" do
+    test "respects the stroke options" do
+      corner = Path.from_svg("M0 0H10V10")
+
+      assert Path.in_stroke(corner, {10.9, -0.9})
+      refute Path.in_stroke(corner, {10.9, -0.9}, line_join: :round)
+      refute Path.in_stroke(corner, {-0.5, 0})
+      assert Path.in_stroke(corner, {-0.5, 0}, line_cap: :square)
+      refute Path.in_stroke(corner, {3, 0}, dashes: [2, 2])
+    end
+
+    test "returns an error for an invalid dash pattern" do
+      assert Path.in_stroke(Path.from_svg("M0 0H10"), {0, 0}, dashes: [-1]) ==
+               {:error, :invalid_dash}
+    end
+  end
+
+  describe "extents/1" do
+    test "returns the exact bounds of curves" do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+      {top_left, bottom_right} = Path.extents(circle)
+
+      assert_in_delta top_left.y, 0, 1.0e-9
+      assert_in_delta bottom_right.y, 100, 1.0e-9
+      assert_in_delta top_left.x, 0, 1.0e-9
+      assert_in_delta bottom_right.x, 100, 1.0e-9
+    end
+
+    test "returns nil for an empty path" do
+      assert Path.extents(Path.new([])) == nil
+    end
+  end
+
+  describe "stroke_extents/2" do
+    test "includes the line width, caps and joins" do
+      corner = Path.from_svg("M0 0H10V10")
+
+      assert Path.stroke_extents(corner) == {Point.new(0, -1), Point.new(11, 10)}
+
+      assert Path.stroke_extents(corner, line_cap: :square, line_width: 4) ==
+               {Point.new(-2, -2), Point.new(12, 12)}
+    end
+
+    test "returns nil when nothing would be painted" do
+      assert Path.stroke_extents(Path.from_svg("M0 0H10"), line_width: 0) == nil
+    end
+  end
+
+  describe "interpolate/3" do
+    setup do
+      [square: Path.from_svg("M0 0H10V10H0Z")]
+    end
+
+    test "starts and ends at the given paths", %{square: square} do
+      triangle = Path.from_svg("M0 0L10 0 5 10Z")
+
+      assert square |> Path.interpolate(triangle, 0) |> Path.to_svg() ==
+               "M0 0L10 0 10 10 0 10Z"
+
+      assert square |> Path.interpolate(triangle, 0.5) |> Path.to_svg() ==
+               "M0 0L10 0 8.75 7.5 2.5 10Z"
+
+      assert square |> Path.interpolate(triangle, 1) |> Path.to_svg() ==
+               "M0 0L10 0 7.5 5 5 10Z"
+    end
+
+    test "grows subpaths missing from the first path", %{square: square} do
+      squares = Path.from_svg("M0 0H10V10H0ZM20 0H30V10H20Z")
+
+      assert square |> Path.interpolate(squares, 0.5) |> Path.to_svg() ==
+               "M0 0L10 0 10 10 0 10ZM20 0L25 0 25 5 20 5Z"
+    end
+
+    test "turns lines paired with curves into curves", %{square: square} do
+      circle = Path.from_svg("M0 50A50 50 0 0 1 100 50A50 50 0 0 1 0 50Z")
+
+      assert square |> Path.interpolate(circle, 0.5) |> Path.to_svg() ==
+               "M0 25C1.668 11.195 14.527 0 30 0 43.805 1.668 55 14.527 55 30 " <>
+                 "53.332 43.805 40.473 55 25 55 11.195 53.332 0 40.473 0 25Z"
+    end
+  end
+
+  describe "regular_polygon/4" do
+    test "places the first corner at the given rotation" do
+      assert Path.regular_polygon({50, 50}, 10, 4, rotation: :math.pi() / 4) |> Path.to_svg() ==
+               "M57.07 57.07L42.93 57.07 42.93 42.93 57.07 42.93Z"
+    end
+
+    test "returns an empty path for fewer than 3 sides" do
+      assert Path.regular_polygon({50, 50}, 10, 2) |> Path.segment_count() == 0
+    end
+
+    test "returns an error for too many sides or points" do
+      assert Path.regular_polygon({50, 50}, 10, 65_537) == {:error, :invalid_size}
+      assert Path.star({50, 50}, 10, 5, 4_294_967_295) == {:error, :invalid_size}
+    end
+  end
+
+  describe "superellipse/4" do
+    test "draws squarer shapes for larger exponents" do
+      circle = Path.superellipse({0, 0}, {10, 10}, 2)
+      squircle = Path.superellipse({0, 0}, {10, 10}, 4)
+
+      refute Path.in_fill(circle, {8, 8})
+      assert Path.in_fill(squircle, {8, 8})
+    end
+
+    test "returns an empty path for an exponent that is not positive" do
+      assert Path.superellipse({0, 0}, {10, 10}, 0) |> Path.segment_count() == 0
+    end
+  end
+
+  describe "spiral/4" do
+    test "runs from the inner to the outer radius" do
+      svg = Path.spiral({0, 0}, {0, 10}, 1, tolerance: 1) |> Path.to_svg()
+
+      assert String.starts_with?(svg, "M0 0L0.578 0.238 ")
+      assert String.ends_with?(svg, " 10 0")
+    end
+
+    test "runs the other way for a negative number of turns" do
+      svg = Path.spiral({0, 0}, {0, 10}, -1, tolerance: 1) |> Path.to_svg()
+
+      assert String.starts_with?(svg, "M0 0L0.578-0.238 ")
+    end
+
+    test "returns an error for too many turns" do
+      assert Path.spiral({0, 0}, {0, 10}, 5000) == {:error, :invalid_size}
+      assert Path.spiral({0, 0}, {0, 10}, 4096, tolerance: 0) |> is_struct(Path)
+    end
+  end
+
+  describe "rose/4" do
+    test "draws 2k petals for an even k" do
+      rose = Path.rose({0, 0}, 10, 2)
+
+      for point <- [{5, 0}, {0, 5}, {-5, 0}, {0, -5}], do: assert(Path.in_fill(rose, point))
+      refute Path.in_fill(rose, {5, 5})
+    end
+
+    test "takes k as a fraction" do
+      assert Path.rose({0, 0}, 10, {4, 2}) |> Path.to_svg() ==
+               Path.rose({0, 0}, 10, 2) |> Path.to_svg()
+    end
+
+    test "returns an error for a fraction with terms above 64" do
+      assert Path.rose({0, 0}, 10, {65, 1}) == {:error, :invalid_size}
+      assert Path.rose({0, 0}, 10, {1, 1000}) == {:error, :invalid_size}
+      assert Path.rose({0, 0}, 10, {128, 2}) |> is_struct(Path)
+    end
+  end
+
+  describe "markers/2" do
+    test "points start and end arrows outwards" do
+      path = Path.from_svg("M0 0H10")
+
+      assert path |> Path.markers(start: :arrow, end: :arrow) |> Path.to_svg() ==
+               "M-2 0L6 4 6-4ZM12 0L4-4 4 4Z"
+    end
+
+    test "turns mid markers halfway between the segments at a vertex" do
+      path = Path.from_svg("M0 0H10V10")
+
+      assert path |> Path.markers(mid: :square, line_width: 1) |> Path.to_svg() ==
+               "M10-2.828L12.828 0 10 2.828 7.172 0Z"
+    end
+
+    test "follows the tangent at the end of a curve" do
+      path = Path.from_svg("M0 0C10 0 10 10 10 20")
+
+      assert path |> Path.markers(end: :arrow, line_width: 1) |> Path.to_svg() ==
+               "M10 21L12 17 8 17Z"
+    end
+
+    test "places custom markers" do
+      path = Path.from_svg("M0 0V10")
+      marker = Path.from_svg("M0 0H1")
+
+      assert path |> Path.markers(end: marker, line_width: 1) |> Path.to_svg() == "M0 10L0 14"
+    end
+  end
+
+  describe "brush_stroke/3" do
+    setup do
+      [line: Path.from_svg("M0 0H20")]
+    end
+
+    test "matches a constant-width stroke for a single width", %{line: line} do
+      assert line |> Path.brush_stroke([{0, 2}], line_cap: :butt) |> Path.to_svg() ==
+               "M0-1L20-1 20 1 0 1Z"
+
+      assert line |> Path.brush_stroke([2], line_cap: :square) |> Path.to_svg() ==
+               "M-1-1L21-1 21 1-1 1Z"
+    end
+
+    test "places width stops along the subpath", %{line: line} do
+      stroke =
+        Path.brush_stroke(line, [{0, 2}, {0.5, 6}, {1, 2}], line_cap: :butt, tolerance: 0.5)
+
+      assert Path.to_svg(stroke) ==
+               "M8.5-2.598L11.5-2.598 20-1 20 1 11.5 2.598 8.5 2.598 0 1 0-1Z"
+    end
+
+    test "takes a width for each point of the path" do
+      path = Path.from_svg("M0 0H10V10")
+
+      stroke = Path.brush_stroke(path, [2, 6, 2], line_cap: :butt, tolerance: 0.5)
+
+      assert Path.to_svg(stroke) ==
+               "M8.5-2.598L11.5-2.598 13 0 11 10 9 10 7.48 2.406 0 1 0-1Z"
+    end
+
+    test "eases between widths when smoothed", %{line: line} do
+      linear = Path.brush_stroke(line, [{0, 0}, {1, 8}])
+      smooth = Path.brush_stroke(line, [{0, 0}, {1, 8}], smooth: true)
+
+      assert Path.in_fill(linear, {5, 0.8})
+      refute Path.in_fill(smooth, {5, 0.8})
+    end
+
+    test "bounds the number of lines curves and circles are split into with a tolerance of 0" do
+      curve = Path.from_svg("M0 0C50-50 100 50 150 0")
+
+      stroke = Path.brush_stroke(curve, [10, 2], smooth: true, tolerance: 0)
+
+      assert is_struct(stroke, Path)
+      assert Path.in_fill(stroke, {0, 0})
+      assert Path.in_fill(stroke, {150, 0})
+    end
+  end
 end
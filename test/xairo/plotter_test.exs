@@ -0,0 +1,93 @@
+defmodule Xairo.PlotterTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Path, Plotter}
+
+  doctest Plotter
+
+  setup do
+    [
+      lines: Path.from_svg("M10 0H20M0 0H5"),
+      square: Path.from_svg("M20 20H30V30H20Z")
+    ]
+  end
+
+  describe "to_hpgl/2" do
+    test "orders strokes to cut down pen-up travel", %{lines: lines, square: square} do
+      assert Plotter.to_hpgl([lines, square]) ==
+               "IN;SP1;\n" <>
+                 "PU0,0;PD200,0;\n" <>
+                 "PU400,0;PD800,0;\n" <>
+                 "PU800,800;PD1200,800,1200,1200,800,1200,800,800;\n" <>
+                 "PU;SP0;\n"
+    end
+
+    test "keeps the given order, and flips the page, if asked", %{lines: lines, square: square} do
+      hpgl =
+        Plotter.to_hpgl([lines, square],
+          optimize: false,
+          page_height: 100,
+          pen: 2,
+          velocity: 10
+        )
+
+      assert hpgl ==
+               "IN;SP2;VS10;\n" <>
+                 "PU400,4000;PD800,4000;\n" <>
+                 "PU0,4000;PD200,4000;\n" <>
+                 "PU800,3200;PD1200,3200,1200,2800,800,2800,800,3200;\n" <>
+                 "PU;SP0;\n"
+    end
+  end
+
+  describe "to_gcode/2" do
+    test "raises and lowers the pen between strokes", %{lines: lines, square: square} do
+      gcode = Plotter.to_gcode([lines, square], pen_up: "M5", pen_down: "M3 S90")
+
+      assert String.split(gcode, "\n", trim: true) == [
+               "G21",
+               "G90",
+               "M5",
+               "G0 X0 Y0",
+               "M3 S90",
+               "G1 X5 Y0 F1000",
+               "M5",
+               "G0 X10 Y0",
+               "M3 S90",
+               "G1 X20 Y0",
+               "M5",
+               "G0 X20 Y20",
+               "M3 S90",
+               "G1 X30 Y20",
+               "G1 X30 Y30",
+               "G1 X20 Y30",
+               "G1 X20 Y20",
+               "M5"
+             ]
+    end
+
+    test "converts units and draws connected strokes without lifting the pen" do
+      path = Path.from_svg("M0 0H10M10 0V10")
+
+      gcode =
+        Plotter.to_gcode(path,
+          unit: :in,
+          pen_up: "G0 Z1",
+          pen_down: "G1 Z0",
+          feed_rate: 1500,
+          travel_rate: 3000
+        )
+
+      assert String.split(gcode, "\n", trim: true) == [
+               "G21",
+               "G90",
+               "G0 Z1",
+               "G1 X0 Y0 F3000",
+               "G1 Z0",
+               "G1 X254 Y0 F1500",
+               "G1 X254 Y254",
+               "G0 Z1"
+             ]
+    end
+  end
+end
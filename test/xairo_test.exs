@@ -76,6 +76,159 @@ defmodule XairoTest do
     end
   end
 
+  describe "quad_to/3 and rel_quad_to/3" do
+    test "draws quadratic curves as the equivalent cubic curves" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(0, 0))
+        |> quad_to(Point.new(10, 10), Point.new(20, 0))
+        |> rel_quad_to(Vector.new(10, -10), Vector.new(20, 0))
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M0 0C6.668 6.668 13.332 6.668 20 0 26.668-6.668 33.332-6.668 40 0"
+    end
+  end
+
+  describe "arc_to/6" do
+    test "draws an elliptical arc from the current point to the given point" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(0, 50))
+        |> arc_to({50, 50}, 0, false, true, Point.new(100, 50))
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M0 50C0 22.387 22.387 0 50 0 77.613 0 100 22.387 100 50"
+    end
+
+    test "draws a straight line when a radius is 0" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(0, 50))
+        |> arc_to({0, 50}, 0, false, true, Point.new(100, 50))
+
+      assert copy_path(image) |> Xairo.Path.to_svg() == "M0 50L100 50"
+    end
+  end
+
+  describe "ellipse/4" do
+    test "draws a closed ellipse around the given center" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> ellipse(Point.new(50, 50), 20, 10)
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M70 50C70 55.523 61.047 60 50 60 38.953 60 30 55.523 30 50 " <>
+                 "30 44.477 38.953 40 50 40 61.047 40 70 44.477 70 50Z"
+    end
+  end
+
+  describe "rounded_rectangle/5" do
+    test "rounds each corner by its own radius" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> rounded_rectangle(Point.new(0, 0), 100, 50, {10, 0, 20, 5})
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M10 0L100 0 100 30C100 41.047 91.047 50 80 50L5 50C2.238 50 0 47.762 0 45" <>
+                 "L0 10C0 4.477 4.477 0 10 0Z"
+    end
+
+    test "scales down radii that do not fit" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> rounded_rectangle(Point.new(0, 0), 100, 50, 50)
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M25 0L75 0C88.809 0 100 11.191 100 25 100 38.809 88.809 50 75 50L25 50" <>
+                 "C11.191 50 0 38.809 0 25 0 11.191 11.191 0 25 0Z"
+    end
+  end
+
+  describe "regular_polygon/5 and star/6" do
+    test "append the shapes to the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> regular_polygon(Point.new(50, 50), 10, 4)
+        |> star(Point.new(0, 0), 10, 5, 4)
+
+      assert copy_path(image) |> Xairo.Path.to_svg() ==
+               "M60 50L50 60 40 50 50 40ZM10 0L3.535 3.535 0 10-3.535 3.535-10 0-3.535-3.535 " <>
+                 "0-10 3.535-3.535Z"
+    end
+  end
+
+  describe "fill_markers/2" do
+    test "preserves the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(10, 50))
+        |> line_to(Point.new(90, 50))
+        |> fill_markers(start: :circle, end: :arrow)
+
+      assert copy_path(image) |> Xairo.Path.to_svg() == "M10 50L90 50"
+    end
+  end
+
+  describe "sketch_stroke/3" do
+    test "preserves the current path" do
+      image =
+        Image.new("test.png", 100, 100)
+        |> move_to(Point.new(10, 50))
+        |> line_to(Point.new(90, 50))
+        |> sketch_stroke(Xairo.Path.from_svg("M10 10H90V90Z"))
+
+      assert copy_path(image) |> Xairo.Path.to_svg() == "M10 50L90 50"
+    end
+
+    test "draws the same strokes for the same seed" do
+      path = Xairo.Path.from_svg("M10 10H90C90 50 50 90 10 90Z")
+
+      sketch = fn filename, seed ->
+        image =
+          Image.new(filename, 100, 100)
+          |> set_source(Rgba.new(0, 0, 0))
+          |> sketch_stroke(path, seed: seed, roughness: 2)
+
+        :ok = Image.save(image)
+        contents = File.read!(filename)
+        :ok = File.rm(filename)
+        contents
+      end
+
+      assert sketch.("sketch_a.png", 7) == sketch.("sketch_b.png", 7)
+      assert sketch.("sketch_a.png", 7) != sketch.("sketch_b.png", 8)
+    end
+  end
+
+  describe "sketch_fill/3" do
+    test "draws the same hachure for the same seed" do
+      path = Xairo.Path.from_svg("M10 10H90V90H10ZM30 30V70H70V30Z")
+
+      sketch = fn filename, seed ->
+        image =
+          Image.new(filename, 100, 100)
+          |> set_source(Rgba.new(0, 0, 0))
+          |> sketch_fill(path, seed: seed, hachure_gap: 6)
+
+        :ok = Image.save(image)
+        contents = File.read!(filename)
+        :ok = File.rm(filename)
+        contents
+      end
+
+      assert sketch.("sketch_a.png", 3) == sketch.("sketch_b.png", 3)
+      assert sketch.("sketch_a.png", 3) != sketch.("sketch_b.png", 4)
+    end
+
+    test "returns an error for a gap too small to draw" do
+      path = Xairo.Path.from_svg("M10 10H90V90H10Z")
+      image = Image.new("test.png", 100, 100)
+
+      assert sketch_fill(image, path, hachure_gap: 0) == {:error, :invalid_size}
+      assert sketch_fill(image, path, hachure_gap: 1.0e-3) == {:error, :invalid_size}
+    end
+  end
+
   describe "close_path/1" do
     test "connects the current point to the point of the most recent move_to" do
       Image.new("close_path.png", 100, 100)